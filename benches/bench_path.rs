@@ -1,30 +1,15 @@
-use bevy_math::Vec3;
 use criterion::*;
 
-use bevy_navigator::{NavGraph, NavPoint};
-
-fn create_grid(size: u32, dense: bool) -> NavGraph {
-    let mut nav_graph = NavGraph::new();
-    let mut id = 1_u32;
-    for x in 1..=size {
-        for y in 1..=size {
-            nav_graph.add_nav_point(NavPoint::new(
-                id,
-                Vec3::new(x as f32, y as f32, 0.0),
-                1.0,
-                1,
-            ));
-            nav_graph.connect_points(id, id - 1);
-            nav_graph.connect_points(id, id - size);
-            if dense {
-                nav_graph.connect_points(id, id - size - 1);
-                nav_graph.connect_points(id, id - size + 1);
-            }
-            id += 1;
-        }
-    }
-
-    nav_graph
+use bevy_math::Vec3;
+use bevy_navigator::{GridConnectivity, NavGraph};
+
+fn create_grid(size: usize, dense: bool) -> NavGraph {
+    let connectivity = if dense {
+        GridConnectivity::Eight
+    } else {
+        GridConnectivity::Four
+    };
+    NavGraph::from_grid(size, size, 1.0, connectivity).0
 }
 
 fn bench_path(c: &mut Criterion) {
@@ -84,6 +69,55 @@ fn bench_path(c: &mut Criterion) {
     large_dense_group.bench_function("short", |b| b.iter(|| large_dense.find_path(500, 2500)));
 
     large_dense_group.finish();
+
+    let mut hierarchical = create_grid(1000, false);
+    hierarchical.assign_grid_regions(16.0);
+    hierarchical.refresh_hierarchy();
+
+    let mut hierarchical_group = c.benchmark_group("Large Sparse Hierarchical Nav");
+    hierarchical_group.measurement_time(core::time::Duration::from_secs(60));
+
+    hierarchical_group.bench_function("long", |b| {
+        b.iter(|| hierarchical.find_path_hierarchical(1, 1000000))
+    });
+    hierarchical_group.bench_function("short", |b| {
+        b.iter(|| hierarchical.find_path_hierarchical(500, 2500))
+    });
+
+    hierarchical_group.finish();
+
+    let mut landmarks = create_grid(1000, false);
+    landmarks.build_landmarks(8);
+
+    let mut landmarks_group = c.benchmark_group("Large Sparse Landmark Nav");
+    landmarks_group.measurement_time(core::time::Duration::from_secs(60));
+
+    landmarks_group.bench_function("long", |b| b.iter(|| landmarks.find_path(1, 1000000)));
+    landmarks_group.bench_function("short", |b| b.iter(|| landmarks.find_path(500, 2500)));
+
+    landmarks_group.finish();
+
+    let mut nearest_group = c.benchmark_group("Large Nearest Point");
+
+    nearest_group.bench_function("inside", |b| {
+        b.iter(|| large_sparse.nearest_point(Vec3::new(412.3, 687.8, 0.0)))
+    });
+    nearest_group.bench_function("outside", |b| {
+        b.iter(|| large_sparse.nearest_point(Vec3::new(-50.0, 1200.0, 0.0)))
+    });
+
+    nearest_group.finish();
+
+    let mut radius_group = c.benchmark_group("Large Radius Query");
+
+    radius_group.bench_function("small", |b| {
+        b.iter(|| large_sparse.points_within_radius(Vec3::new(412.3, 687.8, 0.0), 5.0))
+    });
+    radius_group.bench_function("wide", |b| {
+        b.iter(|| large_sparse.points_within_radius(Vec3::new(412.3, 687.8, 0.0), 50.0))
+    });
+
+    radius_group.finish();
 }
 
 criterion_group!(benches, bench_path);
@@ -1,10 +1,14 @@
 use bevy::{
-    prelude::{App, AssetServer, Camera2dBundle, Vec3},
-    sprite::SpriteBundle,
+    prelude::{App, AssetServer, Camera2dBundle, Color},
+    sprite::{Sprite, SpriteBundle},
+    utils::HashSet,
     DefaultPlugins,
 };
-use bevy_ecs::system::{Commands, Res, ResMut};
-use bevy_navigator::{AutoTraveler, NavGraph, NavPoint, NavPointRef, NavigatorPlugin};
+use bevy_ecs::system::{Commands, Query, Res, ResMut};
+use bevy_navigator::{
+    AutoTraveler, ExpansionPlayback, GridConnectivity, NavGraph, NavPointRef, NavigatorPlugin,
+    OriginClaim, SearchOptions,
+};
 use bevy_transform::prelude::Transform;
 
 fn main() {
@@ -12,39 +16,55 @@ fn main() {
         .add_plugins(DefaultPlugins)
         .add_plugin(NavigatorPlugin::new())
         .add_startup_system(setup)
+        .add_system(draw_expansion)
         .run();
 }
 
 fn setup(mut nav_graph: ResMut<NavGraph>, asset_server: Res<AssetServer>, mut commands: Commands) {
-    commands.spawn(Camera2dBundle::default());
-    let mut id = 1;
-    for y in -20..20 {
-        for x in -20..20 {
-            let location = Vec3::new(x as f32 * 16.0, y as f32 * 16.0, 1.0);
-            commands
-                .spawn(SpriteBundle {
-                    texture: asset_server.load("tile.png"),
-                    transform: Transform::from_xyz(location.x, location.y, location.z - 1.0),
-                    ..Default::default()
-                })
-                .insert(NavPointRef(id));
-            nav_graph.add_nav_point(NavPoint::new(id, location, 1.0, 1));
-            if id > 40 {
-                nav_graph.connect_points(id, id - 40);
-            }
-            if x != -20 && id > 1 {
-                nav_graph.connect_points(id, id - 1);
-            }
-            id += 1;
-        }
+    let mut camera = Camera2dBundle::default();
+    camera.transform.translation.x = 19.5 * 16.0;
+    camera.transform.translation.y = 19.5 * 16.0;
+    commands.spawn(camera);
+
+    let (grid, ids) = NavGraph::from_grid(40, 40, 16.0, GridConnectivity::Four);
+    *nav_graph = grid;
+    for point in nav_graph.iter_points() {
+        let location = point.location();
+        commands
+            .spawn(SpriteBundle {
+                texture: asset_server.load("tile.png"),
+                transform: Transform::from_xyz(location.x, location.y, -1.0),
+                ..Default::default()
+            })
+            .insert(NavPointRef(point.id()));
     }
 
+    let (origin, destination) = (ids.id(0, 0).unwrap(), ids.id(39, 39).unwrap());
     commands
         .spawn(SpriteBundle {
             texture: asset_server.load("ball.png"),
-            transform: Transform::from_xyz(-20.0 * 16.0, -20.0 * 16.0, 1.0),
+            transform: Transform::from_translation(nav_graph.get(origin).unwrap().location()),
             ..Default::default()
         })
-        .insert(AutoTraveler::new(1, id - 1, 100.0));
-    nav_graph.occupy(1);
+        .insert(AutoTraveler::new(origin, destination, 100.0).with_origin_claim(OriginClaim::Fail));
+
+    // Replay the search the traveler is about to make, tinting tiles as they're expanded.
+    let recording = nav_graph.find_path_recorded(origin, destination, &SearchOptions::default());
+    commands.spawn(ExpansionPlayback::new(recording, 60.0));
+}
+
+fn draw_expansion(
+    playback_query: Query<&ExpansionPlayback>,
+    mut tiles_query: Query<(&NavPointRef, &mut Sprite)>,
+) {
+    for playback in playback_query.iter() {
+        let shown: HashSet<u32> = playback.shown().iter().map(|node| node.id).collect();
+        for (nav_point_ref, mut sprite) in tiles_query.iter_mut() {
+            sprite.color = if shown.contains(&nav_point_ref.0) {
+                Color::rgb(0.6, 0.8, 1.0)
+            } else {
+                Color::WHITE
+            };
+        }
+    }
 }
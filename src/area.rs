@@ -0,0 +1,151 @@
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_utils::HashMap;
+#[cfg(feature = "asset")]
+use serde::{Deserialize, Serialize};
+
+use crate::EdgeSpeedModel;
+
+/// How much one kind of traveler minds each area type, e.g. a cart which is slowed down by mud
+/// but speeds along roads. See [`NavPoint::area`](crate::NavPoint::area).
+///
+/// Each multiplier scales the cost of moving onto points of its area type, and divides the speed
+/// of travelers moving onto them. Like speed modifiers, the multipliers of a connection's two
+/// points combine according to the graph's [`EdgeSpeedModel`], so that paths are planned by how
+/// travelers actually move. Area types without a multiplier count as 1.0. Multipliers must be
+/// positive; smaller ones count as the smallest positive `f32`.
+///
+/// ## Example
+/// ```
+/// # use bevy_math::Vec3;
+/// # use bevy_navigator::{AreaCosts, NavGraph, NavPoint, SearchOptions};
+/// const ROAD: u32 = 1;
+/// const GRASS: u32 = 2;
+/// let mut nav_graph = NavGraph::new();
+/// for (id, x, y) in [(1, 0.0, 0.0), (2, 2.0, 0.0), (3, 2.0, 1.0), (4, 4.0, 0.0)] {
+///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 1));
+/// }
+/// nav_graph.modify(2, |point| point.set_area(GRASS));
+/// nav_graph.modify(3, |point| point.set_area(ROAD));
+/// for (a, b) in [(1, 2), (2, 4), (1, 3), (3, 4)] {
+///     nav_graph.connect_points(a, b);
+/// }
+///
+/// // A scout cuts across the grass, while a cart keeps to the road.
+/// assert_eq!(nav_graph.find_path(1, 4), Some(vec![1, 2, 4]));
+/// let cart = AreaCosts::new().with(ROAD, 0.5).with(GRASS, 3.0);
+/// let options = SearchOptions::new().with_area_costs(cart);
+/// assert_eq!(nav_graph.find_path_with(1, 4, &options), Some(vec![1, 3, 4]));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Reflect, FromReflect)]
+#[cfg_attr(feature = "asset", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "asset", serde(transparent))]
+pub struct AreaCosts {
+    multipliers: HashMap<u32, f32>,
+}
+
+impl AreaCosts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, area: u32, multiplier: f32) -> Self {
+        self.set(area, multiplier);
+        self
+    }
+
+    pub fn set(&mut self, area: u32, multiplier: f32) {
+        self.multipliers.insert(area, multiplier);
+    }
+
+    /// The multiplier for `area`, or 1.0 if it has none.
+    #[inline(always)]
+    pub fn multiplier(&self, area: u32) -> f32 {
+        self.multipliers.get(&area).copied().unwrap_or(1.0)
+    }
+
+    /// The multiplier for moving along a connection from a point of area type `from` to one of
+    /// area type `to`, combined the way `model` combines speed modifiers.
+    #[inline(always)]
+    pub fn edge_multiplier(&self, model: EdgeSpeedModel, from: u32, to: u32) -> f32 {
+        1.0 / model.effective(self.pace(from), self.pace(to))
+    }
+
+    /// What the speed of a traveler `progress` of the way along a connection from a point of
+    /// area type `from` to one of area type `to` is multiplied by, as in
+    /// [`EdgeSpeedModel::at`].
+    #[inline(always)]
+    pub fn speed_factor(&self, model: EdgeSpeedModel, from: u32, to: u32, progress: f32) -> f32 {
+        model.at(self.pace(from), self.pace(to), progress)
+    }
+
+    /// How much faster than usual travelers move on `area`.
+    #[inline(always)]
+    fn pace(&self, area: u32) -> f32 {
+        1.0 / self.multiplier(area).max(f32::MIN_POSITIVE)
+    }
+
+    /// The smallest multiplier of any area type, including those without one, so that search
+    /// estimates scaled by it stay below the actual costs.
+    pub(crate) fn min_multiplier(&self) -> f32 {
+        self.multipliers.values().copied().fold(1.0, f32::min)
+    }
+}
+
+#[cfg(all(test, feature = "travelers"))]
+mod tests {
+    use bevy_app::App;
+    use bevy_math::Vec3;
+    use bevy_time::Time;
+    use bevy_transform::prelude::Transform;
+    use bevy_utils::Duration;
+
+    use super::*;
+    use crate::{AutoTraveler, NavGraph, NavPoint, NavigatorPlugin};
+
+    #[test]
+    pub fn test_area_multipliers_scale_movement() {
+        // Travelers head from 1 into the mud at 2, which slows them down as much as it costs.
+        const MUD: u32 = 1;
+        let mut app = App::new();
+        app.add_plugin(NavigatorPlugin::new())
+            .insert_resource(Time::default());
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        for id in 1..=2 {
+            nav_graph.add_nav_point(NavPoint::new(
+                id,
+                Vec3::new(id as f32 * 10.0, 0.0, 0.0),
+                1.0,
+                1,
+            ));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.modify(2, |point| point.set_area(MUD));
+
+        let mut spawn = |area_costs| {
+            app.world
+                .spawn((
+                    Transform::from_xyz(10.0, 0.0, 0.0),
+                    AutoTraveler::new(1, 2, 1.0)
+                        .with_occupancy_weight(0)
+                        .with_area_costs(area_costs),
+                ))
+                .id()
+        };
+        let cart = spawn(AreaCosts::new().with(MUD, 4.0));
+        let scout = spawn(AreaCosts::new());
+        let ghost = spawn(AreaCosts::new().with(MUD, 0.0));
+
+        let start = app.world.resource::<Time>().startup();
+        for seconds in [0.0, 1.0, 2.0] {
+            app.world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_secs_f32(seconds));
+            app.update();
+        }
+        let moved = |entity| app.world.get::<Transform>(entity).unwrap().translation.x - 10.0;
+        assert!((moved(scout) - 4.0 * moved(cart)).abs() < 1e-4);
+        assert!(moved(cart) > 0.0);
+        // Multipliers of 0.0 make for fast but finite movement.
+        assert!(moved(ghost).is_finite() && moved(ghost) > moved(scout));
+    }
+}
@@ -0,0 +1,290 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{
+    AddAsset, AssetEvent, AssetLoader, Assets, BoxedFuture, Error, Handle, LoadContext, LoadedAsset,
+};
+use bevy_ecs::{
+    event::EventReader,
+    system::{Commands, Res, Resource},
+};
+use bevy_math::Vec3;
+use bevy_reflect::TypeUuid;
+use serde::{Deserialize, Serialize};
+
+use crate::{GraphTransition, NavDomain, NavGraph, NavPoint, ReplaceNavGraph};
+
+/// A [`NavGraph`] authored outside code, loaded from `.navgraph.ron` files.
+///
+/// ## Example
+/// ```ron
+/// (
+///     points: [
+///         (id: 1, location: (0.0, 0.0, 0.0)),
+///         (id: 2, location: (1.0, 0.0, 0.0), max_occupancy: 2),
+///         (id: 3, location: (2.0, 0.0, 0.0), speed_modifier: 0.5),
+///     ],
+///     connections: [(1, 2), (2, 3)],
+///     weighted_connections: [(1, 3, 500)],
+///     connection_durations: [(2, 3, 4.0)],
+/// )
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, TypeUuid)]
+#[uuid = "9112c360-c6c7-4019-a2a4-108ca26feac9"]
+pub struct NavGraphAsset {
+    pub points: Vec<NavPointAsset>,
+    #[serde(default)]
+    pub connections: Vec<(u32, u32)>,
+    /// Connections as `(a, b, weight)`. See [`NavGraph::connect_points_weighted`].
+    #[serde(default)]
+    pub weighted_connections: Vec<(u32, u32, u32)>,
+    /// Fixed durations of connections listed above as `(a, b, seconds)`. See
+    /// [`NavGraph::set_connection_duration`].
+    #[serde(default)]
+    pub connection_durations: Vec<(u32, u32, f32)>,
+}
+
+/// A single point of a [`NavGraphAsset`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NavPointAsset {
+    pub id: u32,
+    pub location: Vec3,
+    #[serde(default = "default_speed_modifier")]
+    pub speed_modifier: f32,
+    #[serde(default = "default_max_occupancy")]
+    pub max_occupancy: u32,
+    /// See [`NavPoint::flags`].
+    #[serde(default)]
+    pub flags: u32,
+    /// See [`NavPoint::area`].
+    #[serde(default)]
+    pub area: u32,
+    /// See [`NavPoint::clearance`]. Unlimited if not set.
+    #[serde(default)]
+    pub clearance: Option<f32>,
+}
+
+fn default_speed_modifier() -> f32 {
+    1.0
+}
+
+fn default_max_occupancy() -> u32 {
+    1
+}
+
+impl NavGraphAsset {
+    /// Builds the graph the asset describes. Connections to points which aren't in the asset
+    /// are skipped.
+    pub fn build<M: NavDomain>(&self) -> NavGraph<M> {
+        let mut nav_graph = NavGraph::<M>::default();
+        nav_graph.reserve(self.points.len());
+        for point in &self.points {
+            let mut nav_point = NavPoint::new(
+                point.id,
+                point.location,
+                point.speed_modifier,
+                point.max_occupancy,
+            );
+            nav_point.set_flags(point.flags);
+            nav_point.set_area(point.area);
+            if let Some(clearance) = point.clearance {
+                nav_point.set_clearance(clearance);
+            }
+            nav_graph.add_nav_point(nav_point);
+        }
+        for &(a, b) in &self.connections {
+            nav_graph.connect_points(a, b);
+        }
+        for &(a, b, weight) in &self.weighted_connections {
+            nav_graph.connect_points_weighted(a, b, weight);
+        }
+        for &(a, b, duration) in &self.connection_durations {
+            nav_graph.set_connection_duration(a, b, Some(duration));
+        }
+        nav_graph
+    }
+
+    /// Describes the points and connections of `nav_graph`, e.g. to save a graph built in code
+    /// for designers to edit. Occupancy, availability and other runtime state isn't kept.
+    pub fn from_nav_graph<M: NavDomain>(nav_graph: &NavGraph<M>) -> Self {
+        let mut asset = Self::default();
+        for point in nav_graph.iter_points() {
+            asset.points.push(NavPointAsset {
+                id: point.id(),
+                location: point.location(),
+                speed_modifier: point.speed_modifier(),
+                max_occupancy: point.max_occupancy(),
+                flags: point.flags(),
+                area: point.area(),
+                clearance: point.clearance().is_finite().then(|| point.clearance()),
+            });
+            for &other in point
+                .connections()
+                .iter()
+                .filter(|other| point.id() < **other)
+            {
+                let edge = nav_graph.edge(point.id(), other);
+                match edge.and_then(|edge| edge.weight) {
+                    Some(weight) => asset.weighted_connections.push((point.id(), other, weight)),
+                    None => asset.connections.push((point.id(), other)),
+                }
+                if let Some(duration) = edge.and_then(|edge| edge.duration) {
+                    asset
+                        .connection_durations
+                        .push((point.id(), other, duration));
+                }
+            }
+        }
+        asset.points.sort_by_key(|point| point.id);
+        asset.connections.sort_unstable();
+        asset.weighted_connections.sort_unstable();
+        asset
+            .connection_durations
+            .sort_unstable_by_key(|&(a, b, _)| (a, b));
+        asset
+    }
+}
+
+/// Loads [`NavGraphAsset`]s from `.navgraph.ron` files.
+#[derive(Debug, Default)]
+pub struct NavGraphLoader;
+
+impl AssetLoader for NavGraphLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let asset: NavGraphAsset = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(asset));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["navgraph.ron"]
+    }
+}
+
+/// The [`NavGraphAsset`] the domain `M` navigates.
+///
+/// Whenever the asset finishes loading, or is changed on disk with hot reloading enabled, its
+/// graph replaces the [`NavGraph<M>`] resource through [`ReplaceNavGraph`], using `transition`.
+/// Inserting a different handle, e.g. on a level change, swaps in its graph the same way.
+///
+/// ## Example
+/// ```
+/// # use bevy_asset::AssetServer;
+/// # use bevy_ecs::system::{Commands, Res};
+/// # use bevy_navigator::NavGraphSource;
+/// fn load_level(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     let handle = asset_server.load("levels/town.navgraph.ron");
+///     commands.insert_resource(NavGraphSource::<()>::new(handle));
+/// }
+/// ```
+#[derive(Debug, Resource)]
+pub struct NavGraphSource<M: NavDomain = ()> {
+    pub handle: Handle<NavGraphAsset>,
+    pub transition: GraphTransition,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> NavGraphSource<M> {
+    pub fn new(handle: Handle<NavGraphAsset>) -> Self {
+        Self {
+            handle,
+            transition: GraphTransition::default(),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn with_transition(mut self, transition: GraphTransition) -> Self {
+        self.transition = transition;
+        self
+    }
+}
+
+/// Registers [`NavGraphAsset`] and its loader, and keeps the [`NavGraph<M>`] in sync with the
+/// [`NavGraphSource<M>`], if any.
+///
+/// Needs Bevy's `AssetPlugin` to be added first. Add one per [`NavDomain`] loading graphs from
+/// assets.
+pub struct NavGraphAssetPlugin<M: NavDomain = ()> {
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> Default for NavGraphAssetPlugin<M> {
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: NavDomain> Plugin for NavGraphAssetPlugin<M> {
+    fn build(&self, app: &mut App) {
+        // Domains share the asset type, so only the first plugin registers it.
+        if !app.world.contains_resource::<Assets<NavGraphAsset>>() {
+            app.add_asset::<NavGraphAsset>()
+                .init_asset_loader::<NavGraphLoader>();
+        }
+        app.add_system(swap_loaded_nav_graph::<M>);
+    }
+}
+
+pub(crate) fn swap_loaded_nav_graph<M: NavDomain>(
+    source: Option<Res<NavGraphSource<M>>>,
+    mut asset_events: EventReader<AssetEvent<NavGraphAsset>>,
+    assets: Res<Assets<NavGraphAsset>>,
+    mut commands: Commands,
+) {
+    let Some(source) = source else {
+        asset_events.clear();
+        return;
+    };
+
+    // Every event is read, so none are left over for the next frame.
+    let mut loaded = false;
+    for event in asset_events.iter() {
+        if let AssetEvent::Created { handle } | AssetEvent::Modified { handle } = event {
+            loaded |= *handle == source.handle;
+        }
+    }
+    if !loaded && !source.is_changed() {
+        return;
+    }
+    if let Some(asset) = assets.get(&source.handle) {
+        commands.add(ReplaceNavGraph::new(asset.build::<M>()).with_transition(source.transition));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_asset_round_trip() {
+        let asset: NavGraphAsset = ron::from_str(
+            "(
+                points: [
+                    (id: 1, location: (0.0, 0.0, 0.0)),
+                    (id: 2, location: (1.0, 0.0, 0.0), max_occupancy: 2),
+                    (id: 3, location: (2.0, 0.0, 0.0), speed_modifier: 0.5),
+                ],
+                connections: [(1, 2), (2, 3)],
+                weighted_connections: [(1, 3, 50)],
+                connection_durations: [(2, 3, 4.0)],
+            )",
+        )
+        .unwrap();
+
+        let nav_graph: NavGraph = asset.build();
+        assert_eq!(nav_graph.get_nav_point(2).unwrap().max_occupancy(), 2);
+        assert_eq!(nav_graph.find_path(1, 3).unwrap()[..], [1, 3]);
+        // The slow ride from 2 to 3 makes going back round through 1 cheaper.
+        assert_eq!(nav_graph.connection_duration(2, 3), Some(4.0));
+        assert_eq!(nav_graph.find_path(2, 3).unwrap()[..], [2, 1, 3]);
+        assert_eq!(NavGraphAsset::from_nav_graph(&nav_graph), asset);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{NavDomain, NavGraph, PathPolicy, SearchOptions};
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Computes a path like [`NavGraph::find_path_with`], checking the search along the way in
+    /// debug builds.
+    ///
+    /// With debug assertions enabled, the search is run twice and panics if the two results
+    /// differ, which would make replays and lockstep networking diverge. Unless `options` include
+    /// a schedule, goal tolerance, node limit or turn penalty, the result is also checked against
+    /// an exhaustive search: it panics if the path costs more than the cheapest one, or than
+    /// `epsilon` times that, as happens with an inadmissible heuristic, or if a path was found or
+    /// missed when it shouldn't have been.
+    ///
+    /// In release builds this is exactly [`NavGraph::find_path_with`], so it's meant for catching
+    /// problems during development rather than in shipped games.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint, SearchOptions};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(2.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    ///
+    /// let path = nav_graph.find_path_checked(1, 3, &SearchOptions::default());
+    /// assert_eq!(path.unwrap()[..], [1, 2, 3]);
+    /// ```
+    pub fn find_path_checked(&self, a: u32, b: u32, options: &SearchOptions) -> Option<Vec<u32>> {
+        if !cfg!(debug_assertions) {
+            return self.find_path_with(a, b, options);
+        }
+
+        let result = self.observe(
+            a,
+            b,
+            || self.search(a, b, options),
+            |(path, cost)| (path.path().len(), Some(*cost)),
+        );
+        let repeated = self.search(a, b, options);
+        assert_eq!(
+            result, repeated,
+            "Nondeterministic search from {} to {}",
+            a, b
+        );
+
+        let exhaustive = options.schedule.is_none()
+            && options.goal_tolerance == 0
+            && options.max_nodes.is_none()
+            && options.max_expanded.is_none()
+            && options.turn_penalty <= 0.0
+            && options.path_policy == PathPolicy::CompleteOnly;
+        if exhaustive && a != b && self.has_nav_point(a) {
+            let cheapest = self.cost_map(a, u32::MAX, Some(options)).get(&b).copied();
+            let epsilon = options.epsilon.unwrap_or(1.0).max(1.0) as f64;
+            match (&result, cheapest) {
+                (Some((path, cost)), Some(cheapest)) => assert!(
+                    *cost as f64 <= cheapest as f64 * epsilon,
+                    "Suboptimal search from {} to {}: {:?} costs {}, but the cheapest path costs {}",
+                    a,
+                    b,
+                    path,
+                    cost,
+                    cheapest
+                ),
+                (Some((path, _)), None) => {
+                    panic!("Search from {} to {} found {:?}, but {} is unreachable", a, b, path, b)
+                }
+                (None, Some(cheapest)) => panic!(
+                    "Search from {} to {} found nothing, but a path costing {} exists",
+                    a, b, cheapest
+                ),
+                (None, None) => {}
+            }
+        }
+
+        result.map(|(path, _)| path.into_path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::{GridConnectivity, Heuristic, NavPoint};
+
+    #[test]
+    #[should_panic(expected = "Suboptimal search")]
+    pub fn test_checked_search_catches_suboptimal_paths() {
+        // Two routes from 1 to 4, where the one through 3 looks worse to the legacy heuristic
+        // than it is, thanks to its cheap explicit weights.
+        let mut nav_graph = NavGraph::new();
+        for (id, x, y) in [(1, 0.0, 0.0), (2, 2.0, 0.0), (3, 0.0, 4.0), (4, 4.0, 0.0)] {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 1));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 4);
+        nav_graph.connect_points_weighted(1, 3, 1);
+        nav_graph.connect_points_weighted(3, 4, 1);
+
+        // Fine while the search and the exhaustive check agree...
+        let options = SearchOptions {
+            heuristic: Some(Heuristic::SquaredLegacy),
+            ..SearchOptions::default()
+        };
+        let excluding = options.clone().excluding([3]);
+        assert_eq!(
+            nav_graph.find_path_checked(1, 4, &excluding).unwrap()[..],
+            [1, 2, 4]
+        );
+        // ...but an inadmissible heuristic misleads the search.
+        nav_graph.find_path_checked(1, 4, &options);
+    }
+
+    #[test]
+    pub fn test_checked_search_allows_turn_penalty_and_epsilon() {
+        let (nav_graph, _) = NavGraph::<()>::from_grid(4, 4, 1.0, GridConnectivity::Four);
+        let turning = SearchOptions {
+            turn_penalty: 2.0,
+            ..SearchOptions::default()
+        };
+        assert!(nav_graph.find_path_checked(1, 16, &turning).is_some());
+        let greedy = SearchOptions {
+            epsilon: Some(3.0),
+            ..SearchOptions::default()
+        };
+        assert!(nav_graph.find_path_checked(1, 16, &greedy).is_some());
+    }
+}
@@ -0,0 +1,382 @@
+use bevy_ecs::system::Resource;
+use bevy_reflect::{FromReflect, Reflect};
+
+use crate::{NavDomain, NavGraph, SearchOptions};
+
+/// The game clock that [`Availability`] windows are measured against.
+///
+/// The navigator never advances this itself, since game time rarely matches real time (it may be
+/// paused, sped up, or count in-game hours). Update `now` from your own clock each frame.
+#[derive(Debug, Default, Clone, Copy, Resource, Reflect, FromReflect)]
+pub struct NavClock {
+    pub now: f64,
+}
+
+impl NavClock {
+    pub fn new(now: f64) -> Self {
+        Self { now }
+    }
+
+    pub fn advance(&mut self, delta: f64) {
+        self.now += delta;
+    }
+}
+
+/// A window of [`NavClock`] time during which a point or connection can be used.
+///
+/// The window is open from `opens` (inclusive) until `closes` (exclusive). With a `period`, the
+/// window repeats every `period` units of time, e.g. shop hours that repeat daily.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, FromReflect)]
+pub struct Availability {
+    pub opens: f64,
+    pub closes: f64,
+    pub period: Option<f64>,
+}
+
+impl Availability {
+    /// A window which is open once, from `opens` until `closes`.
+    pub fn between(opens: f64, closes: f64) -> Self {
+        Self {
+            opens,
+            closes,
+            period: None,
+        }
+    }
+
+    /// A window which is open from `opens` until `closes`, and again every `period` after that.
+    pub fn repeating(opens: f64, closes: f64, period: f64) -> Self {
+        Self {
+            opens,
+            closes,
+            period: Some(period),
+        }
+    }
+
+    /// Returns true if the window is open at `time`.
+    pub fn is_open(&self, time: f64) -> bool {
+        let time = self.fold(time);
+        time >= self.opens && time < self.closes
+    }
+
+    /// The earliest time at or after `time` that the window is open, or `None` if it never opens
+    /// again.
+    pub fn next_open(&self, time: f64) -> Option<f64> {
+        if self.is_open(time) {
+            return Some(time);
+        }
+
+        match self.period {
+            Some(period) if period > 0.0 => {
+                let folded = self.fold(time);
+                let wait = if folded < self.opens {
+                    self.opens - folded
+                } else {
+                    self.opens + period - folded
+                };
+                Some(time + wait)
+            }
+            _ => (time < self.opens).then_some(self.opens),
+        }
+    }
+
+    /// Maps `time` into the first period of a repeating window.
+    fn fold(&self, time: f64) -> f64 {
+        match self.period {
+            Some(period) if period > 0.0 && time >= self.opens => {
+                self.opens + (time - self.opens) % period
+            }
+            _ => time,
+        }
+    }
+}
+
+/// How a search treats points and connections that are closed when they would be reached.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+pub enum AvailabilityMode {
+    /// Closed points and connections are treated as impassable.
+    #[default]
+    Avoid,
+    /// The path may wait for closed points and connections to open, such as a drawbridge which
+    /// will be lowered shortly. Time spent waiting adds to the path's cost.
+    Wait,
+}
+
+/// When and how fast a search expects the traveler to move, so [`Availability`] windows can be
+/// checked against the time each point would be reached.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Schedule {
+    /// The [`NavClock`] time the path starts at.
+    pub departure: f64,
+    /// The traveler's speed, as used by [`AutoTraveler::speed`](crate::AutoTraveler::speed).
+    pub speed: f32,
+    pub mode: AvailabilityMode,
+}
+
+/// Attributes of a single connection between two [`NavPoint`](crate::NavPoint)s.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect, FromReflect)]
+pub struct NavEdge {
+    pub availability: Option<Availability>,
+    /// Travelers won't move along a closed connection, waiting for it to open instead.
+    ///
+    /// Unlike an [`Availability`] window, searches still path through closed connections, since
+    /// they're expected to open again, e.g. the connections to a [`NavPlatform`](crate::NavPlatform)
+    /// which is elsewhere.
+    pub closed: bool,
+    pub traversal: Traversal,
+    /// The cost of moving along the connection in either direction, in place of the cost
+    /// derived from the points' locations and speed modifiers. See
+    /// [`NavGraph::connect_points_weighted`].
+    pub weight: Option<u32>,
+    /// The seconds travelers take to move along the connection, regardless of its length or
+    /// their speed, e.g. an elevator ride or a ferry crossing. See
+    /// [`NavGraph::set_connection_duration`].
+    pub duration: Option<f32>,
+}
+
+/// How a connection is traversed, for travelers which move or animate differently along some
+/// connections.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+pub enum Traversal {
+    #[default]
+    Walk,
+    /// A jump across a gap, e.g. as bridged by [`WalkabilityMap::max_gap`](crate::WalkabilityMap).
+    Jump,
+}
+
+/// The key connection attributes are stored under, the same in either direction.
+#[inline(always)]
+pub(crate) fn edge_key(a: u32, b: u32) -> (u32, u32) {
+    (a.min(b), a.max(b))
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Limits when the specified point can be used. Passing `None` makes it always available.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{Availability, AvailabilityMode, NavGraph, NavPoint, SearchOptions};
+    /// let mut nav_graph = NavGraph::new();
+    /// for i in 1..=3 {
+    ///     nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    ///
+    /// // A shop which opens at 9 and closes at 17.
+    /// nav_graph.set_point_availability(2, Some(Availability::between(9.0, 17.0)));
+    ///
+    /// // Leaving at 7 would reach the shop at 8, before it opens.
+    /// let at_7 = SearchOptions::new().departing_at(7.0, 1.0, AvailabilityMode::Avoid);
+    /// assert!(nav_graph.find_path_with(1, 3, &at_7).is_none());
+    ///
+    /// let at_10 = SearchOptions::new().departing_at(10.0, 1.0, AvailabilityMode::Avoid);
+    /// assert!(nav_graph.find_path_with(1, 3, &at_10).is_some());
+    ///
+    /// // Or wait outside until it opens.
+    /// let waiting = SearchOptions::new().departing_at(7.0, 1.0, AvailabilityMode::Wait);
+    /// assert!(nav_graph.find_path_with(1, 3, &waiting).is_some());
+    /// ```
+    pub fn set_point_availability(&mut self, id: u32, availability: Option<Availability>) {
+        match availability {
+            Some(availability) if self.has_nav_point(id) => {
+                self.point_availability.insert(id, availability);
+                self.revocation_candidates.insert(id);
+            }
+            _ => {
+                self.point_availability.remove(&id);
+            }
+        }
+    }
+
+    /// Limits when the connection between `a` and `b`, in either direction, can be used. Passing
+    /// `None` makes it always available.
+    ///
+    /// Does nothing if the points aren't connected.
+    pub fn set_connection_availability(
+        &mut self,
+        a: u32,
+        b: u32,
+        availability: Option<Availability>,
+    ) {
+        let connected = self
+            .get_nav_point(a)
+            .map(|point| point.connections().contains(&b))
+            .unwrap_or(false);
+        if !connected && availability.is_some() {
+            return;
+        }
+
+        let key = edge_key(a, b);
+        match availability {
+            Some(availability) => {
+                self.edges.entry(key).or_default().availability = Some(availability);
+                self.revocation_candidates.extend([a, b]);
+            }
+            None => {
+                if let Some(edge) = self.edges.get_mut(&key) {
+                    edge.availability = None;
+                }
+            }
+        }
+    }
+
+    /// Opens or closes the connection between `a` and `b`. See [`NavEdge::closed`].
+    ///
+    /// Does nothing if the points aren't connected.
+    pub fn set_connection_closed(&mut self, a: u32, b: u32, closed: bool) {
+        let connected = self
+            .get_nav_point(a)
+            .map(|point| point.connections().contains(&b))
+            .unwrap_or(false);
+        if connected {
+            self.edges.entry(edge_key(a, b)).or_default().closed = closed;
+            if closed {
+                self.revocation_candidates.extend([a, b]);
+            }
+        }
+    }
+
+    /// Sets how the connection between `a` and `b` is traversed. See [`Traversal`].
+    ///
+    /// Does nothing if the points aren't connected.
+    pub fn set_traversal(&mut self, a: u32, b: u32, traversal: Traversal) {
+        let connected = self
+            .get_nav_point(a)
+            .map(|point| point.connections().contains(&b))
+            .unwrap_or(false);
+        if connected {
+            self.edges.entry(edge_key(a, b)).or_default().traversal = traversal;
+        }
+    }
+
+    /// Returns how the connection between `a` and `b` is traversed.
+    pub fn traversal(&self, a: u32, b: u32) -> Traversal {
+        self.edge(a, b)
+            .map(|edge| edge.traversal)
+            .unwrap_or_default()
+    }
+
+    /// Returns the availability window of the specified point, if it has one.
+    pub fn point_availability(&self, id: u32) -> Option<&Availability> {
+        self.point_availability.get(&id)
+    }
+
+    /// Returns the attributes of the connection between `a` and `b`, if any have been set.
+    pub fn edge(&self, a: u32, b: u32) -> Option<&NavEdge> {
+        self.edges.get(&edge_key(a, b))
+    }
+
+    /// Returns true if both the connection from `a` to `b` and the point `b` are open at `time`.
+    ///
+    /// Closed connections are never open, regardless of their availability window.
+    pub fn is_open(&self, a: u32, b: u32, time: f64) -> bool {
+        if self.edge(a, b).map(|edge| edge.closed).unwrap_or(false) {
+            return false;
+        }
+
+        let point_open = self
+            .point_availability
+            .get(&b)
+            .map(|availability| availability.is_open(time))
+            .unwrap_or(true);
+        let edge_open = self
+            .edge(a, b)
+            .and_then(|edge| edge.availability)
+            .map(|availability| availability.is_open(time))
+            .unwrap_or(true);
+        point_open && edge_open
+    }
+
+    /// Works out when a traveler leaving `a` no earlier than `departure` reaches `b`, and how
+    /// long it waits beforehand. Returns `None` if the connection can't be used.
+    pub(crate) fn traverse(
+        &self,
+        a: u32,
+        b: u32,
+        departure: f64,
+        schedule: &Schedule,
+    ) -> Option<(f64, f64)> {
+        let (a_point, b_point) = (self.points.get(&a)?, self.points.get(&b)?);
+        let rate = schedule.speed
+            * self
+                .edge_speed_model()
+                .effective(a_point.speed_modifier(), b_point.speed_modifier());
+        let travel_time = if let Some(duration) = self.connection_duration(a, b) {
+            duration as f64
+        } else if rate > 0.0 {
+            (a_point.location().distance(b_point.location()) / rate) as f64
+        } else {
+            0.0
+        };
+
+        let edge = self.edge(a, b).and_then(|edge| edge.availability);
+        let point = self.point_availability.get(&b);
+        if edge.is_none() && point.is_none() {
+            return Some((departure + travel_time, 0.0));
+        }
+
+        let mut leave = departure;
+        // Alternate between the two windows until both are open; a handful of rounds is plenty
+        // for any windows which actually overlap.
+        for _ in 0..8 {
+            let edge_open = edge.map(|edge| edge.is_open(leave)).unwrap_or(true);
+            let point_open = point
+                .map(|point| point.is_open(leave + travel_time))
+                .unwrap_or(true);
+            if edge_open && point_open {
+                return Some((leave + travel_time, leave - departure));
+            }
+            if schedule.mode == AvailabilityMode::Avoid {
+                return None;
+            }
+
+            if let Some(edge) = edge {
+                leave = edge.next_open(leave)?;
+            }
+            if let Some(point) = point {
+                leave = leave.max(point.next_open(leave + travel_time)? - travel_time);
+            }
+        }
+        None
+    }
+
+    /// Drops availability windows which refer to `id`, used when the point is removed.
+    pub(crate) fn clear_availability(&mut self, id: u32) {
+        self.point_availability.remove(&id);
+        self.edges.retain(|(a, b), _| *a != id && *b != id);
+    }
+}
+
+impl SearchOptions {
+    /// Checks [`Availability`] windows against the time each point would be reached, for a
+    /// traveler leaving at `departure` and moving at `speed`.
+    pub fn departing_at(mut self, departure: f64, speed: f32, mode: AvailabilityMode) -> Self {
+        self.schedule = Some(Schedule {
+            departure,
+            speed,
+            mode,
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_repeating_window() {
+        let availability = Availability::repeating(9.0, 17.0, 24.0);
+
+        assert!(!availability.is_open(8.0));
+        assert!(availability.is_open(9.0));
+        assert!(!availability.is_open(17.0));
+        assert!(availability.is_open(24.0 + 12.0));
+        assert_eq!(availability.next_open(8.0), Some(9.0));
+        assert_eq!(availability.next_open(20.0), Some(33.0));
+
+        let once = Availability::between(9.0, 17.0);
+        assert_eq!(once.next_open(20.0), None);
+    }
+}
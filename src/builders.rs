@@ -0,0 +1,334 @@
+use std::ops::Range;
+
+use bevy_math::Vec3;
+use bevy_tasks::{ComputeTaskPool, TaskPool};
+
+use crate::{
+    availability::edge_key,
+    navigation::{travel_cost, DEFAULT_CAPACITY_HINT},
+    NavDomain, NavGraph, NavPoint, Traversal,
+};
+
+/// A grid of walkable and blocked cells, e.g. read from an image, heightmap or tilemap, to add to
+/// a [`NavGraph`] with [`NavGraph::add_walkability_map`].
+#[derive(Debug, Clone, Copy)]
+pub struct WalkabilityMap<'a> {
+    pub width: usize,
+    pub height: usize,
+    /// Whether each cell is walkable, row by row.
+    pub walkable: &'a [bool],
+    /// The distance between neighboring cells.
+    pub spacing: f32,
+    /// Whether cells are also connected to their diagonal neighbors. Diagonals are only
+    /// connected if both cells beside them are walkable too, so corners can't be cut.
+    pub diagonals: bool,
+    /// Cells on either side of a straight gap of at most this many blocked cells are connected
+    /// by a [`Traversal::Jump`], so platformer-style travelers can jump across. 0 bridges nothing.
+    pub max_gap: usize,
+}
+
+impl<'a> WalkabilityMap<'a> {
+    /// # Panics
+    /// If `walkable` doesn't hold exactly `width * height` cells.
+    pub fn new(width: usize, height: usize, walkable: &'a [bool]) -> Self {
+        assert_eq!(
+            walkable.len(),
+            width * height,
+            "walkability map should have width * height cells"
+        );
+        Self {
+            width,
+            height,
+            walkable,
+            spacing: 1.0,
+            diagonals: false,
+            max_gap: 0,
+        }
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn with_diagonals(mut self) -> Self {
+        self.diagonals = true;
+        self
+    }
+
+    /// Bridges gaps of up to `max_gap` blocked cells with jumps. See [`WalkabilityMap::max_gap`].
+    pub fn with_gap_bridging(mut self, max_gap: usize) -> Self {
+        self.max_gap = max_gap;
+        self
+    }
+
+    fn is_walkable(&self, x: isize, y: isize) -> bool {
+        x >= 0
+            && y >= 0
+            && (x as usize) < self.width
+            && (y as usize) < self.height
+            && self.walkable[y as usize * self.width + x as usize]
+    }
+
+    fn location(&self, x: isize, y: isize) -> Vec3 {
+        Vec3::new(x as f32 * self.spacing, y as f32 * self.spacing, 0.0)
+    }
+
+    /// The distance to the walkable cell across a gap from `(x, y)` in direction `(dx, dy)`, if
+    /// the gap is narrow enough to bridge.
+    fn gap_across(&self, x: isize, y: isize, (dx, dy): (isize, isize)) -> Option<isize> {
+        (2..=self.max_gap as isize + 1)
+            .take_while(|&step| {
+                let (gx, gy) = (x + dx * (step - 1), y + dy * (step - 1));
+                let in_bounds =
+                    gx >= 0 && gy >= 0 && (gx as usize) < self.width && (gy as usize) < self.height;
+                in_bounds && !self.is_walkable(gx, gy)
+            })
+            .find(|&step| self.is_walkable(x + dx * step, y + dy * step))
+    }
+
+    /// Builds the fully linked points for the walkable cells of `rows`, along with the jumps
+    /// between them.
+    fn build_rows(&self, rows: Range<usize>, first_id: u32) -> (Vec<NavPoint>, Vec<(u32, u32)>) {
+        const ORTHOGONAL: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+        const DIAGONAL: [(isize, isize); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+        let id = |x: isize, y: isize| first_id + (y as usize * self.width + x as usize) as u32;
+        let mut points = Vec::new();
+        let mut jumps = Vec::new();
+        for y in rows.map(|y| y as isize) {
+            for x in 0..self.width as isize {
+                if !self.is_walkable(x, y) {
+                    continue;
+                }
+
+                let location = self.location(x, y);
+                let mut point = NavPoint::new(id(x, y), location, 1.0, 1);
+                let diagonals = DIAGONAL.iter().filter(|(dx, dy)| {
+                    self.diagonals && self.is_walkable(x + dx, y) && self.is_walkable(x, y + dy)
+                });
+                for (dx, dy) in ORTHOGONAL.iter().chain(diagonals) {
+                    let (nx, ny) = (x + dx, y + dy);
+                    if self.is_walkable(nx, ny) {
+                        point.connections.insert(id(nx, ny));
+                        point.costs.insert(
+                            id(nx, ny),
+                            travel_cost(location, self.location(nx, ny), 1.0),
+                        );
+                    }
+                }
+                for (dx, dy) in ORTHOGONAL {
+                    let Some(step) = self.gap_across(x, y, (dx, dy)) else {
+                        continue;
+                    };
+                    let (nx, ny) = (x + dx * step, y + dy * step);
+                    point.connections.insert(id(nx, ny));
+                    point.costs.insert(
+                        id(nx, ny),
+                        travel_cost(location, self.location(nx, ny), 1.0),
+                    );
+                    jumps.push(edge_key(id(x, y), id(nx, ny)));
+                }
+                points.push(point);
+            }
+        }
+        (points, jumps)
+    }
+}
+
+/// Which neighbors each cell of a grid is connected to. See [`NavGraph::from_grid`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GridConnectivity {
+    /// The cells above, below and to either side.
+    #[default]
+    Four,
+    /// The orthogonal neighbors and the diagonal ones.
+    Eight,
+}
+
+/// Maps between the cells of a grid and the IDs of their points. See [`NavGraph::from_grid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridIds {
+    pub width: usize,
+    pub height: usize,
+    /// The ID of the cell at `(0, 0)`.
+    pub first_id: u32,
+}
+
+impl GridIds {
+    /// The ID of the point for the cell at `(x, y)`, or `None` if it's outside the grid.
+    pub fn id(&self, x: usize, y: usize) -> Option<u32> {
+        (x < self.width && y < self.height).then(|| self.first_id + (y * self.width + x) as u32)
+    }
+
+    /// The cell of the point with the specified ID, or `None` if it isn't part of the grid.
+    pub fn cell(&self, id: u32) -> Option<(usize, usize)> {
+        let index = id.checked_sub(self.first_id)? as usize;
+        (index < self.width * self.height).then(|| (index % self.width, index / self.width))
+    }
+}
+
+impl NavGraph {
+    /// Creates a graph of `width` by `height` points, `spacing` apart on the XY plane starting
+    /// from the origin, each connected to its neighbors.
+    ///
+    /// The [`NavGraph::capacity_hint`] is set to suit the spacing.
+    ///
+    /// # Panics
+    /// If the grid has more points than there are IDs.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_navigator::{GridConnectivity, NavGraph};
+    /// let (nav_graph, ids) = NavGraph::from_grid(10, 10, 16.0, GridConnectivity::Eight);
+    ///
+    /// let corner = ids.id(9, 9).unwrap();
+    /// assert_eq!(nav_graph.find_path(ids.id(0, 0).unwrap(), corner).unwrap().len(), 10);
+    /// assert_eq!(ids.cell(corner), Some((9, 9)));
+    /// ```
+    pub fn from_grid(
+        width: usize,
+        height: usize,
+        spacing: f32,
+        connectivity: GridConnectivity,
+    ) -> (Self, GridIds) {
+        let walkable = vec![true; width * height];
+        let mut map = WalkabilityMap::new(width, height, &walkable).with_spacing(spacing);
+        map.diagonals = connectivity == GridConnectivity::Eight;
+
+        let mut nav_graph = Self::default();
+        if spacing > 0.0 {
+            nav_graph.set_capacity_hint(DEFAULT_CAPACITY_HINT / spacing);
+        }
+        let first_id = nav_graph
+            .add_walkability_map(&map)
+            .expect("more grid cells than there are NavPoint IDs");
+        let ids = GridIds {
+            width,
+            height,
+            first_id,
+        };
+        (nav_graph, ids)
+    }
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Adds a point for every walkable cell of `map`, connected to its walkable neighbors, and
+    /// across gaps as set by [`WalkabilityMap::max_gap`].
+    ///
+    /// The cell at `(x, y)` is given the ID `first_id + y * width + x`, where `first_id`, which is
+    /// returned, is one more than the highest ID in the graph. Cells are laid out on the XY plane,
+    /// `spacing` apart, starting from the origin. Returns `None`, adding nothing, if the IDs of
+    /// every cell wouldn't fit below `u32::MAX`.
+    ///
+    /// Large maps are built in parallel on the [`ComputeTaskPool`], with the same result as
+    /// building them on a single thread.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_navigator::{NavGraph, WalkabilityMap};
+    /// // A 3x2 map with a wall in the middle of the top row.
+    /// let walkable = [true, true, true, true, false, true];
+    /// let mut nav_graph = NavGraph::new();
+    /// let first_id = nav_graph
+    ///     .add_walkability_map(&WalkabilityMap::new(3, 2, &walkable))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(nav_graph.len(), 5);
+    /// let (x, y) = (2, 1);
+    /// let path = nav_graph.find_path(first_id + 3, first_id + y * 3 + x).unwrap();
+    /// assert_eq!(path.len(), 5);
+    /// ```
+    pub fn add_walkability_map(&mut self, map: &WalkabilityMap) -> Option<u32> {
+        let first_id = self.highest_id.checked_add(1)?;
+        let cells = u32::try_from(map.width.checked_mul(map.height)?).ok()?;
+        if cells == 0 {
+            return Some(first_id);
+        }
+        // The last cell's ID has to fit too.
+        first_id.checked_add(cells - 1)?;
+
+        let pool = ComputeTaskPool::init(TaskPool::default);
+        let rows_per_task = map.height.div_ceil(pool.thread_num().max(1));
+        // Each task builds its rows independently, and the results come back in the order the
+        // tasks were spawned, so the merge doesn't depend on scheduling.
+        let chunks = pool.scope(|scope| {
+            for start in (0..map.height).step_by(rows_per_task) {
+                let rows = start..(start + rows_per_task).min(map.height);
+                scope.spawn(async move { map.build_rows(rows, first_id) });
+            }
+        });
+
+        self.reserve(chunks.iter().map(|(points, _)| points.len()).sum());
+        let mut all_jumps = Vec::new();
+        self.insert_linked_points(chunks.into_iter().flat_map(|(points, jumps)| {
+            all_jumps.extend(jumps);
+            points
+        }));
+        for (a, b) in all_jumps {
+            self.set_traversal(a, b, Traversal::Jump);
+        }
+        Some(first_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_walkability_map_links_neighbors() {
+        let (width, height) = (64, 48);
+        let walkable: Vec<bool> = (0..width * height).map(|i| i % 7 != 3).collect();
+        let map = WalkabilityMap::new(width, height, &walkable)
+            .with_spacing(2.0)
+            .with_diagonals();
+
+        let mut nav_graph = NavGraph::new();
+        let first_id = nav_graph.add_walkability_map(&map).unwrap();
+        assert_eq!(first_id, 1);
+        assert_eq!(nav_graph.len(), walkable.iter().filter(|w| **w).count());
+
+        // Cell (3, 0) is blocked, so (2, 0) and (3, 1) can't cut the corner between them.
+        assert!(!nav_graph.has_nav_point(4));
+        assert!(!nav_graph
+            .get_nav_point(3)
+            .unwrap()
+            .connections
+            .contains(&(4 + 64)));
+
+        // Compare against the same map built on one thread, and linked one point at a time.
+        let (single_threaded, _) = map.build_rows(0..height, first_id);
+        let mut expected = NavGraph::new();
+        for point in &single_threaded {
+            expected.add_nav_point(NavPoint::new(point.id(), point.location(), 1.0, 1));
+        }
+        for point in &single_threaded {
+            for connection in &point.connections {
+                expected.connect_points(point.id(), *connection);
+            }
+        }
+        for point in expected.points.values() {
+            let built = nav_graph.get_nav_point(point.id()).unwrap();
+            assert_eq!(built.connections, point.connections);
+            assert_eq!(built.costs, point.costs);
+            assert_eq!(built.location(), point.location());
+        }
+    }
+
+    #[test]
+    pub fn test_gap_bridging() {
+        #[rustfmt::skip]
+        let walkable = [
+            true, false, false, true, false, false, false, true,
+        ];
+        let map = WalkabilityMap::new(8, 1, &walkable).with_gap_bridging(2);
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_walkability_map(&map);
+
+        // Two cells is narrow enough to jump, three isn't.
+        assert_eq!(nav_graph.find_path(1, 4).unwrap()[..], [1, 4]);
+        assert_eq!(nav_graph.traversal(1, 4), Traversal::Jump);
+        assert!(nav_graph.find_path(4, 8).is_none());
+    }
+}
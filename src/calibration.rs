@@ -0,0 +1,165 @@
+use bevy_utils::HashMap;
+
+use crate::{memory::map_bytes, NavDomain, NavGraph};
+
+/// How much each recorded path moves the running averages of [`HeuristicCalibration`].
+const CALIBRATION_RATE: f32 = 0.1;
+
+/// The range the heuristic is scaled within, so a few odd paths can't make it useless.
+const MIN_SCALE: f32 = 0.01;
+const MAX_SCALE: f32 = 100.0;
+
+/// Running averages of how the actual costs of paths compare with the heuristic's estimates, per
+/// region of the points they start from and overall, for [`NavGraph::set_adaptive_heuristic`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct HeuristicCalibration {
+    enabled: bool,
+    overall: Option<f32>,
+    regions: HashMap<u32, f32>,
+}
+
+impl HeuristicCalibration {
+    /// The factor to scale the heuristic by for searches starting in `region`.
+    pub fn scale(&self, region: u32) -> f32 {
+        if !self.enabled {
+            return 1.0;
+        }
+        self.regions
+            .get(&region)
+            .copied()
+            .or(self.overall)
+            .map_or(1.0, |ratio| ratio.clamp(MIN_SCALE, MAX_SCALE))
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        map_bytes(&self.regions)
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.regions.shrink_to_fit();
+    }
+}
+
+/// Scales a heuristic estimate by `scale`, from [`HeuristicCalibration::scale`].
+#[inline(always)]
+pub(crate) fn scale_heuristic(estimate: u32, scale: f32) -> u32 {
+    if scale == 1.0 {
+        estimate
+    } else {
+        (estimate as f32 * scale) as u32
+    }
+}
+
+fn blend(average: Option<f32>, ratio: f32) -> f32 {
+    average.map_or(ratio, |average| {
+        average + (ratio - average) * CALIBRATION_RATE
+    })
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Scales the heuristic used by searches by how it has compared with the actual costs of
+    /// the paths recorded with [`NavGraph::calibrate_heuristic`], per region of the point a
+    /// search starts from, or across all regions for regions without any paths recorded yet.
+    ///
+    /// On maps where the straight line distance poorly predicts the cost of getting somewhere,
+    /// e.g. mazes, this keeps searches from expanding far more points than they need to. The
+    /// paths found are no longer guaranteed to be the cheapest. Travelers record the paths they
+    /// plan, so this adapts on its own as they move about. While landmarks built with
+    /// [`NavGraph::build_landmarks`] are up to date, searches use those instead.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_navigator::{GridConnectivity, NavGraph};
+    /// let (mut nav_graph, ids) = NavGraph::<()>::from_grid(20, 20, 1.0, GridConnectivity::Four);
+    /// nav_graph.set_adaptive_heuristic(true);
+    ///
+    /// let (a, b) = (ids.id(0, 0).unwrap(), ids.id(19, 19).unwrap());
+    /// let path = nav_graph.find_path(a, b).unwrap();
+    /// nav_graph.calibrate_heuristic(a, b, nav_graph.path_cost(&path).unwrap());
+    /// // Without diagonal connections, paths are longer than the straight line.
+    /// assert!(nav_graph.heuristic_scale(a) > 1.0);
+    /// assert!(nav_graph.find_path(a, b).is_some());
+    /// ```
+    pub fn set_adaptive_heuristic(&mut self, enabled: bool) {
+        self.heuristic_calibration.enabled = enabled;
+    }
+
+    /// Records that the cheapest path found from `a` to `b` costs `cost`, for
+    /// [`NavGraph::set_adaptive_heuristic`].
+    ///
+    /// Paths between unknown or coincident points are ignored.
+    pub fn calibrate_heuristic(&mut self, a: u32, b: u32, cost: u32) {
+        let estimate = self.h_func(&a, &b);
+        if estimate == 0 || estimate == u32::MAX {
+            return;
+        }
+        let ratio = cost as f32 / estimate as f32;
+        let region = self.points[&a].region();
+
+        let calibration = &mut self.heuristic_calibration;
+        calibration.overall = Some(blend(calibration.overall, ratio));
+        let average = calibration.regions.get(&region).copied();
+        calibration.regions.insert(region, blend(average, ratio));
+    }
+
+    /// The factor searches starting from `id` scale the heuristic by, which is 1.0 unless
+    /// [`NavGraph::set_adaptive_heuristic`] is enabled.
+    pub fn heuristic_scale(&self, id: u32) -> f32 {
+        self.points.get(&id).map_or(1.0, |point| {
+            self.heuristic_calibration.scale(point.region())
+        })
+    }
+
+    /// Forgets the paths recorded with [`NavGraph::calibrate_heuristic`], e.g. after the map
+    /// changes substantially.
+    pub fn reset_heuristic_calibration(&mut self) {
+        let enabled = self.heuristic_calibration.enabled;
+        self.heuristic_calibration = HeuristicCalibration {
+            enabled,
+            ..Default::default()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridConnectivity;
+
+    #[test]
+    pub fn test_heuristic_scales_per_region() {
+        let (mut nav_graph, ids) = NavGraph::<()>::from_grid(20, 10, 1.0, GridConnectivity::Four);
+        let id = |x, y| ids.id(x, y).unwrap();
+        // A wall with a gap at the far end, so crossing it means a long detour.
+        for x in 0..19 {
+            nav_graph.remove_point(id(x, 5));
+        }
+        nav_graph.assign_grid_regions(10.0);
+        let (a, b) = (id(0, 4), id(0, 6));
+        assert_eq!(nav_graph.heuristic_scale(a), 1.0);
+
+        nav_graph.set_adaptive_heuristic(true);
+        let cost = nav_graph
+            .path_cost(&nav_graph.find_path(a, b).unwrap())
+            .unwrap();
+        for _ in 0..50 {
+            nav_graph.calibrate_heuristic(a, b, cost);
+        }
+        // The estimate is far too low across the wall.
+        let scale = nav_graph.heuristic_scale(a);
+        assert!(scale > 5.0, "{}", scale);
+        let path = nav_graph.find_path(a, b).unwrap();
+        assert_eq!((path[0], path[path.len() - 1]), (a, b));
+
+        // Regions without their own paths go by the overall average.
+        let elsewhere = id(15, 0);
+        assert_ne!(
+            nav_graph.get_nav_point(elsewhere).unwrap().region(),
+            nav_graph.get_nav_point(a).unwrap().region()
+        );
+        assert!(nav_graph.heuristic_scale(elsewhere) > 1.0);
+
+        nav_graph.reset_heuristic_calibration();
+        assert_eq!(nav_graph.heuristic_scale(a), 1.0);
+    }
+}
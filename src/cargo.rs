@@ -0,0 +1,268 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::EventWriter,
+    system::{Query, ResMut},
+};
+use bevy_reflect::{FromReflect, Reflect};
+
+use crate::{traveler::TravelerPosition, CargoTransferred, NavDomain, NavGraph};
+
+/// When a [`CargoRule`] applies.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+pub enum CargoTrigger {
+    #[default]
+    Arrive,
+    Depart,
+}
+
+/// What a [`CargoRule`] does with the point's stock.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+pub enum CargoAction {
+    /// Takes as much stock as the carrier has room for.
+    #[default]
+    Load,
+    /// Puts everything the carrier holds into the point's stock.
+    Unload,
+}
+
+/// Loads or unloads a [`Carrier`] when it arrives at or departs from a point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+pub struct CargoRule {
+    /// The point the rule applies at, or `None` for every point.
+    pub nav_point: Option<u32>,
+    pub trigger: CargoTrigger,
+    pub action: CargoAction,
+}
+
+/// Lets a traveler pick up and drop off stock held at [`NavPoint`](crate::NavPoint)s.
+///
+/// Whenever the traveler arrives at or departs from a point, matching `rules` move stock between
+/// the point (see [`NavGraph::stock`]) and `carried`, sending a [`CargoTransferred`] event for
+/// each transfer. Transfers go through the [`NavGraph`], so two carriers can never take the same
+/// stock.
+#[derive(Debug, Default, Clone, Component, Reflect, FromReflect)]
+pub struct Carrier {
+    pub carried: u32,
+    pub capacity: u32,
+    pub rules: Vec<CargoRule>,
+    #[reflect(ignore)]
+    pub(crate) last_position: Option<(u32, bool)>,
+}
+
+impl Carrier {
+    pub fn new(capacity: u32) -> Self {
+        Self {
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    /// Loads stock when departing from `nav_point`.
+    pub fn loading_at(mut self, nav_point: u32) -> Self {
+        self.rules.push(CargoRule {
+            nav_point: Some(nav_point),
+            trigger: CargoTrigger::Depart,
+            action: CargoAction::Load,
+        });
+        self
+    }
+
+    /// Unloads everything when arriving at `nav_point`.
+    pub fn unloading_at(mut self, nav_point: u32) -> Self {
+        self.rules.push(CargoRule {
+            nav_point: Some(nav_point),
+            trigger: CargoTrigger::Arrive,
+            action: CargoAction::Unload,
+        });
+        self
+    }
+
+    pub fn with_rule(mut self, rule: CargoRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    fn apply<M: NavDomain>(
+        &mut self,
+        entity: Entity,
+        nav_point: u32,
+        trigger: CargoTrigger,
+        nav_graph: &mut NavGraph<M>,
+        events: &mut EventWriter<CargoTransferred<M>>,
+    ) {
+        for rule in &self.rules {
+            if rule.trigger != trigger || rule.nav_point.is_some_and(|id| id != nav_point) {
+                continue;
+            }
+
+            let amount = match rule.action {
+                CargoAction::Load => {
+                    nav_graph.take_stock(nav_point, self.capacity.saturating_sub(self.carried))
+                }
+                CargoAction::Unload => {
+                    nav_graph.add_stock(nav_point, self.carried);
+                    self.carried
+                }
+            };
+            if amount == 0 {
+                continue;
+            }
+            match rule.action {
+                CargoAction::Load => self.carried += amount,
+                CargoAction::Unload => self.carried -= amount,
+            }
+            events.send(CargoTransferred::new(
+                entity,
+                nav_point,
+                rule.action,
+                amount,
+            ));
+        }
+    }
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// The amount of stock held at the specified point.
+    pub fn stock(&self, id: u32) -> u32 {
+        self.stock.get(&id).copied().unwrap_or(0)
+    }
+
+    /// Sets the amount of stock held at the specified point. Does nothing if the point doesn't
+    /// exist.
+    pub fn set_stock(&mut self, id: u32, amount: u32) {
+        if self.has_nav_point(id) {
+            self.stock.insert(id, amount);
+        }
+    }
+
+    /// Adds stock to the specified point. Does nothing if the point doesn't exist.
+    pub fn add_stock(&mut self, id: u32, amount: u32) {
+        if self.has_nav_point(id) {
+            let stock = self.stock.entry(id).or_insert(0);
+            *stock = stock.saturating_add(amount);
+        }
+    }
+
+    /// Takes up to `max` stock from the specified point, returning how much was taken.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.set_stock(1, 5);
+    ///
+    /// assert_eq!(nav_graph.take_stock(1, 3), 3);
+    /// assert_eq!(nav_graph.take_stock(1, 3), 2);
+    /// assert_eq!(nav_graph.stock(1), 0);
+    /// ```
+    pub fn take_stock(&mut self, id: u32, max: u32) -> u32 {
+        let Some(stock) = self.stock.get_mut(&id) else {
+            return 0;
+        };
+        let taken = max.min(*stock);
+        *stock -= taken;
+        taken
+    }
+}
+
+pub(crate) fn transfer_cargo<M: NavDomain>(
+    mut carriers_query: Query<(Entity, &mut Carrier, &TravelerPosition), M::Filter>,
+    mut nav_graph: ResMut<NavGraph<M>>,
+    mut events: EventWriter<CargoTransferred<M>>,
+) {
+    for (entity, mut carrier, traveler_position) in carriers_query.iter_mut() {
+        let current = traveler_position.current_nav_point;
+        let departed = traveler_position.next_nav_point.is_some();
+        let Some((last, last_departed)) = carrier.last_position else {
+            carrier.last_position = Some((current, departed));
+            continue;
+        };
+        if (last, last_departed) == (current, departed) {
+            continue;
+        }
+
+        if current != last {
+            // Points passed through within a single update, e.g. in turn-based movement, are
+            // departed from and arrived at without being seen in between.
+            if !last_departed {
+                carrier.apply(
+                    entity,
+                    last,
+                    CargoTrigger::Depart,
+                    &mut nav_graph,
+                    &mut events,
+                );
+            }
+            carrier.apply(
+                entity,
+                current,
+                CargoTrigger::Arrive,
+                &mut nav_graph,
+                &mut events,
+            );
+        }
+        if departed && (current != last || !last_departed) {
+            carrier.apply(
+                entity,
+                current,
+                CargoTrigger::Depart,
+                &mut nav_graph,
+                &mut events,
+            );
+        }
+        carrier.last_position = Some((current, departed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::event::Events;
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::NavPoint;
+
+    #[test]
+    pub fn test_pickup_and_dropoff() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.set_stock(1, 10);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .add_event::<CargoTransferred>()
+            .add_system(transfer_cargo::<()>);
+        let carrier = app
+            .world
+            .spawn((
+                Carrier::new(4).loading_at(1).unloading_at(2),
+                TravelerPosition {
+                    current_nav_point: 1,
+                    next_nav_point: None,
+                },
+            ))
+            .id();
+        let mut step = |current, next| {
+            *app.world.get_mut::<TravelerPosition>(carrier).unwrap() = TravelerPosition {
+                current_nav_point: current,
+                next_nav_point: next,
+            };
+            app.update();
+        };
+
+        step(1, None);
+        step(1, Some(2));
+        step(2, None);
+
+        let nav_graph = app.world.resource::<NavGraph>();
+        assert_eq!(nav_graph.stock(1), 6);
+        assert_eq!(nav_graph.stock(2), 4);
+        assert_eq!(app.world.get::<Carrier>(carrier).unwrap().carried, 0);
+        assert_eq!(app.world.resource::<Events<CargoTransferred>>().len(), 2);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{NavDomain, NavGraph};
+
+/// How much weight the latest interval between releases gets in the running mean.
+const SMOOTHING: f32 = 0.25;
+
+/// How often a [`NavPoint`](crate::NavPoint) has recently been released.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct OccupancyChurn {
+    last_release: f64,
+    mean_interval: Option<f32>,
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Like [`NavGraph::unoccupy`], but also records that the point was released at `now`, in
+    /// seconds, for [`NavGraph::expected_wait`].
+    pub fn unoccupy_at(&mut self, id: u32, now: f64) {
+        self.unoccupy_weighted_at(id, 1, now);
+    }
+
+    /// Like [`NavGraph::unoccupy_at`], but releases `weight` slots at once.
+    pub fn unoccupy_weighted_at(&mut self, id: u32, weight: u32, now: f64) {
+        if !self.has_nav_point(id) {
+            return;
+        }
+        self.unoccupy_weighted(id, weight);
+
+        let churn = self.churn.entry(id).or_insert(OccupancyChurn {
+            last_release: now,
+            mean_interval: None,
+        });
+        if now > churn.last_release {
+            let interval = (now - churn.last_release) as f32;
+            churn.mean_interval = Some(match churn.mean_interval {
+                Some(mean) => mean + (interval - mean) * SMOOTHING,
+                None => interval,
+            });
+            churn.last_release = now;
+        }
+    }
+
+    /// How long, in seconds, it typically takes for the specified point to be released, based on
+    /// the recent intervals between releases recorded by [`NavGraph::unoccupy_at`].
+    ///
+    /// Returns `None` until the point has been released at least twice.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    ///
+    /// for now in [0.0, 2.0, 4.0] {
+    ///     nav_graph.occupy(1);
+    ///     nav_graph.unoccupy_at(1, now);
+    /// }
+    /// assert_eq!(nav_graph.expected_wait(1), Some(2.0));
+    /// ```
+    pub fn expected_wait(&self, id: u32) -> Option<f32> {
+        self.churn.get(&id).and_then(|churn| churn.mean_interval)
+    }
+}
+
+#[cfg(all(test, feature = "travelers"))]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::entity::Entity;
+    use bevy_math::Vec3;
+    use bevy_time::Time;
+    use bevy_transform::prelude::Transform;
+    use bevy_utils::{Duration, Instant};
+
+    use super::*;
+    use crate::{
+        traveler::{move_travelers, TravelerPosition},
+        AutoTraveler, BlockedBehavior, DestinationSubstituted, LodPolicy, NavClock, NavPoint,
+        TrafficLanes, TravelCompleted, TravelFailed,
+    };
+
+    /// A blocked traveler at 1, heading for 3 through 2, with a longer way round through 4.
+    fn blocked_app(expected_wait: f64) -> (App, Entity) {
+        let mut nav_graph = NavGraph::new();
+        for (id, x, y) in [(1, 0.0, 0.0), (2, 1.0, 0.0), (3, 2.0, 0.0), (4, 1.0, 3.0)] {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 1));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+        nav_graph.connect_points(1, 4);
+        nav_graph.connect_points(4, 3);
+        for now in [0.0, expected_wait] {
+            nav_graph.occupy(2);
+            nav_graph.unoccupy_at(2, now);
+        }
+        nav_graph.occupy(1);
+        nav_graph.occupy(2);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .init_resource::<LodPolicy>()
+            .init_resource::<TrafficLanes>()
+            .add_event::<TravelCompleted>()
+            .add_event::<DestinationSubstituted>()
+            .add_event::<TravelFailed>()
+            .add_system(move_travelers::<()>);
+        let mut auto_traveler =
+            AutoTraveler::new(1, 3, 1.0).with_blocked_behavior(BlockedBehavior::Adaptive);
+        auto_traveler.path = Some(vec![1, 2, 3]);
+        let entity = app
+            .world
+            .spawn((
+                auto_traveler,
+                TravelerPosition {
+                    current_nav_point: 1,
+                    next_nav_point: None,
+                },
+                Transform::default(),
+            ))
+            .id();
+
+        let start = Instant::now();
+        app.world.resource_mut::<Time>().update_with_instant(start);
+        app.world
+            .resource_mut::<Time>()
+            .update_with_instant(start + Duration::from_millis(100));
+        app.update();
+        (app, entity)
+    }
+
+    #[test]
+    pub fn test_adaptive_blocking_weighs_wait_against_detour() {
+        let (app, entity) = blocked_app(0.5);
+        let path = app.world.get::<AutoTraveler>(entity).unwrap().path.clone();
+        assert_eq!(path, Some(vec![1, 2, 3]));
+
+        let (app, entity) = blocked_app(60.0);
+        let path = app.world.get::<AutoTraveler>(entity).unwrap().path.clone();
+        assert_eq!(path, Some(vec![1, 4, 3]));
+    }
+}
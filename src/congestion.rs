@@ -0,0 +1,140 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::Without,
+    system::{Commands, Query, Res},
+};
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_time::Time;
+
+use crate::{graphs::TravelerGraphs, traveler::TravelerPosition, AutoTraveler, NavDomain, Replan};
+
+/// Re-plans a traveler as soon as the route ahead becomes congested, rather than waiting until it
+/// is blocked at a full point.
+///
+/// The congestion of the route is the sum of the occupancy ratios (current over maximum
+/// occupancy) of the points left on the traveler's path, not counting the ones it occupies
+/// itself. Whenever it's over `threshold`, a [`Replan`] is requested, at most once every
+/// `cooldown` seconds.
+#[derive(Debug, Clone, Copy, Component, Reflect, FromReflect)]
+pub struct CongestionReplan {
+    pub threshold: f32,
+    /// Only this many points ahead are considered, or the whole remaining path if `None`.
+    pub lookahead: Option<usize>,
+    pub cooldown: f32,
+    #[reflect(ignore)]
+    since_replan: f32,
+}
+
+impl CongestionReplan {
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            threshold,
+            lookahead: None,
+            cooldown: 1.0,
+            since_replan: f32::INFINITY,
+        }
+    }
+
+    pub fn with_lookahead(mut self, lookahead: usize) -> Self {
+        self.lookahead = Some(lookahead);
+        self
+    }
+
+    pub fn with_cooldown(mut self, cooldown: f32) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+}
+
+type CongestionTravelerQuery<'w, 's, M> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static AutoTraveler,
+        &'static TravelerPosition,
+        &'static mut CongestionReplan,
+    ),
+    (<M as NavDomain>::Filter, Without<Replan>),
+>;
+
+pub(crate) fn replan_congested_travelers<M: NavDomain>(
+    mut travelers_query: CongestionTravelerQuery<M>,
+    graphs: TravelerGraphs<M>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, auto_traveler, traveler_position, mut congestion) in travelers_query.iter_mut() {
+        congestion.since_replan += time.delta_seconds();
+        if congestion.since_replan < congestion.cooldown || auto_traveler.fixed_path {
+            continue;
+        }
+        let (Some(path), Some(nav_graph)) =
+            (auto_traveler.path.as_ref(), graphs.get(auto_traveler.graph))
+        else {
+            continue;
+        };
+
+        let ahead = path
+            .iter()
+            .skip(auto_traveler.current_index + 1)
+            .filter(|id| traveler_position.next_nav_point != Some(**id))
+            .take(congestion.lookahead.unwrap_or(usize::MAX));
+        let load: f32 = ahead
+            .filter_map(|id| nav_graph.get_nav_point(*id))
+            .map(|point| point.current_occupancy() as f32 / point.max_occupancy().max(1) as f32)
+            .sum();
+
+        if load > congestion.threshold {
+            congestion.since_replan = 0.0;
+            commands.entity(entity).insert(Replan);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::{NavGraph, NavPoint};
+
+    #[test]
+    pub fn test_congested_route_requests_replan() {
+        let mut nav_graph = NavGraph::new();
+        for id in 1..=4 {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 2));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+        nav_graph.connect_points(3, 4);
+        nav_graph.occupy(3);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .insert_resource(Time::default())
+            .add_system(replan_congested_travelers::<()>);
+        let mut spawn = |threshold| {
+            let mut auto_traveler = AutoTraveler::new(1, 4, 1.0);
+            auto_traveler.path = Some(vec![1, 2, 3, 4]);
+            app.world
+                .spawn((
+                    auto_traveler,
+                    TravelerPosition {
+                        current_nav_point: 1,
+                        next_nav_point: Some(2),
+                    },
+                    CongestionReplan::new(threshold),
+                ))
+                .id()
+        };
+        let relaxed = spawn(0.5);
+        let sensitive = spawn(0.25);
+        app.update();
+
+        assert!(app.world.get::<Replan>(relaxed).is_none());
+        assert!(app.world.get::<Replan>(sensitive).is_some());
+    }
+}
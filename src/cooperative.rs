@@ -0,0 +1,597 @@
+use std::{cmp::Ordering, collections::BinaryHeap, marker::PhantomData};
+
+#[cfg(feature = "travelers")]
+use bevy_ecs::{
+    component::Component,
+    system::{Local, Query, RemovedComponents, Res, ResMut},
+};
+use bevy_ecs::{entity::Entity, system::Resource};
+#[cfg(feature = "travelers")]
+use bevy_reflect::{FromReflect, Reflect};
+#[cfg(feature = "travelers")]
+use bevy_time::Time;
+use bevy_utils::HashMap;
+
+#[cfg(feature = "travelers")]
+use crate::{graphs::TravelerGraphs, traveler::TravelerPosition, AutoTraveler, NavGraphId};
+use crate::{NavDomain, NavGraph, SearchOptions};
+
+/// A span of time a point is reserved for an entity in [`SpaceTimeReservations`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TimeSlot {
+    from: f64,
+    to: f64,
+    entity: Entity,
+}
+
+/// When each point is going to be taken by travelers following cooperatively planned paths, so
+/// others can plan around them. See [`NavGraph::find_path_cooperative`].
+///
+/// Times are in seconds, on whichever clock the searches use; the traveler systems use
+/// [`Time::elapsed_seconds_f64`](bevy_time::Time::elapsed_seconds_f64).
+#[derive(Debug, Resource)]
+pub struct SpaceTimeReservations<M: NavDomain = ()> {
+    slots: HashMap<u32, Vec<TimeSlot>>,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> Default for SpaceTimeReservations<M> {
+    fn default() -> Self {
+        Self {
+            slots: HashMap::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: NavDomain> SpaceTimeReservations<M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserves the point `id` for `entity` from `from` until `to`.
+    pub fn reserve(&mut self, id: u32, from: f64, to: f64, entity: Entity) {
+        self.slots
+            .entry(id)
+            .or_default()
+            .push(TimeSlot { from, to, entity });
+    }
+
+    /// Returns true unless the point `id` is reserved for an entity other than `entity` at any
+    /// time from `from` until `to`.
+    pub fn is_free(&self, id: u32, from: f64, to: f64, entity: Entity) -> bool {
+        self.slots.get(&id).is_none_or(|slots| {
+            slots
+                .iter()
+                .all(|slot| slot.entity == entity || slot.to <= from || to <= slot.from)
+        })
+    }
+
+    /// Returns true if the point `id` is reserved at any time.
+    pub fn is_reserved(&self, id: u32) -> bool {
+        self.slots.contains_key(&id)
+    }
+
+    /// Releases everything reserved for `entity`.
+    pub fn release(&mut self, entity: Entity) {
+        self.slots.retain(|_, slots| {
+            slots.retain(|slot| slot.entity != entity);
+            !slots.is_empty()
+        });
+    }
+
+    /// Drops the reservations which ended before `now`.
+    pub fn expire(&mut self, now: f64) {
+        self.slots.retain(|_, slots| {
+            slots.retain(|slot| slot.to >= now);
+            !slots.is_empty()
+        });
+    }
+}
+
+/// Who a cooperative search is for and how far ahead it looks. See
+/// [`NavGraph::find_path_cooperative`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CooperativeSearch {
+    pub entity: Entity,
+    /// The time the path starts at.
+    pub departure: f64,
+    /// The traveler's speed, as used by [`AutoTraveler::speed`](crate::AutoTraveler::speed).
+    pub speed: f32,
+    /// How many seconds past `departure` reservations are taken into account. Past the window,
+    /// the path ignores other travelers, so it's best planned again before then.
+    pub window: f32,
+    /// How long each wait in place lasts, in seconds. Smaller steps find tighter plans but
+    /// expand more states.
+    pub wait: f32,
+}
+
+impl CooperativeSearch {
+    pub fn new(entity: Entity, departure: f64, speed: f32) -> Self {
+        Self {
+            entity,
+            departure,
+            speed,
+            window: 10.0,
+            wait: 0.5,
+        }
+    }
+
+    pub fn with_window(mut self, window: f32) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub fn with_wait(mut self, wait: f32) -> Self {
+        self.wait = wait;
+        self
+    }
+}
+
+/// A path found by [`NavGraph::find_path_cooperative`], with when to reach and leave each point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CooperativePath {
+    points: Vec<u32>,
+    arrivals: Vec<f64>,
+    departures: Vec<f64>,
+    horizon: f64,
+}
+
+impl CooperativePath {
+    pub fn points(&self) -> &[u32] {
+        &self.points
+    }
+
+    /// When each point is reached. The first is the search's departure time.
+    pub fn arrivals(&self) -> &[f64] {
+        &self.arrivals
+    }
+
+    /// When to leave each point, after waiting there if it's later than the arrival. The last is
+    /// the arrival at the destination.
+    pub fn departures(&self) -> &[f64] {
+        &self.departures
+    }
+
+    /// Reserves each point of the path within the search's window for `entity`, from when it
+    /// starts moving toward the point until it reaches the next one. The destination stays
+    /// reserved from then on.
+    pub fn reserve<M: NavDomain>(
+        &self,
+        reservations: &mut SpaceTimeReservations<M>,
+        entity: Entity,
+    ) {
+        for (i, id) in self.points.iter().enumerate() {
+            let from = match i {
+                0 => self.arrivals[0],
+                _ => self.departures[i - 1],
+            };
+            if from > self.horizon {
+                break;
+            }
+            let to = self.arrivals.get(i + 1).copied().unwrap_or(f64::INFINITY);
+            reservations.reserve(*id, from, to, entity);
+        }
+    }
+}
+
+/// A state of the cooperative search: a point, and which wait step it's reached in while within
+/// the window. Past the window, states are told apart by point alone.
+type StateKey = (u32, Option<u32>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OpenState {
+    estimate: f64,
+    time: f64,
+    key: StateKey,
+}
+
+impl Eq for OpenState {}
+
+impl Ord for OpenState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .total_cmp(&self.estimate)
+            .then_with(|| other.time.total_cmp(&self.time))
+            .then_with(|| self.key.cmp(&other.key))
+    }
+}
+
+impl PartialOrd for OpenState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Computes a path which keeps out of the way of other travelers' reserved paths, waiting in
+    /// place where needed, as in windowed hierarchical cooperative A* (WHCA*). Meant for
+    /// single-occupancy graphs such as tile maps, where travelers planning alone keep blocking
+    /// each other.
+    ///
+    /// Searches minimize travel time rather than the graph's costs. Each connection takes its
+    /// length divided by the traveler's speed and its speed modifier under the graph's
+    /// [`EdgeSpeedModel`](crate::EdgeSpeedModel), or
+    /// its fixed duration (see [`NavGraph::set_connection_duration`]). Points which are full but
+    /// reserved are taken to be held by cooperative travelers, and are passed according to their
+    /// reservations instead. `options` are applied as in [`NavGraph::find_path_with`], apart
+    /// from their costs.
+    ///
+    /// Once found, reserve the path with [`CooperativePath::reserve`] so searches for other
+    /// travelers plan around it. Travelers with a [`CooperativePlanning`] do all of this on
+    /// their own.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_ecs::entity::Entity;
+    /// # use bevy_navigator::{
+    /// #     CooperativeSearch, GridConnectivity, NavGraph, SearchOptions, SpaceTimeReservations,
+    /// # };
+    /// let (nav_graph, ids) = NavGraph::<()>::from_grid(3, 1, 1.0, GridConnectivity::Four);
+    /// let mut reservations = SpaceTimeReservations::new();
+    /// let (a, b) = (Entity::from_raw(1), Entity::from_raw(2));
+    ///
+    /// // `a` is leaving the middle for the right, so `b` waits until it's gone.
+    /// let search = CooperativeSearch::new(a, 0.0, 1.0);
+    /// let first = nav_graph
+    ///     .find_path_cooperative(2, 3, &search, &reservations, &SearchOptions::new())
+    ///     .unwrap();
+    /// first.reserve(&mut reservations, a);
+    ///
+    /// let search = CooperativeSearch::new(b, 0.0, 1.0);
+    /// let second = nav_graph
+    ///     .find_path_cooperative(1, 2, &search, &reservations, &SearchOptions::new())
+    ///     .unwrap();
+    /// assert_eq!(second.points(), [1, 2]);
+    /// assert_eq!(second.departures()[0], 1.0);
+    /// ```
+    pub fn find_path_cooperative(
+        &self,
+        a: u32,
+        b: u32,
+        search: &CooperativeSearch,
+        reservations: &SpaceTimeReservations<M>,
+        options: &SearchOptions,
+    ) -> Option<CooperativePath> {
+        self.observe(
+            a,
+            b,
+            || self.search_cooperative(a, b, search, reservations, options),
+            |path| (path.points().len(), self.path_cost(path.points())),
+        )
+    }
+
+    fn search_cooperative(
+        &self,
+        a: u32,
+        b: u32,
+        search: &CooperativeSearch,
+        reservations: &SpaceTimeReservations<M>,
+        options: &SearchOptions,
+    ) -> Option<CooperativePath> {
+        let goal = self.points.get(&b)?.location();
+        self.points.get(&a)?;
+
+        let horizon = search.departure + search.window.max(0.0) as f64;
+        let key_at = |id, time: f64| {
+            let step = (time <= horizon && search.wait > 0.0)
+                .then(|| ((time - search.departure) / search.wait as f64).round() as u32);
+            (id, step)
+        };
+        let fastest = search.speed * self.spatial.fastest_speed_modifier();
+        let estimate = |id: u32| match (self.points.get(&id), fastest > 0.0) {
+            (Some(point), true) => (point.location().distance(goal) / fastest) as f64,
+            _ => 0.0,
+        };
+        let relaxed = SearchOptions {
+            ignore_occupancy: true,
+            ..options.clone()
+        };
+        let entity = search.entity;
+
+        let start = key_at(a, search.departure);
+        let mut open = BinaryHeap::from([OpenState {
+            estimate: search.departure + estimate(a),
+            time: search.departure,
+            key: start,
+        }]);
+        let mut best = HashMap::from([(start, search.departure)]);
+        let mut came_from = HashMap::<StateKey, (StateKey, f64)>::new();
+        let mut expanded = 0;
+        while let Some(OpenState { time, key, .. }) = open.pop() {
+            if best.get(&key).is_some_and(|best| *best < time) {
+                continue;
+            }
+            let (id, _) = key;
+            if id == b {
+                return Some(self.cooperative_path(key, time, &came_from, horizon));
+            }
+            expanded += 1;
+            if options.max_expanded.is_some_and(|max| expanded > max) {
+                return None;
+            }
+            let reserved = time <= horizon;
+
+            let mut push = |next: StateKey, next_time: f64| {
+                if best.get(&next).is_some_and(|best| *best <= next_time) {
+                    return;
+                }
+                best.insert(next, next_time);
+                came_from.insert(next, (key, time));
+                open.push(OpenState {
+                    estimate: next_time + estimate(next.0),
+                    time: next_time,
+                    key: next,
+                });
+            };
+
+            if reserved && search.wait > 0.0 {
+                let until = time + search.wait as f64;
+                if reservations.is_free(id, time, until, entity) {
+                    push(key_at(id, until), until);
+                }
+            }
+
+            let point = self.points.get(&id)?;
+            for &next in point.connections() {
+                let Some(next_point) = self.points.get(&next) else {
+                    continue;
+                };
+                let passable = self.is_passable(next_point, &relaxed)
+                    && (options.ignore_occupancy
+                        || next_point.can_occupy_weighted(options.occupancy_weight.max(1))
+                        || reservations.is_reserved(next));
+                if !passable {
+                    continue;
+                }
+                let travel = match self.connection_duration(id, next) {
+                    Some(duration) => duration,
+                    None => {
+                        let rate = search.speed
+                            * self
+                                .edge_speed_model()
+                                .effective(point.speed_modifier(), next_point.speed_modifier());
+                        if rate <= 0.0 {
+                            continue;
+                        }
+                        point.location().distance(next_point.location()) / rate
+                    }
+                };
+                let arrival = time + travel as f64;
+                if reserved {
+                    let until = if next == b { f64::INFINITY } else { arrival };
+                    if !reservations.is_free(id, time, arrival, entity)
+                        || !reservations.is_free(next, time, until, entity)
+                    {
+                        continue;
+                    }
+                }
+                push(key_at(next, arrival), arrival);
+            }
+        }
+        None
+    }
+
+    fn cooperative_path(
+        &self,
+        end: StateKey,
+        time: f64,
+        came_from: &HashMap<StateKey, (StateKey, f64)>,
+        horizon: f64,
+    ) -> CooperativePath {
+        let mut states = vec![(end.0, time)];
+        let mut key = end;
+        while let Some((previous, time)) = came_from.get(&key) {
+            states.push((previous.0, *time));
+            key = *previous;
+        }
+        states.reverse();
+
+        let mut path = CooperativePath {
+            points: Vec::new(),
+            arrivals: Vec::new(),
+            departures: Vec::new(),
+            horizon,
+        };
+        for (id, time) in states {
+            if path.points.last() == Some(&id) {
+                // Waited in place.
+                *path.departures.last_mut().unwrap() = time;
+            } else {
+                path.points.push(id);
+                path.arrivals.push(time);
+                path.departures.push(time);
+            }
+        }
+        path
+    }
+}
+
+/// Plans a traveler's path with [`NavGraph::find_path_cooperative`] in place of
+/// [`NavGraph::find_path`], so travelers which have one wait for each other rather than
+/// blocking each other, e.g. on single-occupancy tile maps.
+///
+/// The path is planned again from the point the traveler is at every `window / 2` seconds, and
+/// whenever it's replaced, e.g. by a [`Replan`](crate::Replan). Travelers wait at points until
+/// their planned departure. If no cooperative path is found, the traveler keeps its path, trying
+/// again every `wait` seconds.
+///
+/// ## Example
+/// ```
+/// # use bevy_ecs::system::Commands;
+/// # use bevy_navigator::{AutoTraveler, CooperativePlanning};
+/// fn spawn_workers(mut commands: Commands) {
+///     for origin in 1..=4 {
+///         commands.spawn((AutoTraveler::new(origin, 42, 2.0), CooperativePlanning::new(8.0)));
+///     }
+/// }
+/// ```
+#[cfg(feature = "travelers")]
+#[derive(Debug, Clone, Component, Reflect, FromReflect)]
+pub struct CooperativePlanning {
+    /// See [`CooperativeSearch::window`].
+    pub window: f32,
+    /// See [`CooperativeSearch::wait`].
+    pub wait: f32,
+    #[reflect(ignore)]
+    plan: Option<CooperativePath>,
+    planned_at: f64,
+}
+
+#[cfg(feature = "travelers")]
+impl CooperativePlanning {
+    pub fn new(window: f32) -> Self {
+        Self {
+            window,
+            wait: 0.5,
+            plan: None,
+            planned_at: f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn with_wait(mut self, wait: f32) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    /// The traveler's current plan, if it has one.
+    pub fn plan(&self) -> Option<&CooperativePath> {
+        self.plan.as_ref()
+    }
+
+    /// Whether a traveler following `path` should stay at the point at `index` at `now`.
+    pub(crate) fn holds(&self, path: &[u32], index: usize, now: f64) -> bool {
+        self.plan
+            .as_ref()
+            .filter(|plan| plan.points == path)
+            .and_then(|plan| plan.departures.get(index))
+            .is_some_and(|departure| *departure > now)
+    }
+}
+
+#[cfg(feature = "travelers")]
+type CooperativeTravelerQuery<'w, 's, M> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut AutoTraveler,
+        &'static TravelerPosition,
+        &'static mut CooperativePlanning,
+    ),
+    <M as NavDomain>::Filter,
+>;
+
+#[cfg(feature = "travelers")]
+pub(crate) fn plan_cooperative_travelers<M: NavDomain>(
+    mut travelers_query: CooperativeTravelerQuery<M>,
+    removed_planning: RemovedComponents<CooperativePlanning>,
+    removed_travelers: RemovedComponents<AutoTraveler>,
+    graphs: TravelerGraphs<M>,
+    mut reservations: ResMut<SpaceTimeReservations<M>>,
+    mut keyed_reservations: Local<HashMap<NavGraphId, SpaceTimeReservations<M>>>,
+    time: Res<Time>,
+) {
+    // Plans on the graphs in `NavGraphs` are kept apart from those on the `NavGraph` resource.
+    keyed_reservations.retain(|id, _| graphs.get(Some(*id)).is_some());
+    let now = time.elapsed_seconds_f64();
+    for reservations in std::iter::once(&mut *reservations).chain(keyed_reservations.values_mut()) {
+        for entity in removed_planning.iter().chain(removed_travelers.iter()) {
+            reservations.release(entity);
+        }
+        if !reservations.slots.is_empty() {
+            reservations.expire(now);
+        }
+    }
+
+    for (entity, mut auto_traveler, traveler_position, mut planning) in travelers_query.iter_mut() {
+        if auto_traveler.fixed_path || traveler_position.next_nav_point.is_some() {
+            continue;
+        }
+        let (Some(path), Some(nav_graph)) =
+            (auto_traveler.path.as_ref(), graphs.get(auto_traveler.graph))
+        else {
+            continue;
+        };
+        let current = planning
+            .plan
+            .as_ref()
+            .is_some_and(|plan| plan.points == *path);
+        let interval = match (current, &planning.plan) {
+            (true, _) => planning.window / 2.0,
+            (false, None) => planning.wait,
+            (false, Some(_)) => 0.0,
+        };
+        if now < planning.planned_at + interval as f64 {
+            continue;
+        }
+
+        // The traveler may have come over from another graph.
+        reservations.release(entity);
+        for reservations in keyed_reservations.values_mut() {
+            reservations.release(entity);
+        }
+        let reservations = match auto_traveler.graph {
+            None => &mut *reservations,
+            Some(id) => keyed_reservations
+                .entry(id)
+                .or_insert_with(SpaceTimeReservations::new),
+        };
+        let search = CooperativeSearch::new(entity, now, auto_traveler.speed)
+            .with_window(planning.window)
+            .with_wait(planning.wait);
+        let plan = nav_graph.find_path_cooperative(
+            traveler_position.current_nav_point,
+            auto_traveler.destination,
+            &search,
+            reservations,
+            &auto_traveler.search_options(),
+        );
+        if let Some(plan) = &plan {
+            plan.reserve(reservations, entity);
+            auto_traveler.path = Some(plan.points.clone());
+            auto_traveler.current_index = 0;
+        }
+        planning.plan = plan;
+        planning.planned_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridConnectivity;
+
+    #[test]
+    pub fn test_crossing_paths_wait_their_turn() {
+        // A plus sign: 4 - 5 - 6 across, 2 - 5 - 8 up.
+        let (mut nav_graph, _) = NavGraph::<()>::from_grid(3, 3, 1.0, GridConnectivity::Four);
+        for corner in [1, 3, 7, 9] {
+            nav_graph.remove_point(corner);
+        }
+        let mut reservations = SpaceTimeReservations::new();
+        let (across, up) = (Entity::from_raw(1), Entity::from_raw(2));
+        let find = |reservations: &SpaceTimeReservations, a, b, entity| {
+            let search = CooperativeSearch::new(entity, 0.0, 1.0);
+            nav_graph
+                .find_path_cooperative(a, b, &search, reservations, &SearchOptions::new())
+                .unwrap()
+        };
+
+        let first = find(&reservations, 4, 6, across);
+        assert_eq!(first.points(), [4, 5, 6]);
+        assert_eq!(first.departures(), [0.0, 1.0, 2.0]);
+        first.reserve(&mut reservations, across);
+
+        // The center is taken until the first arrives at 6.
+        let second = find(&reservations, 2, 8, up);
+        assert_eq!(second.points(), [2, 5, 8]);
+        assert_eq!(second.departures(), [2.0, 3.0, 4.0]);
+        assert_eq!(second.arrivals(), [0.0, 3.0, 4.0]);
+
+        // Alone, nobody waits.
+        reservations.release(across);
+        assert_eq!(find(&reservations, 2, 8, up).departures(), [0.0, 1.0, 2.0]);
+    }
+}
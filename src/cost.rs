@@ -0,0 +1,121 @@
+use std::{fmt, sync::Arc};
+
+use crate::{NavDomain, NavGraph, NavPoint, SearchOptions};
+
+/// Supplies the cost of moving along each connection a search considers, so costs such as
+/// threat maps or terrain types can be layered over the graph's own without keeping them in it.
+///
+/// `base` is the graph's cost for the connection: its squared length times 100, divided by its
+/// speed modifier under the graph's [`EdgeSpeedModel`](crate::EdgeSpeedModel), or its weight if
+/// it has one. Territory penalties, congestion costs and noise are applied on top of what's
+/// returned. Returning less than `base` can make searches miss the cheapest
+/// path, since the heuristic assumes connections cost at least their distance. To keep
+/// searches out of points entirely, exclude them instead; see [`SearchOptions::excluded`].
+///
+/// Closures taking `(from, to, base)` are cost providers too.
+///
+/// ## Example
+/// ```
+/// # use bevy_math::Vec3;
+/// # use bevy_navigator::{NavGraph, NavPoint};
+/// let mut nav_graph = NavGraph::new();
+/// for (id, y) in [(1, 0.0), (2, 1.0), (3, -1.0), (4, 0.0)] {
+///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id.min(3) as f32, y, 0.0), 1.0, 1));
+/// }
+/// for (a, b) in [(1, 2), (2, 4), (1, 3), (3, 4)] {
+///     nav_graph.connect_points(a, b);
+/// }
+///
+/// // Point 2 is watched by a guard, so moving onto it costs ten times as much.
+/// let threat = |_: &NavPoint, to: &NavPoint, base: u32| if to.id() == 2 { base * 10 } else { base };
+/// assert_eq!(nav_graph.find_path_with_costs(1, 4, threat), Some(vec![1, 3, 4]));
+/// ```
+pub trait CostProvider: Send + Sync + 'static {
+    /// The cost of moving from `from` onto `to`.
+    fn cost(&self, from: &NavPoint, to: &NavPoint, base: u32) -> u32;
+}
+
+impl<F> CostProvider for F
+where
+    F: Fn(&NavPoint, &NavPoint, u32) -> u32 + Send + Sync + 'static,
+{
+    fn cost(&self, from: &NavPoint, to: &NavPoint, base: u32) -> u32 {
+        self(from, to, base)
+    }
+}
+
+impl fmt::Debug for dyn CostProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CostProvider")
+    }
+}
+
+/// The graph's own costs, which searches use without a [`CostProvider`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DistanceCost;
+
+impl CostProvider for DistanceCost {
+    fn cost(&self, _from: &NavPoint, _to: &NavPoint, base: u32) -> u32 {
+        base
+    }
+}
+
+impl SearchOptions {
+    /// Takes the cost of each connection from `cost_provider`. See [`CostProvider`].
+    pub fn with_cost_provider(mut self, cost_provider: impl CostProvider) -> Self {
+        self.cost_provider = Some(Arc::new(cost_provider));
+        self
+    }
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Computes a path like [`NavGraph::find_path`], with the cost of each connection given by
+    /// `cost_provider`.
+    pub fn find_path_with_costs(
+        &self,
+        a: u32,
+        b: u32,
+        cost_provider: impl CostProvider,
+    ) -> Option<Vec<u32>> {
+        self.find_path_with(
+            a,
+            b,
+            &SearchOptions::new().with_cost_provider(cost_provider),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridConnectivity;
+
+    #[test]
+    pub fn test_custom_costs_replace_the_graphs() {
+        let (nav_graph, ids) = NavGraph::<()>::from_grid(5, 5, 1.0, GridConnectivity::Four);
+        let (a, b) = (ids.id(0, 0).unwrap(), ids.id(4, 4).unwrap());
+        assert_eq!(
+            nav_graph.find_path_with_costs(a, b, DistanceCost),
+            nav_graph.find_path(a, b)
+        );
+
+        // Doubling every cost doubles the cost seen along the path.
+        let doubled =
+            SearchOptions::new().with_cost_provider(|_: &NavPoint, _: &NavPoint, base| base * 2);
+        let path = nav_graph.find_path_with(a, b, &doubled).unwrap();
+        let segments = nav_graph.explain_path_with(&path, &doubled).unwrap();
+        let cost: u32 = segments.iter().map(|segment| segment.cost).sum();
+        assert_eq!(cost, nav_graph.path_cost(&path).unwrap() * 2);
+
+        // The middle row is expensive to enter everywhere but at its left end.
+        let walled = |_: &NavPoint, to: &NavPoint, base: u32| {
+            if to.location().y == 2.0 && to.location().x > 0.0 {
+                base * 100
+            } else {
+                base
+            }
+        };
+        let path = nav_graph.find_path_with_costs(a, b, walled).unwrap();
+        assert!(path.contains(&ids.id(0, 2).unwrap()));
+    }
+}
@@ -0,0 +1,58 @@
+use bevy_math::Vec3;
+
+use crate::{NavDomain, NavGraph};
+
+/// How crowded an area of a [`NavGraph`] is. See [`NavGraph::density_in_radius`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CrowdDensity {
+    /// The current occupancy of each point in the area, as `(id, occupants)`, closest first.
+    pub occupants: Vec<(u32, u32)>,
+    /// The current occupancy of all the points in the area together.
+    pub occupancy: u32,
+    /// The max occupancy of all the points in the area together.
+    pub capacity: u32,
+}
+
+impl CrowdDensity {
+    /// The share of the area's capacity in use, from 0.0 when empty to 1.0 when every point is
+    /// full. An area without capacity counts as empty.
+    pub fn density(&self) -> f32 {
+        if self.capacity == 0 {
+            return 0.0;
+        }
+        self.occupancy as f32 / self.capacity as f32
+    }
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Measures how crowded the points within `radius` of `center` are, e.g. for spawners to
+    /// avoid crowded areas or for an AI director to tell where the action is.
+    ///
+    /// Occupancy weights count as they do for [`NavGraph::occupy_weighted`], so the figures are
+    /// those the graph uses to keep travelers apart.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{GridConnectivity, NavGraph};
+    /// let (mut nav_graph, ids) = NavGraph::<()>::from_grid(10, 10, 1.0, GridConnectivity::Four);
+    /// nav_graph.occupy(ids.id(0, 0).unwrap());
+    /// nav_graph.occupy(ids.id(1, 0).unwrap());
+    ///
+    /// let crowd = nav_graph.density_in_radius(Vec3::ZERO, 1.0);
+    /// assert_eq!((crowd.occupancy, crowd.capacity), (2, 3));
+    /// assert!(nav_graph.density_in_radius(Vec3::new(8.0, 8.0, 0.0), 1.0).density() == 0.0);
+    /// ```
+    pub fn density_in_radius(&self, center: Vec3, radius: f32) -> CrowdDensity {
+        let mut density = CrowdDensity::default();
+        for id in self.points_within_radius(center, radius) {
+            let Some(point) = self.get_nav_point(id) else {
+                continue;
+            };
+            density.occupants.push((id, point.current_occupancy()));
+            density.occupancy += point.current_occupancy();
+            density.capacity += point.max_occupancy();
+        }
+        density
+    }
+}
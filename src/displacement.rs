@@ -0,0 +1,220 @@
+use bevy_ecs::{
+    component::Component,
+    query::Without,
+    system::{Query, Res},
+};
+use bevy_math::Vec3;
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_time::Time;
+use bevy_transform::prelude::Transform;
+use bevy_utils::tracing::info;
+
+use crate::{
+    graphs::TravelerGraphsMut,
+    lod::LowFidelity,
+    traveler::{replan, TravelerPosition},
+    AutoTraveler, NavClock, NavDomain, SuspendTravel,
+};
+
+/// Puts a traveler back on the graph where it now is when something other than its own movement,
+/// e.g. knockback or a teleport, carries it more than `max_drift` away from the segment it's
+/// traveling along.
+///
+/// Without this, a displaced traveler heads straight back to its segment, through whatever is in
+/// the way. Instead, it's projected onto the graph (see
+/// [`NavGraph::project_onto_graph`](crate::NavGraph::project_onto_graph)) and rejoins its path at
+/// the nearer end of the connection it lands on, if that point is still ahead on its path. Otherwise its path is replanned from there, or for a fixed path, it heads
+/// for the closest point still ahead. The points it held are released, and the one it rejoins
+/// at is occupied, even if full.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect, FromReflect)]
+pub struct DisplacementRejoin {
+    pub max_drift: f32,
+}
+
+impl DisplacementRejoin {
+    pub fn new(max_drift: f32) -> Self {
+        Self { max_drift }
+    }
+}
+
+type DisplacedTravelerQuery<'w, 's, M> = Query<
+    'w,
+    's,
+    (
+        &'static mut AutoTraveler,
+        &'static mut TravelerPosition,
+        &'static Transform,
+        &'static DisplacementRejoin,
+        Option<&'static LowFidelity>,
+    ),
+    (<M as NavDomain>::Filter, Without<SuspendTravel>),
+>;
+
+pub(crate) fn rejoin_displaced_travelers<M: NavDomain>(
+    mut travelers_query: DisplacedTravelerQuery<M>,
+    mut graphs: TravelerGraphsMut<M>,
+    clock: Res<NavClock>,
+    time: Res<Time>,
+) {
+    for (mut auto_traveler, mut traveler_position, transform, rejoin, low_fidelity) in
+        travelers_query.iter_mut()
+    {
+        let (Some(path), Some(nav_graph)) = (
+            auto_traveler.path.as_ref(),
+            graphs.get_mut(auto_traveler.graph),
+        ) else {
+            continue;
+        };
+        let current = traveler_position.current_nav_point;
+        let Some(from) = nav_graph
+            .get_nav_point(current)
+            .map(|point| point.location())
+        else {
+            continue;
+        };
+        let to = traveler_position
+            .next_nav_point
+            .and_then(|next| nav_graph.get_nav_point(next))
+            .map_or(from, |point| point.location());
+        let position = transform.translation;
+        if distance_to_segment(position, from, to) <= rejoin.max_drift {
+            continue;
+        }
+        let Some(projection) = nav_graph.project_onto_graph(position) else {
+            continue;
+        };
+
+        let remaining = auto_traveler.current_index..path.len();
+        let join = projection.closest_point();
+        let on_path = path[remaining.clone()]
+            .iter()
+            .position(|id| *id == join)
+            .map(|offset| remaining.start + offset);
+        let rejoin_at = match on_path {
+            Some(index) => Some((index, join)),
+            None if auto_traveler.fixed_path => path[remaining.clone()]
+                .iter()
+                .enumerate()
+                .filter_map(|(offset, id)| {
+                    let location = nav_graph.get_nav_point(*id)?.location();
+                    Some((
+                        location.distance_squared(position),
+                        remaining.start + offset,
+                        *id,
+                    ))
+                })
+                .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)))
+                .map(|(_, index, id)| (index, id)),
+            None => None,
+        };
+        info!(
+            "Traveler displaced from {} to near {}, rejoining",
+            current, join
+        );
+
+        if low_fidelity.is_none() {
+            let weight = auto_traveler.occupancy_weight;
+            nav_graph.unoccupy_weighted_at(current, weight, time.elapsed_seconds_f64());
+            if let Some(next) = traveler_position.next_nav_point {
+                nav_graph.unoccupy_weighted(next, weight);
+            }
+            nav_graph.occupy_unchecked(rejoin_at.map_or(join, |(_, id)| id), weight);
+        }
+        traveler_position.next_nav_point = None;
+        match rejoin_at {
+            Some((index, id)) => {
+                traveler_position.current_nav_point = id;
+                auto_traveler.current_index = index;
+            }
+            None => {
+                traveler_position.current_nav_point = join;
+                if !replan(
+                    nav_graph,
+                    &mut auto_traveler,
+                    &mut traveler_position,
+                    position,
+                    clock.now,
+                ) {
+                    info!("No path found from where the traveler was displaced to");
+                }
+            }
+        }
+    }
+}
+
+fn distance_to_segment(position: Vec3, from: Vec3, to: Vec3) -> f32 {
+    let segment = to - from;
+    let length_squared = segment.length_squared();
+    let t = if length_squared > 0.0 {
+        ((position - from).dot(segment) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    position.distance(from + segment * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+
+    use super::*;
+    use crate::{NavGraph, NavPoint};
+
+    #[test]
+    pub fn test_displaced_traveler_rejoins_ahead() {
+        // A corridor from 1 to 5, doubling back on itself from 3 so that 5 passes close to 1.
+        let mut nav_graph = NavGraph::new();
+        for (id, x, y) in [
+            (1, 0.0, 0.0),
+            (2, 4.0, 0.0),
+            (3, 8.0, 0.0),
+            (4, 8.0, 2.0),
+            (5, 0.0, 2.0),
+        ] {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 1));
+        }
+        for id in 1..5 {
+            nav_graph.connect_points(id, id + 1);
+        }
+        nav_graph.occupy(1);
+        nav_graph.occupy(2);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .init_resource::<NavClock>()
+            .insert_resource(Time::default())
+            .add_system(rejoin_displaced_travelers::<()>);
+        let mut auto_traveler = AutoTraveler::new(1, 5, 1.0);
+        auto_traveler.path = Some(vec![1, 2, 3, 4, 5]);
+        let traveler = app
+            .world
+            .spawn((
+                auto_traveler,
+                TravelerPosition {
+                    current_nav_point: 1,
+                    next_nav_point: Some(2),
+                },
+                // Knocked across the gap, next to the last stretch of the corridor.
+                Transform::from_xyz(1.0, 2.5, 0.0),
+                DisplacementRejoin::new(1.0),
+            ))
+            .id();
+        app.update();
+
+        let traveler_position = app.world.get::<TravelerPosition>(traveler).unwrap();
+        assert_eq!(traveler_position.current_nav_point, 5);
+        assert_eq!(traveler_position.next_nav_point, None);
+        assert_eq!(
+            app.world
+                .get::<AutoTraveler>(traveler)
+                .unwrap()
+                .current_index,
+            4
+        );
+        let nav_graph = app.world.resource::<NavGraph>();
+        let occupancy: Vec<_> = (1..=5)
+            .map(|id| nav_graph.get_nav_point(id).unwrap().current_occupancy())
+            .collect();
+        assert_eq!(occupancy, [0, 0, 0, 0, 1]);
+    }
+}
@@ -0,0 +1,102 @@
+use bevy_ecs::query::ReadOnlyWorldQuery;
+
+/// Identifies an independent navigation domain.
+///
+/// Each [`NavigatorPlugin::<M>`](crate::NavigatorPlugin) instance has its own
+/// [`NavGraph<M>`](crate::NavGraph), [`PathRequestQueue<M>`](crate::PathRequestQueue), travel
+/// events and systems, so e.g. pedestrians and vehicles can navigate separate graphs side by side.
+/// The systems of a domain only see entities matching its `Filter`.
+///
+/// The default domain, `()`, sees every entity, so apps with several domains should give each of
+/// them its own marker:
+///
+/// ```
+/// # use bevy_ecs::{component::Component, query::With};
+/// # use bevy_navigator::NavDomain;
+/// #[derive(Component)]
+/// struct Vehicles;
+///
+/// impl NavDomain for Vehicles {
+///     type Filter = With<Vehicles>;
+/// }
+/// ```
+///
+/// Maps loaded side by side in a single domain, such as the interiors of an overworld's
+/// buildings, go in its [`NavGraphs`](crate::NavGraphs) rather than in domains of their own, with
+/// each traveler picking its map with [`AutoTraveler::with_graph`](crate::AutoTraveler::with_graph).
+pub trait NavDomain: Send + Sync + 'static {
+    /// Selects the entities belonging to the domain.
+    type Filter: ReadOnlyWorldQuery + 'static;
+}
+
+impl NavDomain for () {
+    type Filter = ();
+}
+
+#[cfg(all(test, feature = "travelers"))]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::{component::Component, event::Events, query::With};
+    use bevy_math::Vec3;
+    use bevy_time::Time;
+
+    use super::*;
+    use crate::{AutoTraveler, NavGraph, NavPoint, NavigatorPlugin, OriginClaim, TravelFailed};
+
+    #[derive(Component)]
+    struct People;
+
+    impl NavDomain for People {
+        type Filter = With<People>;
+    }
+
+    #[derive(Component)]
+    struct Vehicles;
+
+    impl NavDomain for Vehicles {
+        type Filter = With<Vehicles>;
+    }
+
+    #[test]
+    pub fn test_domains_are_independent() {
+        let mut app = App::new();
+        app.add_plugin(NavigatorPlugin::<People>::default())
+            .add_plugin(NavigatorPlugin::<Vehicles>::default())
+            .insert_resource(Time::default());
+        for id in [1, 2] {
+            let point = NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1);
+            app.world
+                .resource_mut::<NavGraph<People>>()
+                .add_nav_point(point);
+            let point = NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1);
+            app.world
+                .resource_mut::<NavGraph<Vehicles>>()
+                .add_nav_point(point);
+        }
+        // Only the road between the two points is built.
+        app.world
+            .resource_mut::<NavGraph<Vehicles>>()
+            .connect_points(1, 2);
+
+        let traveler = || AutoTraveler::new(1, 2, 1.0).with_origin_claim(OriginClaim::Fail);
+        let person = app.world.spawn((traveler(), People)).id();
+        let vehicle = app.world.spawn((traveler(), Vehicles)).id();
+        app.update();
+
+        let failed = app.world.resource::<Events<TravelFailed<People>>>();
+        let failed: Vec<_> = failed.get_reader().iter(failed).map(|e| e.entity).collect();
+        assert_eq!(failed, [person]);
+        assert!(app
+            .world
+            .resource::<Events<TravelFailed<Vehicles>>>()
+            .is_empty());
+        assert!(app
+            .world
+            .get::<AutoTraveler>(vehicle)
+            .unwrap()
+            .path
+            .is_some());
+        assert!(!app.world.resource::<NavGraph<Vehicles>>().can_occupy(1));
+        assert!(app.world.resource::<NavGraph<People>>().can_occupy(1));
+    }
+}
@@ -0,0 +1,130 @@
+use std::{error::Error, fmt};
+
+use bevy_utils::HashSet;
+
+use crate::{NavDomain, NavGraph, PathPolicy, SearchOptions, TerritoryAccess};
+
+/// Why a navigation operation failed.
+///
+/// Returned by the `try_` variants of [`NavGraph`] methods, such as
+/// [`NavGraph::try_find_path`], which otherwise fail silently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavError {
+    /// There's no [`NavPoint`](crate::NavPoint) with this ID in the graph.
+    UnknownNode(u32),
+    /// The points aren't connected at all.
+    Unreachable,
+    /// A path exists, but full, excluded or hostile points are in the way.
+    Blocked,
+    /// A path exists, but only one with more points than
+    /// [`SearchOptions::max_nodes`](crate::SearchOptions::max_nodes) allows, or the search gave
+    /// up after expanding [`SearchOptions::max_expanded`](crate::SearchOptions::max_expanded)
+    /// points before finding it.
+    BudgetExceeded,
+}
+
+impl fmt::Display for NavError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NavError::UnknownNode(id) => write!(f, "no nav point with ID {id}"),
+            NavError::Unreachable => write!(f, "no path between the points exists"),
+            NavError::Blocked => write!(f, "the path is blocked"),
+            NavError::BudgetExceeded => write!(f, "the search budget was exceeded"),
+        }
+    }
+}
+
+impl Error for NavError {}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Like [`NavGraph::connect_points`], but fails if either point doesn't exist.
+    ///
+    /// Connecting a point to itself does nothing.
+    pub fn try_connect_points(&mut self, a: u32, b: u32) -> Result<(), NavError> {
+        self.check_exists(a)?;
+        self.check_exists(b)?;
+        self.connect_points(a, b);
+        Ok(())
+    }
+
+    /// Like [`NavGraph::find_path`], but reports why no path was found.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavError, NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// for id in 1..=4 {
+    ///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    ///
+    /// assert_eq!(nav_graph.try_find_path(1, 5), Err(NavError::UnknownNode(5)));
+    /// assert_eq!(nav_graph.try_find_path(1, 4), Err(NavError::Unreachable));
+    /// nav_graph.occupy(2);
+    /// assert_eq!(nav_graph.try_find_path(1, 3), Err(NavError::Blocked));
+    /// ```
+    pub fn try_find_path(&self, a: u32, b: u32) -> Result<Vec<u32>, NavError> {
+        self.try_find_path_with(a, b, &SearchOptions::default())
+    }
+
+    /// Like [`NavGraph::find_path_with`], but reports why no path was found.
+    ///
+    /// Working out why takes further searches with the constraints in `options` relaxed, so
+    /// failures cost more than with [`NavGraph::find_path_with`].
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavError, NavGraph, NavPoint, SearchOptions};
+    /// let mut nav_graph = NavGraph::new();
+    /// for id in 1..=4 {
+    ///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// for id in 1..4 {
+    ///     nav_graph.connect_points(id, id + 1);
+    /// }
+    ///
+    /// let options = SearchOptions::new().with_max_expanded(1);
+    /// assert_eq!(
+    ///     nav_graph.try_find_path_with(1, 4, &options),
+    ///     Err(NavError::BudgetExceeded)
+    /// );
+    /// ```
+    pub fn try_find_path_with(
+        &self,
+        a: u32,
+        b: u32,
+        options: &SearchOptions,
+    ) -> Result<Vec<u32>, NavError> {
+        if let Some(path) = self.find_path_with(a, b, options) {
+            return Ok(path);
+        }
+        self.check_exists(a)?;
+        self.check_exists(b)?;
+
+        let mut relaxed = options.clone();
+        relaxed.max_nodes = None;
+        relaxed.max_expanded = None;
+        relaxed.path_policy = PathPolicy::CompleteOnly;
+        let limited = options.max_nodes.is_some() || options.max_expanded.is_some();
+        if limited && self.search(a, b, &relaxed).is_some() {
+            return Err(NavError::BudgetExceeded);
+        }
+        relaxed.ignore_occupancy = true;
+        relaxed.excluded = HashSet::default();
+        relaxed.territory = TerritoryAccess::Allow;
+        match self.search(a, b, &relaxed) {
+            Some(_) => Err(NavError::Blocked),
+            None => Err(NavError::Unreachable),
+        }
+    }
+
+    fn check_exists(&self, id: u32) -> Result<(), NavError> {
+        match self.has_nav_point(id) {
+            true => Ok(()),
+            false => Err(NavError::UnknownNode(id)),
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Buffered travel events.
+//!
+//! The Bevy version targeted here has no entity observers, so per-entity reactions should read
+//! these with an `EventReader` and match on `entity`.
+//!
+//! Each [`NavDomain`] sends its own events, so e.g. `TravelCompleted<Vehicles>` only reports
+//! travelers of the `Vehicles` domain.
+
+use std::marker::PhantomData;
+
+use bevy_ecs::entity::Entity;
+
+use crate::{CargoAction, NavDomain};
+
+/// Sent when a traveler reaches the end of its path.
+///
+/// The traveler's [`AutoTraveler`](crate::AutoTraveler) is removed in the same frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TravelCompleted<M: NavDomain = ()> {
+    pub entity: Entity,
+    pub destination: u32,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> TravelCompleted<M> {
+    pub fn new(entity: Entity, destination: u32) -> Self {
+        Self {
+            entity,
+            destination,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Sent when no path could be found for a traveler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TravelFailed<M: NavDomain = ()> {
+    pub entity: Entity,
+    pub origin: u32,
+    pub destination: u32,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> TravelFailed<M> {
+    pub fn new(entity: Entity, origin: u32, destination: u32) -> Self {
+        Self {
+            entity,
+            origin,
+            destination,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Sent when a traveler is stopped before reaching its destination because its graph was
+/// replaced. See [`ReplaceNavGraph`](crate::ReplaceNavGraph).
+///
+/// The traveler's [`AutoTraveler`](crate::AutoTraveler) is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TravelAborted<M: NavDomain = ()> {
+    pub entity: Entity,
+    pub destination: u32,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> TravelAborted<M> {
+    pub fn new(entity: Entity, destination: u32) -> Self {
+        Self {
+            entity,
+            destination,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Sent when a traveler is held back from planning because its destination's quota is full.
+/// See [`NavGraph::set_destination_quota`](crate::NavGraph::set_destination_quota).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DestinationQueued<M: NavDomain = ()> {
+    pub entity: Entity,
+    pub destination: u32,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> DestinationQueued<M> {
+    pub fn new(entity: Entity, destination: u32) -> Self {
+        Self {
+            entity,
+            destination,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Sent when a traveler with
+/// [`DestinationBehavior::WithinRadius`](crate::DestinationBehavior::WithinRadius) finds its
+/// destination full and heads for a free point nearby instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DestinationSubstituted<M: NavDomain = ()> {
+    pub entity: Entity,
+    pub requested: u32,
+    pub substitute: u32,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> DestinationSubstituted<M> {
+    pub fn new(entity: Entity, requested: u32, substitute: u32) -> Self {
+        Self {
+            entity,
+            requested,
+            substitute,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Sent when a [`Carrier`](crate::Carrier) loads or unloads stock at a point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CargoTransferred<M: NavDomain = ()> {
+    pub entity: Entity,
+    pub nav_point: u32,
+    pub action: CargoAction,
+    pub amount: u32,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> CargoTransferred<M> {
+    pub fn new(entity: Entity, nav_point: u32, action: CargoAction, amount: u32) -> Self {
+        Self {
+            entity,
+            nav_point,
+            action,
+            amount,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Sent when a traveler with [`TerritoryAccess::AllowWithEvent`](crate::TerritoryAccess) starts
+/// moving onto a point owned by a faction hostile to its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnteredHostileTerritory<M: NavDomain = ()> {
+    pub entity: Entity,
+    pub point: u32,
+    pub owner: u32,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> EnteredHostileTerritory<M> {
+    pub fn new(entity: Entity, point: u32, owner: u32) -> Self {
+        Self {
+            entity,
+            point,
+            owner,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Sent when travelers lose the [`NavPoint`](crate::NavPoint) they were moving onto, because it
+/// was removed or closed, or its max_occupancy was lowered below its current occupancy.
+///
+/// The affected travelers release the point and are replanned, or wait for it to free up if
+/// they follow a fixed path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservationRevoked<M: NavDomain = ()> {
+    pub node: u32,
+    pub entities: Vec<Entity>,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> ReservationRevoked<M> {
+    pub fn new(node: u32, entities: Vec<Entity>) -> Self {
+        Self {
+            node,
+            entities,
+            marker: PhantomData,
+        }
+    }
+}
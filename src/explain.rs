@@ -0,0 +1,156 @@
+use crate::{navigation::travel_cost, NavDomain, NavGraph, SearchOptions, TerritoryAccess};
+
+/// What one connection of a path costs, and why, from [`NavGraph::explain_path`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentCost {
+    pub from: u32,
+    pub to: u32,
+    /// The straight line distance between the two points.
+    pub distance: f32,
+    /// The speed modifier of the connection under the graph's
+    /// [`EdgeSpeedModel`](crate::EdgeSpeedModel), which divides its cost.
+    pub speed_modifier: f32,
+    /// The cost of the connection going by `distance` and `speed_modifier` alone.
+    pub distance_cost: u32,
+    /// The connection's weight, which replaces `distance_cost` when set. See
+    /// [`NavGraph::connect_points_weighted`].
+    pub weight: Option<u32>,
+    /// The multiplier for the area types of `from` and `to` from [`SearchOptions::area_costs`],
+    /// combined by the graph's [`EdgeSpeedModel`](crate::EdgeSpeedModel), or `1.0` without area
+    /// costs.
+    pub area_factor: f32,
+    /// The multiplier for entering hostile territory, or `1.0` if not applied.
+    pub territory_factor: f32,
+    /// The multiplier from [`SearchOptions::noise`], or `1.0` without noise.
+    pub noise_factor: f32,
+    /// The multiplier for how crowded `to` is from [`SearchOptions::congestion_cost`], or `1.0`
+    /// without a congestion cost.
+    pub congestion_factor: f32,
+    /// The cost a search sees, after all of the above.
+    pub cost: u32,
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Breaks the cost of `path` down by connection, to find out why a search preferred it.
+    ///
+    /// The costs add up to [`NavGraph::path_cost`]. Returns `None` if any consecutive points in
+    /// the path aren't connected.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(2.0, 0.0, 0.0), 0.5, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(3.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points_weighted(2, 3, 7);
+    ///
+    /// let segments = nav_graph.explain_path(&[1, 2, 3]).unwrap();
+    /// // Slow going onto 2 doubles the cost of the first connection.
+    /// assert_eq!((segments[0].distance_cost, segments[0].cost), (800, 800));
+    /// // The second connection's weight takes the place of its distance.
+    /// assert_eq!((segments[1].distance_cost, segments[1].cost), (100, 7));
+    /// ```
+    pub fn explain_path(&self, path: &[u32]) -> Option<Vec<SegmentCost>> {
+        self.explain_path_with(path, &SearchOptions::default())
+    }
+
+    /// Like [`NavGraph::explain_path`], with the custom costs, area costs, territory penalties,
+    /// congestion costs and noise of `options`, as seen by a traveler searching with them.
+    /// Custom costs only show in `cost`.
+    pub fn explain_path_with(
+        &self,
+        path: &[u32],
+        options: &SearchOptions,
+    ) -> Option<Vec<SegmentCost>> {
+        path.windows(2)
+            .map(|pair| {
+                let (from, to) = (pair[0], pair[1]);
+                let (from_point, to_point) = (self.points.get(&from)?, self.points.get(&to)?);
+                if !from_point.connections.contains(&to) {
+                    return None;
+                }
+                let area_factor = options.area_costs.as_ref().map_or(1.0, |area_costs| {
+                    area_costs.edge_multiplier(
+                        self.edge_speed_model(),
+                        from_point.area(),
+                        to_point.area(),
+                    )
+                });
+                let territory_factor = match options.territory {
+                    TerritoryAccess::Penalize(factor)
+                        if self.is_hostile_to(to, options.faction) =>
+                    {
+                        factor
+                    }
+                    _ => 1.0,
+                };
+                let noise_factor = match options.noise {
+                    Some(noise) if noise.magnitude > 0.0 => noise.factor(from, to),
+                    _ => 1.0,
+                };
+                let congestion_factor = if options.congestion_cost > 0.0 {
+                    options.congestion_factor(to_point)
+                } else {
+                    1.0
+                };
+                let speed_modifier = self
+                    .edge_speed_model()
+                    .effective(from_point.speed_modifier(), to_point.speed_modifier());
+                Some(SegmentCost {
+                    from,
+                    to,
+                    distance: from_point.location().distance(to_point.location()),
+                    speed_modifier,
+                    distance_cost: travel_cost(
+                        from_point.location(),
+                        to_point.location(),
+                        speed_modifier,
+                    ),
+                    weight: self.edge(from, to).and_then(|edge| edge.weight),
+                    area_factor,
+                    territory_factor,
+                    noise_factor,
+                    congestion_factor,
+                    cost: self.edge_cost(from, to, options),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::NavPoint;
+
+    #[test]
+    pub fn test_explained_costs_add_up() {
+        let mut nav_graph = NavGraph::new();
+        for (id, x, speed_modifier) in [(1, 0.0, 1.0), (2, 1.5, 2.0), (3, 4.0, 0.25)] {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, 0.0, 0.0), speed_modifier, 1));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+
+        let path = [1, 2, 3];
+        let segments = nav_graph.explain_path(&path).unwrap();
+        assert_eq!(
+            segments.iter().map(|segment| segment.cost).sum::<u32>(),
+            nav_graph.path_cost(&path).unwrap()
+        );
+        assert_eq!(segments[1].speed_modifier, 0.25);
+
+        let options = SearchOptions::default().with_noise(7, 0.5);
+        let noisy = nav_graph.explain_path_with(&path, &options).unwrap();
+        for (plain, noisy) in segments.iter().zip(&noisy) {
+            assert_eq!(noisy.cost, (plain.cost as f32 * noisy.noise_factor) as u32);
+        }
+
+        assert_eq!(nav_graph.explain_path(&[1, 3]), None);
+    }
+}
@@ -0,0 +1,161 @@
+use std::io::{self, Write};
+
+use crate::{NavDomain, NavGraph};
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Counts a traveler moving along the connection from `from` to `to`, for
+    /// [`NavGraph::traversal_count`] and the traffic columns of the CSV exports.
+    ///
+    /// Travelers record their moves as they arrive at each point, so this is only needed for
+    /// entities moved by other means.
+    pub fn record_traversal(&mut self, from: u32, to: u32) {
+        *self.traversals.entry((from, to)).or_default() += 1;
+    }
+
+    /// How many times travelers have moved along the connection from `from` to `to`.
+    pub fn traversal_count(&self, from: u32, to: u32) -> u32 {
+        self.traversals.get(&(from, to)).copied().unwrap_or(0)
+    }
+
+    /// Forgets the traversals recorded so far, e.g. at the start of a playtest.
+    pub fn clear_traversals(&mut self) {
+        self.traversals.clear();
+    }
+
+    /// Writes a CSV table of the graph's points, one row per point in order of ID, for
+    /// analyzing traffic in spreadsheets or notebooks.
+    ///
+    /// The columns are `id`, `x`, `y`, `z`, `tags`, `current_occupancy`, `max_occupancy`,
+    /// `expected_wait` (empty until known, see [`NavGraph::expected_wait`]) and `arrivals`, the
+    /// total traversals of the connections into the point.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 2));
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.record_traversal(1, 2);
+    ///
+    /// let mut csv = Vec::new();
+    /// nav_graph.write_points_csv(&mut csv).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(csv).unwrap(),
+    ///     "id,x,y,z,tags,current_occupancy,max_occupancy,expected_wait,arrivals\n\
+    ///      1,0,0,0,0,0,1,,0\n\
+    ///      2,1,0,0,0,0,2,,1\n"
+    /// );
+    /// ```
+    pub fn write_points_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(
+            writer,
+            "id,x,y,z,tags,current_occupancy,max_occupancy,expected_wait,arrivals"
+        )?;
+        let mut ids: Vec<u32> = self.points.keys().copied().collect();
+        ids.sort_unstable();
+        for id in ids {
+            let point = &self.points[&id];
+            let location = point.location();
+            let expected_wait = self
+                .expected_wait(id)
+                .map(|wait| wait.to_string())
+                .unwrap_or_default();
+            let arrivals: u32 = point
+                .connections()
+                .iter()
+                .map(|from| self.traversal_count(*from, id))
+                .sum();
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{}",
+                id,
+                location.x,
+                location.y,
+                location.z,
+                point.tags(),
+                point.current_occupancy(),
+                point.max_occupancy(),
+                expected_wait,
+                arrivals
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes a CSV table of the graph's connections, one row per direction of each connection
+    /// in order of the IDs of its ends.
+    ///
+    /// The columns are `from`, `to`, `cost`, `weight` (empty unless set, see
+    /// [`NavGraph::connect_points_weighted`]) and `traversals`, see
+    /// [`NavGraph::traversal_count`].
+    pub fn write_connections_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "from,to,cost,weight,traversals")?;
+        let mut connections: Vec<(u32, u32)> = self
+            .points
+            .values()
+            .flat_map(|point| point.connections().iter().map(|to| (point.id(), *to)))
+            .collect();
+        connections.sort_unstable();
+        for (from, to) in connections {
+            let cost = self.points[&from]
+                .costs
+                .get(&to)
+                .map(|cost| cost.to_string())
+                .unwrap_or_default();
+            let weight = self
+                .edge(from, to)
+                .and_then(|edge| edge.weight)
+                .map(|weight| weight.to_string())
+                .unwrap_or_default();
+            writeln!(
+                writer,
+                "{},{},{},{},{}",
+                from,
+                to,
+                cost,
+                weight,
+                self.traversal_count(from, to)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::NavPoint;
+
+    #[test]
+    pub fn test_connections_csv() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(3.0, 4.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(3.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points_weighted(2, 3, 7);
+        for _ in 0..3 {
+            nav_graph.record_traversal(2, 1);
+        }
+        nav_graph.record_traversal(3, 2);
+        nav_graph.remove_point(3);
+        nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(3.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(2, 3);
+
+        let mut csv = Vec::new();
+        nav_graph.write_connections_csv(&mut csv).unwrap();
+        let cost = nav_graph.path_cost(&[1, 2]).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            format!(
+                "from,to,cost,weight,traversals\n1,2,{cost},,0\n2,1,{cost},,3\n2,3,{},,0\n3,2,{},,0\n",
+                nav_graph.path_cost(&[2, 3]).unwrap(),
+                nav_graph.path_cost(&[3, 2]).unwrap(),
+            )
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use bevy_ecs::system::Resource;
+#[cfg(feature = "travelers")]
+use bevy_ecs::system::{Res, ResMut};
+use bevy_math::Vec3;
+#[cfg(feature = "travelers")]
+use bevy_time::Time;
+use bevy_utils::HashMap;
+
+use crate::{NavDomain, NavGraph, NavPoint};
+
+/// The recorded state of one [`NavPoint`], apart from its connections.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointState {
+    pub id: u32,
+    pub location: Vec3,
+    pub speed_modifier: f32,
+    pub max_occupancy: u32,
+    pub current_occupancy: u32,
+    pub region: u32,
+    pub tags: u32,
+    pub flags: u32,
+    pub area: u32,
+    pub clearance: f32,
+}
+
+impl PointState {
+    fn of(point: &NavPoint) -> Self {
+        Self {
+            id: point.id(),
+            location: point.location(),
+            speed_modifier: point.speed_modifier(),
+            max_occupancy: point.max_occupancy(),
+            current_occupancy: point.current_occupancy(),
+            region: point.region(),
+            tags: point.tags(),
+            flags: point.flags(),
+            area: point.area(),
+            clearance: point.clearance(),
+        }
+    }
+
+    fn apply_to(&self, point: &mut NavPoint) {
+        point.set_location(self.location);
+        point.set_speed_modifier(self.speed_modifier);
+        point.set_max_occupancy(self.max_occupancy);
+        point.set_current_occupancy(self.current_occupancy);
+        point.set_region(self.region);
+        point.set_tags(self.tags);
+        point.set_flags(self.flags);
+        point.set_area(self.area);
+        point.set_clearance(self.clearance);
+    }
+}
+
+/// One change to a [`NavGraph`] found by [`GraphLog::record`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphChange {
+    AddPoint(PointState),
+    /// Any of the point's attributes, including its occupancy, changed.
+    UpdatePoint(PointState),
+    RemovePoint(u32),
+    /// A connection was added, or its weight changed. See
+    /// [`NavGraph::connect_points_weighted`].
+    Connect {
+        a: u32,
+        b: u32,
+        weight: Option<u32>,
+    },
+    Disconnect {
+        a: u32,
+        b: u32,
+    },
+}
+
+/// A [`GraphChange`] and when it was recorded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraphLogEntry {
+    pub tick: u64,
+    /// The time of the tick in seconds, e.g. of `Time::elapsed_seconds_f64`.
+    pub time: f64,
+    pub change: GraphChange,
+}
+
+/// An append-only log of the changes made to a [`NavGraph`] over time, from which the graph can
+/// be rebuilt as it was at any recorded tick, e.g. to replay a match or find out why a path
+/// couldn't be found at some point.
+///
+/// Changes are found by comparing the graph with its state at the previous
+/// [`GraphLog::record`], which looks at every point, so recording is best done once per frame
+/// at most. Inserting a `GraphLog` as a resource has the
+/// [`NavigatorPlugin`](crate::NavigatorPlugin) record the graph at the end of each frame.
+///
+/// Points, their attributes and occupancy, and connections and their weights are recorded.
+/// Other state, such as availability windows, territory and reservations, is kept as it was when
+/// the log was started.
+///
+/// ## Example
+/// ```
+/// # use bevy_math::Vec3;
+/// # use bevy_navigator::{GraphLog, NavGraph, NavPoint};
+/// let mut nav_graph = NavGraph::new();
+/// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+/// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+/// nav_graph.connect_points(1, 2);
+/// let mut log = GraphLog::new(&nav_graph, 0.0);
+///
+/// // The bridge collapses at tick 1.
+/// nav_graph.disconnect_points(1, 2);
+/// log.record(&nav_graph, 1, 0.5);
+///
+/// assert!(log.state_at(0).find_path(1, 2).is_some());
+/// assert!(log.state_at(1).find_path(1, 2).is_none());
+/// ```
+#[derive(Debug, Clone, Resource)]
+pub struct GraphLog<M: NavDomain = ()> {
+    base: NavGraph<M>,
+    points: HashMap<u32, PointState>,
+    connections: HashMap<(u32, u32), Option<u32>>,
+    start_time: f64,
+    entries: Vec<GraphLogEntry>,
+    tick: u64,
+}
+
+impl<M: NavDomain> GraphLog<M> {
+    /// Starts a log of `nav_graph` as it is at tick 0, `time` seconds in.
+    pub fn new(nav_graph: &NavGraph<M>, time: f64) -> Self {
+        Self {
+            base: nav_graph.clone(),
+            points: point_states(nav_graph),
+            connections: connections(nav_graph),
+            start_time: time,
+            entries: Vec::new(),
+            tick: 0,
+        }
+    }
+
+    /// Records the changes made to `nav_graph` since it was last recorded, as of `tick`.
+    ///
+    /// Ticks should only go up; entries are looked up by tick in the order they're recorded.
+    pub fn record(&mut self, nav_graph: &NavGraph<M>, tick: u64, time: f64) {
+        self.tick = tick;
+        let mut push = |change| {
+            self.entries.push(GraphLogEntry { tick, time, change });
+        };
+
+        let connections = connections(nav_graph);
+        for key in self.connections.keys() {
+            if !connections.contains_key(key) {
+                push(GraphChange::Disconnect { a: key.0, b: key.1 });
+            }
+        }
+        let points = point_states(nav_graph);
+        for id in self.points.keys() {
+            if !points.contains_key(id) {
+                push(GraphChange::RemovePoint(*id));
+            }
+        }
+        for (id, state) in &points {
+            match self.points.get(id) {
+                None => push(GraphChange::AddPoint(*state)),
+                Some(previous) if previous != state => push(GraphChange::UpdatePoint(*state)),
+                Some(_) => {}
+            }
+        }
+        for (key, weight) in &connections {
+            if self.connections.get(key) != Some(weight) {
+                push(GraphChange::Connect {
+                    a: key.0,
+                    b: key.1,
+                    weight: *weight,
+                });
+            }
+        }
+        self.points = points;
+        self.connections = connections;
+    }
+
+    /// The changes recorded so far, oldest first.
+    pub fn entries(&self) -> &[GraphLogEntry] {
+        &self.entries
+    }
+
+    /// The last tick recorded, or 0 before anything has been.
+    pub fn last_tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// A tick whose state is the graph's at `time` seconds, for [`GraphLog::state_at`], or
+    /// `None` if the log was started after then.
+    pub fn tick_at(&self, time: f64) -> Option<u64> {
+        if time < self.start_time {
+            return None;
+        }
+        Some(
+            self.entries
+                .iter()
+                .take_while(|entry| entry.time <= time)
+                .last()
+                .map_or(0, |entry| entry.tick),
+        )
+    }
+
+    /// Rebuilds the graph as it was at `tick`, after the changes recorded for it.
+    pub fn state_at(&self, tick: u64) -> NavGraph<M> {
+        let mut nav_graph = self.base.clone();
+        for entry in self.entries.iter().take_while(|entry| entry.tick <= tick) {
+            match entry.change {
+                GraphChange::AddPoint(state) => {
+                    let mut point = NavPoint::new(
+                        state.id,
+                        state.location,
+                        state.speed_modifier,
+                        state.max_occupancy,
+                    );
+                    state.apply_to(&mut point);
+                    nav_graph.add_nav_point(point);
+                }
+                GraphChange::UpdatePoint(state) => {
+                    nav_graph.modify(state.id, |point| state.apply_to(point));
+                }
+                GraphChange::RemovePoint(id) => nav_graph.remove_point(id),
+                GraphChange::Connect { a, b, weight } => {
+                    nav_graph.connect_points(a, b);
+                    nav_graph.set_connection_weight(a, b, weight);
+                }
+                GraphChange::Disconnect { a, b } => nav_graph.disconnect_points(a, b),
+            }
+        }
+        nav_graph
+    }
+}
+
+fn point_states<M: NavDomain>(nav_graph: &NavGraph<M>) -> HashMap<u32, PointState> {
+    nav_graph
+        .iter_points()
+        .map(|point| (point.id(), PointState::of(point)))
+        .collect()
+}
+
+/// Each connection of `nav_graph` by the IDs of its ends, lowest first, with its weight.
+fn connections<M: NavDomain>(nav_graph: &NavGraph<M>) -> HashMap<(u32, u32), Option<u32>> {
+    nav_graph
+        .iter_points()
+        .flat_map(|point| {
+            point
+                .connections()
+                .iter()
+                .filter(move |other| point.id() < **other)
+                .map(move |other| {
+                    let weight = nav_graph
+                        .edge(point.id(), *other)
+                        .and_then(|edge| edge.weight);
+                    ((point.id(), *other), weight)
+                })
+        })
+        .collect()
+}
+
+#[cfg(feature = "travelers")]
+pub(crate) fn record_graph_changes<M: NavDomain>(
+    log: Option<ResMut<GraphLog<M>>>,
+    nav_graph: Res<NavGraph<M>>,
+    time: Res<Time>,
+) {
+    if let Some(mut log) = log {
+        let tick = log.last_tick() + 1;
+        log.record(&nav_graph, tick, time.elapsed_seconds_f64());
+    }
+}
+
+#[cfg(all(test, feature = "travelers"))]
+mod tests {
+    use bevy_app::App;
+    use bevy_utils::Duration;
+
+    use super::*;
+    use crate::{GridConnectivity, NavigatorPlugin};
+
+    #[test]
+    pub fn test_graph_log_replays_changes() {
+        let mut app = App::new();
+        app.add_plugin(NavigatorPlugin::new())
+            .insert_resource(Time::default());
+        let (nav_graph, _) = NavGraph::<()>::from_grid(3, 1, 1.0, GridConnectivity::Four);
+        app.insert_resource(GraphLog::new(&nav_graph, 0.0))
+            .insert_resource(nav_graph);
+
+        let start = app.world.resource::<Time>().startup();
+        let mut advance_to = |seconds: f32, change: fn(&mut NavGraph)| {
+            change(&mut app.world.resource_mut::<NavGraph>());
+            app.world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_secs_f32(seconds));
+            app.update();
+        };
+        advance_to(1.0, |nav_graph| {
+            nav_graph.occupy(2);
+        });
+        advance_to(2.0, |_| {});
+        advance_to(3.0, |nav_graph| {
+            nav_graph.remove_point(2);
+            nav_graph.connect_points_weighted(1, 3, 500);
+        });
+        advance_to(4.0, |nav_graph| {
+            nav_graph.occupy(3);
+        });
+
+        let log = app.world.resource::<GraphLog>();
+        assert_eq!(log.last_tick(), 4);
+        // Removing 2 also severs both of its connections.
+        assert_eq!(log.entries().len(), 6);
+        assert_eq!(log.tick_at(2.5), Some(1));
+        assert_eq!(log.tick_at(-1.0), None);
+
+        let at = |tick| log.state_at(tick);
+        assert!(at(0).can_occupy(2));
+        assert!(!at(1).can_occupy(2));
+        assert_eq!(at(2).find_path(1, 3), None);
+        assert_eq!(at(3).find_path(1, 3), Some(vec![1, 3]));
+        assert_eq!(at(3).path_cost(&[1, 3]), Some(500));
+        assert!(!at(3).has_nav_point(2));
+        assert!(at(3).can_occupy(3) && !at(4).can_occupy(3));
+    }
+}
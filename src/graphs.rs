@@ -0,0 +1,291 @@
+use std::marker::PhantomData;
+
+#[cfg(feature = "travelers")]
+use bevy_ecs::system::ResMut;
+use bevy_ecs::system::{Res, Resource, SystemParam};
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_utils::HashMap;
+
+use crate::{NavDomain, NavGraph};
+
+/// Identifies one of the graphs in a [`NavGraphs`] resource.
+///
+/// IDs can be anything unique within a domain, such as the index of a level, or derived from a
+/// label with [`NavGraphId::named`].
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Reflect, FromReflect,
+)]
+pub struct NavGraphId(pub u64);
+
+impl NavGraphId {
+    /// The ID for `label`. The same label always gives the same ID, so IDs made this way can be
+    /// stored in save files.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_navigator::NavGraphId;
+    /// const TAVERN: NavGraphId = NavGraphId::named("tavern");
+    /// assert_eq!(TAVERN, NavGraphId::named("tavern"));
+    /// assert_ne!(TAVERN, NavGraphId::named("smithy"));
+    /// ```
+    pub const fn named(label: &str) -> Self {
+        // 64-bit FNV-1a.
+        let bytes = label.as_bytes();
+        let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+        let mut i = 0;
+        while i < bytes.len() {
+            hash ^= bytes[i] as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+            i += 1;
+        }
+        Self(hash)
+    }
+}
+
+/// The [`NavGraph`]s of the domain `M` besides its [`NavGraph<M>`] resource, for maps loaded
+/// side by side at runtime, e.g. the interiors of an overworld's buildings.
+///
+/// Each graph has its own points, IDs and occupancy. An [`AutoTraveler`](crate::AutoTraveler)
+/// paths against the graph named by its [`graph`](crate::AutoTraveler::graph), or the
+/// [`NavGraph<M>`] resource if it has none. Travelers whose graph is missing fail to plan, and
+/// otherwise wait until it's back.
+///
+/// The [`PathCache<M>`](crate::PathCache), [`TravelerIndex<M>`](crate::TravelerIndex),
+/// [`SpaceTimeReservations<M>`](crate::SpaceTimeReservations),
+/// [`NavPlatform`](crate::NavPlatform)s, [`Carrier`](crate::Carrier)s and the asset loader only
+/// ever work with the [`NavGraph<M>`] resource, though cooperative travelers on the same graph
+/// here still plan around each other. To swap out one of these graphs while travelers are using
+/// it, see [`ReplaceNavGraph::on_graph`](crate::ReplaceNavGraph::on_graph).
+///
+/// ## Example
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_math::Vec3;
+/// # use bevy_navigator::{GridConnectivity, NavGraph, NavGraphId, NavGraphs, NavigatorPlugin};
+/// # #[cfg(feature = "travelers")]
+/// # use bevy_navigator::AutoTraveler;
+/// const TAVERN: NavGraphId = NavGraphId::named("tavern");
+///
+/// let mut app = App::new();
+/// app.add_plugin(NavigatorPlugin::<()>::default());
+///
+/// // Loaded when the player walks in, with IDs of its own.
+/// let (interior, _) = NavGraph::from_grid(4, 4, 1.0, GridConnectivity::Four);
+/// app.world.resource_mut::<NavGraphs>().insert(TAVERN, interior);
+///
+/// # #[cfg(feature = "travelers")]
+/// app.world.spawn(AutoTraveler::new(1, 16, 1.0).with_graph(TAVERN));
+/// ```
+#[derive(Debug, Resource)]
+pub struct NavGraphs<M: NavDomain = ()> {
+    graphs: HashMap<NavGraphId, NavGraph<M>>,
+}
+
+impl<M: NavDomain> Default for NavGraphs<M> {
+    fn default() -> Self {
+        Self {
+            graphs: HashMap::default(),
+        }
+    }
+}
+
+impl<M: NavDomain> NavGraphs<M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the graph `id`, returning the graph it replaces, if any.
+    ///
+    /// Travelers on a replaced graph keep the IDs of the old one, see
+    /// [`ReplaceNavGraph::on_graph`](crate::ReplaceNavGraph::on_graph).
+    pub fn insert(&mut self, id: NavGraphId, nav_graph: NavGraph<M>) -> Option<NavGraph<M>> {
+        self.graphs.insert(id, nav_graph)
+    }
+
+    /// Removes the graph `id`, e.g. when its map is unloaded.
+    pub fn remove(&mut self, id: NavGraphId) -> Option<NavGraph<M>> {
+        self.graphs.remove(&id)
+    }
+
+    pub fn get(&self, id: NavGraphId) -> Option<&NavGraph<M>> {
+        self.graphs.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: NavGraphId) -> Option<&mut NavGraph<M>> {
+        self.graphs.get_mut(&id)
+    }
+
+    pub fn contains(&self, id: NavGraphId) -> bool {
+        self.graphs.contains_key(&id)
+    }
+
+    /// The number of graphs, not counting the [`NavGraph<M>`] resource.
+    pub fn len(&self) -> usize {
+        self.graphs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.graphs.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (NavGraphId, &NavGraph<M>)> {
+        self.graphs.iter().map(|(id, nav_graph)| (*id, nav_graph))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (NavGraphId, &mut NavGraph<M>)> {
+        self.graphs
+            .iter_mut()
+            .map(|(id, nav_graph)| (*id, nav_graph))
+    }
+}
+
+/// The graphs travelers of the domain `M` path against: the [`NavGraph<M>`] resource for `None`,
+/// and the [`NavGraphs<M>`] for the rest.
+#[derive(SystemParam)]
+pub(crate) struct TravelerGraphs<'w, 's, M: NavDomain> {
+    main: Res<'w, NavGraph<M>>,
+    others: Option<Res<'w, NavGraphs<M>>>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+impl<'w, 's, M: NavDomain> TravelerGraphs<'w, 's, M> {
+    pub fn get(&self, id: Option<NavGraphId>) -> Option<&NavGraph<M>> {
+        match id {
+            None => Some(&self.main),
+            Some(id) => self.others.as_ref()?.get(id),
+        }
+    }
+}
+
+/// Like [`TravelerGraphs`], for systems which change the graphs.
+#[cfg(feature = "travelers")]
+#[derive(SystemParam)]
+pub(crate) struct TravelerGraphsMut<'w, 's, M: NavDomain> {
+    main: ResMut<'w, NavGraph<M>>,
+    others: Option<ResMut<'w, NavGraphs<M>>>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+#[cfg(feature = "travelers")]
+impl<'w, 's, M: NavDomain> TravelerGraphsMut<'w, 's, M> {
+    pub fn get(&self, id: Option<NavGraphId>) -> Option<&NavGraph<M>> {
+        match id {
+            None => Some(&self.main),
+            Some(id) => self.others.as_ref()?.get(id),
+        }
+    }
+
+    pub fn get_mut(&mut self, id: Option<NavGraphId>) -> Option<&mut NavGraph<M>> {
+        match id {
+            None => Some(&mut self.main),
+            Some(id) => self.others.as_mut()?.get_mut(id),
+        }
+    }
+
+    /// Every graph, the [`NavGraph<M>`] resource first.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Option<NavGraphId>, &mut NavGraph<M>)> {
+        let others = self.others.as_deref_mut().into_iter().flat_map(|others| {
+            others
+                .iter_mut()
+                .map(|(id, nav_graph)| (Some(id), nav_graph))
+        });
+        std::iter::once((None, &mut *self.main)).chain(others)
+    }
+}
+
+#[cfg(all(test, feature = "travelers"))]
+mod tests {
+    use bevy_ecs::{event::Events, system::Command};
+    use bevy_transform::prelude::Transform;
+
+    use super::*;
+    use crate::{
+        testing::{test_app, NavTestApp},
+        AutoTraveler, GridConnectivity, OriginClaim, ReplaceNavGraph, TravelFailed,
+    };
+
+    const TAVERN: NavGraphId = NavGraphId::named("tavern");
+
+    fn occupancy(nav_graph: &NavGraph, id: u32) -> u32 {
+        nav_graph.get_nav_point(id).unwrap().current_occupancy()
+    }
+
+    #[test]
+    pub fn test_traveler_paths_on_its_graph() {
+        let mut app = test_app();
+        // The same IDs as the tavern, but nothing connects them.
+        let (mut nav_graph, _) = NavGraph::<()>::from_grid(3, 1, 1.0, GridConnectivity::Four);
+        nav_graph.disconnect_points(1, 2);
+        app.insert_resource(nav_graph);
+        let (interior, _) = NavGraph::from_grid(3, 1, 1.0, GridConnectivity::Four);
+        app.world
+            .resource_mut::<NavGraphs>()
+            .insert(TAVERN, interior);
+
+        let traveler = app
+            .world
+            .spawn((
+                Transform::default(),
+                AutoTraveler::new(1, 3, 1.0)
+                    .with_graph(TAVERN)
+                    .with_origin_claim(OriginClaim::Fail),
+            ))
+            .id();
+        app.update();
+        let tavern = app.world.resource::<NavGraphs>().get(TAVERN).unwrap();
+        assert_eq!(occupancy(tavern, 1), 1);
+        app.assert_occupancy(1, 0);
+
+        app.advance_until_arrived(traveler, 0.5, 10.0);
+        let tavern = app.world.resource::<NavGraphs>().get(TAVERN).unwrap();
+        assert_eq!(occupancy(tavern, 3), 1);
+        app.assert_occupancy(3, 0);
+    }
+
+    #[test]
+    pub fn test_traveler_on_missing_graph_fails() {
+        let mut app = test_app();
+        let (nav_graph, _) = NavGraph::<()>::from_grid(3, 1, 1.0, GridConnectivity::Four);
+        app.insert_resource(nav_graph);
+
+        let traveler = app
+            .world
+            .spawn(AutoTraveler::new(1, 3, 1.0).with_graph(TAVERN))
+            .id();
+        app.update();
+
+        let failed = app.world.resource::<Events<TravelFailed>>();
+        let failed: Vec<_> = failed.get_reader().iter(failed).map(|e| e.entity).collect();
+        assert_eq!(failed, [traveler]);
+        app.assert_occupancy(1, 0);
+    }
+
+    #[test]
+    pub fn test_replacing_graph_leaves_others_alone() {
+        let mut app = test_app();
+        let (nav_graph, _) = NavGraph::<()>::from_grid(3, 1, 1.0, GridConnectivity::Four);
+        app.insert_resource(nav_graph);
+        let (interior, _) = NavGraph::from_grid(3, 1, 1.0, GridConnectivity::Four);
+        app.world
+            .resource_mut::<NavGraphs>()
+            .insert(TAVERN, interior);
+
+        let traveler = || AutoTraveler::new(1, 3, 1.0).with_origin_claim(OriginClaim::Fail);
+        let outside = app.world.spawn(traveler()).id();
+        let inside = app.world.spawn(traveler().with_graph(TAVERN)).id();
+        app.update();
+
+        let (interior, _) = NavGraph::from_grid(2, 1, 1.0, GridConnectivity::Four);
+        ReplaceNavGraph::new(interior)
+            .on_graph(TAVERN)
+            .write(&mut app.world);
+
+        assert!(app.world.get::<AutoTraveler>(outside).is_some());
+        assert!(app.world.get::<AutoTraveler>(inside).is_none());
+        app.assert_occupancy(1, 1);
+        let tavern = app.world.resource::<NavGraphs>().get(TAVERN).unwrap();
+        assert_eq!(tavern.len(), 2);
+    }
+}
@@ -0,0 +1,288 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use bevy_utils::HashMap;
+
+use crate::{memory::map_bytes, NavDomain, NavGraph};
+
+/// The points on the borders between regions, and the costs of moving between them, for
+/// [`NavGraph::find_path_hierarchical`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct PortalGraph {
+    topology_version: Option<u64>,
+    /// The portals of each region: its points with a connection into another region.
+    portals: HashMap<u32, Vec<u32>>,
+    /// The cost of moving from each portal to the portals it reaches directly, whether through
+    /// its region or across a connection into the next one.
+    edges: HashMap<u32, Vec<(u32, u32)>>,
+}
+
+impl PortalGraph {
+    pub fn memory_usage(&self) -> usize {
+        map_bytes(&self.portals)
+            + map_bytes(&self.edges)
+            + self
+                .portals
+                .values()
+                .map(|portals| portals.capacity() * std::mem::size_of::<u32>())
+                .sum::<usize>()
+            + self
+                .edges
+                .values()
+                .map(|edges| edges.capacity() * std::mem::size_of::<(u32, u32)>())
+                .sum::<usize>()
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.portals.shrink_to_fit();
+        self.edges.shrink_to_fit();
+    }
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Rebuilds the portal graph used by [`NavGraph::find_path_hierarchical`], if the graph has
+    /// changed since it was last built.
+    ///
+    /// Portals are the points with a connection into another region, and the cost between every
+    /// pair of portals of a region is computed through that region alone, so this gets more
+    /// expensive as regions grow. Regions of a few hundred points suit most graphs; see
+    /// [`NavGraph::assign_grid_regions`].
+    pub fn refresh_hierarchy(&mut self) {
+        if self.hierarchy.topology_version == Some(self.topology_version()) {
+            return;
+        }
+
+        let mut portals = HashMap::<u32, Vec<u32>>::new();
+        let mut edges = HashMap::<u32, Vec<(u32, u32)>>::new();
+        for point in self.points.values() {
+            for other in &point.connections {
+                let Some(other_point) = self.points.get(other) else {
+                    continue;
+                };
+                if other_point.region() != point.region() {
+                    edges
+                        .entry(point.id())
+                        .or_default()
+                        .push((*other, self.connection_cost(point.id(), *other)));
+                }
+            }
+            if edges.contains_key(&point.id()) {
+                portals.entry(point.region()).or_default().push(point.id());
+            }
+        }
+
+        for (region, region_portals) in &mut portals {
+            region_portals.sort_unstable();
+            for portal in region_portals.iter() {
+                let reached = self.region_search(*portal, *region, None, false);
+                let portal_edges = edges.entry(*portal).or_default();
+                for other in region_portals.iter().filter(|other| *other != portal) {
+                    if let Some((cost, _)) = reached.get(other) {
+                        portal_edges.push((*other, *cost));
+                    }
+                }
+            }
+        }
+        for portal_edges in edges.values_mut() {
+            portal_edges.sort_unstable();
+        }
+
+        self.hierarchy = PortalGraph {
+            topology_version: Some(self.topology_version()),
+            portals,
+            edges,
+        };
+    }
+
+    /// Computes a path between two points by first planning between the portals of their
+    /// regions, then filling in the path through each region, which is much quicker than
+    /// [`NavGraph::find_path`] for long paths across large graphs.
+    ///
+    /// The portal graph describes the layout of the graph, so occupancy isn't considered, and the
+    /// path found is close to, but not always exactly, the cheapest. Points in the same region,
+    /// and any search while the portal graph is out of date (see
+    /// [`NavGraph::refresh_hierarchy`]), fall back to [`NavGraph::find_path`].
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_navigator::{GridConnectivity, NavGraph};
+    /// let (mut nav_graph, _) = NavGraph::<()>::from_grid(40, 40, 1.0, GridConnectivity::Four);
+    /// nav_graph.assign_grid_regions(8.0);
+    /// nav_graph.refresh_hierarchy();
+    ///
+    /// let path = nav_graph.find_path_hierarchical(1, 1600).unwrap();
+    /// assert_eq!((path[0], path[path.len() - 1]), (1, 1600));
+    /// ```
+    pub fn find_path_hierarchical(&self, a: u32, b: u32) -> Option<Vec<u32>> {
+        let (a_region, b_region) = (
+            self.get_nav_point(a)?.region(),
+            self.get_nav_point(b)?.region(),
+        );
+        if a_region == b_region || self.hierarchy.topology_version != Some(self.topology_version())
+        {
+            return self.find_path(a, b);
+        }
+
+        let from_a = self.region_search(a, a_region, None, false);
+        let to_b = self.region_search(b, b_region, None, true);
+        let no_portals = Vec::new();
+
+        // Dijkstra's algorithm over the portals, starting from every portal `a` reaches.
+        let mut costs = HashMap::<u32, u32>::new();
+        let mut came_from = HashMap::<u32, u32>::new();
+        let mut open_set = BinaryHeap::new();
+        for portal in self.hierarchy.portals.get(&a_region).unwrap_or(&no_portals) {
+            if let Some((cost, _)) = from_a.get(portal) {
+                costs.insert(*portal, *cost);
+                open_set.push(Reverse((*cost, *portal)));
+            }
+        }
+        let mut best: Option<(u32, u32)> = None;
+        while let Some(Reverse((cost, portal))) = open_set.pop() {
+            if best.is_some_and(|(best_cost, _)| cost >= best_cost) {
+                break;
+            }
+            if cost > costs[&portal] {
+                continue;
+            }
+            if let Some((rest, _)) = to_b.get(&portal) {
+                let total = cost.saturating_add(*rest);
+                if best.is_none_or(|best| (total, portal) < best) {
+                    best = Some((total, portal));
+                }
+            }
+            for (other, step) in self.hierarchy.edges.get(&portal).unwrap_or(&Vec::new()) {
+                let other_cost = cost.saturating_add(*step);
+                if other_cost < *costs.get(other).unwrap_or(&u32::MAX) {
+                    costs.insert(*other, other_cost);
+                    came_from.insert(*other, portal);
+                    open_set.push(Reverse((other_cost, *other)));
+                }
+            }
+        }
+        let (_, last_portal) = best?;
+
+        let mut portals = vec![last_portal];
+        while let Some(previous) = came_from.get(portals.last().unwrap()) {
+            portals.push(*previous);
+        }
+        portals.reverse();
+
+        // Fill in the path through each region, starting with the way from `a` to the first
+        // portal.
+        let mut path = trace(&from_a, portals[0]);
+        for pair in portals.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let region = self.points[&from].region();
+            if self.points[&to].region() != region {
+                path.push(to);
+                continue;
+            }
+            let reached = self.region_search(from, region, Some(to), false);
+            path.extend(trace(&reached, to).into_iter().skip(1));
+        }
+        let mut rest = trace(&to_b, last_portal);
+        rest.reverse();
+        path.extend(rest.into_iter().skip(1));
+        Some(path)
+    }
+
+    /// Runs Dijkstra's algorithm from `start` through the points of `region` only, returning the
+    /// cost of reaching each point and the point it's reached from, stopping early once `target`
+    /// is reached.
+    ///
+    /// With `reverse`, costs are of moving from each point to `start` instead, and each point's
+    /// "parent" is the next point on the way to `start`.
+    fn region_search(
+        &self,
+        start: u32,
+        region: u32,
+        target: Option<u32>,
+        reverse: bool,
+    ) -> HashMap<u32, (u32, u32)> {
+        let mut reached = HashMap::<u32, (u32, u32)>::new();
+        reached.insert(start, (0, start));
+        let mut open_set = BinaryHeap::new();
+        open_set.push(Reverse((0, start)));
+        while let Some(Reverse((cost, id))) = open_set.pop() {
+            if cost > reached[&id].0 {
+                continue;
+            }
+            if Some(id) == target {
+                break;
+            }
+            for neighbor in &self.points[&id].connections {
+                if self
+                    .points
+                    .get(neighbor)
+                    .is_none_or(|point| point.region() != region)
+                {
+                    continue;
+                }
+                let step = if reverse {
+                    self.connection_cost(*neighbor, id)
+                } else {
+                    self.connection_cost(id, *neighbor)
+                };
+                let neighbor_cost = cost.saturating_add(step);
+                if reached
+                    .get(neighbor)
+                    .is_none_or(|(known, _)| neighbor_cost < *known)
+                {
+                    reached.insert(*neighbor, (neighbor_cost, id));
+                    open_set.push(Reverse((neighbor_cost, *neighbor)));
+                }
+            }
+        }
+        reached
+    }
+}
+
+/// The path from the start of a [`NavGraph::region_search`] to `end`.
+fn trace(reached: &HashMap<u32, (u32, u32)>, end: u32) -> Vec<u32> {
+    let mut path = vec![end];
+    while let Some((_, parent)) = reached.get(path.last().unwrap()) {
+        if *parent == *path.last().unwrap() {
+            break;
+        }
+        path.push(*parent);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridConnectivity;
+
+    #[test]
+    pub fn test_hierarchical_paths_are_near_optimal() {
+        let (mut nav_graph, ids) = NavGraph::<()>::from_grid(30, 30, 1.0, GridConnectivity::Eight);
+        let id = |x, y| ids.id(x, y).unwrap();
+        // A wall down the middle, with a single gap near the bottom.
+        for y in 0..28 {
+            nav_graph.remove_point(id(15, y));
+        }
+        nav_graph.assign_grid_regions(6.0);
+        nav_graph.refresh_hierarchy();
+
+        for (a, b) in [
+            (id(0, 0), id(29, 29)),
+            (id(29, 0), id(0, 29)),
+            (id(0, 2), id(29, 14)),
+            (id(4, 0), id(5, 0)),
+        ] {
+            let path = nav_graph.find_path_hierarchical(a, b).unwrap();
+            assert_eq!((path[0], path[path.len() - 1]), (a, b));
+            let cost = nav_graph.path_cost(&path).unwrap();
+            let cheapest = nav_graph.cost_map(a, u32::MAX, None)[&b];
+            assert!(cost <= cheapest + cheapest / 4, "{} vs {}", cost, cheapest);
+        }
+
+        // Cut off by a change the portal graph doesn't know about yet, falling back to a
+        // regular search.
+        nav_graph.remove_point(id(15, 28));
+        nav_graph.remove_point(id(15, 29));
+        assert_eq!(nav_graph.find_path_hierarchical(id(0, 0), id(29, 29)), None);
+    }
+}
@@ -0,0 +1,141 @@
+use bevy_ecs::component::Component;
+use bevy_reflect::{FromReflect, Reflect};
+
+/// A single visit recorded in a [`TravelHistory`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect, FromReflect)]
+pub struct TravelHistoryEntry {
+    /// The ID of the visited [`NavPoint`](crate::NavPoint).
+    pub nav_point: u32,
+    /// The time the point was reached, in seconds since app startup.
+    pub time: f64,
+}
+
+/// An opt-in record of the [`NavPoint`](crate::NavPoint)s a traveler has reached.
+///
+/// Add this alongside an [`AutoTraveler`](crate::AutoTraveler) to have each reached point
+/// recorded along with the time it was reached. The history is a ring buffer, so once
+/// `capacity` visits have been recorded the oldest ones are overwritten.
+#[derive(Debug, Clone, Component, Reflect, FromReflect)]
+pub struct TravelHistory {
+    capacity: usize,
+    entries: Vec<TravelHistoryEntry>,
+    head: usize,
+}
+
+impl Default for TravelHistory {
+    fn default() -> Self {
+        Self::with_capacity(32)
+    }
+}
+
+impl TravelHistory {
+    /// Creates a new [`TravelHistory`] which remembers the last `capacity` visits.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Vec::with_capacity(capacity),
+            head: 0,
+        }
+    }
+
+    /// The maximum number of visits which will be remembered.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of visits currently remembered.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no visits have been recorded.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Records a visit to `nav_point` at `time`, overwriting the oldest visit if full.
+    pub fn record(&mut self, nav_point: u32, time: f64) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let entry = TravelHistoryEntry { nav_point, time };
+        if self.entries.len() < self.capacity {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.head] = entry;
+            self.head = (self.head + 1) % self.capacity;
+        }
+    }
+
+    /// Iterates over the remembered visits, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &TravelHistoryEntry> {
+        self.entries[self.head..]
+            .iter()
+            .chain(self.entries[..self.head].iter())
+    }
+
+    /// Returns the most recent visit, if any.
+    pub fn last(&self) -> Option<&TravelHistoryEntry> {
+        self.iter().next_back()
+    }
+
+    /// Returns the time of the most recent remembered visit to `nav_point`.
+    pub fn last_visit(&self, nav_point: u32) -> Option<f64> {
+        self.iter()
+            .rev()
+            .find(|e| e.nav_point == nav_point)
+            .map(|e| e.time)
+    }
+
+    /// Returns true if `nav_point` was visited at or after `since`.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_navigator::TravelHistory;
+    /// let mut history = TravelHistory::with_capacity(2);
+    /// history.record(1, 0.5);
+    /// history.record(2, 1.0);
+    ///
+    /// assert!(history.visited_since(1, 0.0));
+    /// assert!(!history.visited_since(1, 0.75));
+    ///
+    /// // Only the last 2 visits are remembered.
+    /// history.record(3, 1.5);
+    /// assert!(!history.visited_since(1, 0.0));
+    /// ```
+    pub fn visited_since(&self, nav_point: u32, since: f64) -> bool {
+        self.last_visit(nav_point)
+            .map(|time| time >= since)
+            .unwrap_or(false)
+    }
+
+    /// Forgets all remembered visits.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.head = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_ring_buffer_order() {
+        let mut history = TravelHistory::with_capacity(3);
+        for i in 1..=5 {
+            history.record(i, i as f64);
+        }
+
+        assert_eq!(history.len(), 3);
+        let visited: Vec<u32> = history.iter().map(|e| e.nav_point).collect();
+        assert_eq!(visited, [3, 4, 5]);
+        assert_eq!(history.last().unwrap().nav_point, 5);
+        assert_eq!(history.last_visit(4), Some(4.0));
+        assert_eq!(history.last_visit(1), None);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{NavDomain, NavGraph};
+
+/// A problem with a [`NavGraph`]'s internal consistency, found by [`NavGraph::validate`].
+///
+/// Graphs only built through the [`NavGraph`] API are always consistent, but buggy procedural
+/// generation, reflection or bad save files can leave them otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphIssue {
+    /// `point` is connected to `connection`, which isn't in the graph.
+    DanglingConnection { point: u32, connection: u32 },
+    /// The point is connected to itself.
+    SelfConnection(u32),
+    /// `point` is connected to `connection`, but not the other way around.
+    OneWayConnection { point: u32, connection: u32 },
+    /// The cached cost of the connection from `point` to `connection` is missing, or there's a
+    /// cost cached for a connection which doesn't exist.
+    StaleCost { point: u32, connection: u32 },
+    /// The point has more occupants than its maximum occupancy.
+    OverOccupied {
+        point: u32,
+        occupancy: u32,
+        max_occupancy: u32,
+    },
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Checks the graph for internal inconsistencies, returning every one found, sorted by point.
+    pub fn validate(&self) -> Vec<GraphIssue> {
+        let mut issues = Vec::new();
+        for point in self.points.values() {
+            let id = point.id();
+            for &connection in &point.connections {
+                if connection == id {
+                    issues.push(GraphIssue::SelfConnection(id));
+                    continue;
+                }
+                match self.points.get(&connection) {
+                    None => issues.push(GraphIssue::DanglingConnection {
+                        point: id,
+                        connection,
+                    }),
+                    Some(other) if !other.connections.contains(&id) => {
+                        issues.push(GraphIssue::OneWayConnection {
+                            point: id,
+                            connection,
+                        })
+                    }
+                    Some(_) if !point.costs.contains_key(&connection) => {
+                        issues.push(GraphIssue::StaleCost {
+                            point: id,
+                            connection,
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+            for &connection in point.costs.keys() {
+                if !point.connections.contains(&connection) {
+                    issues.push(GraphIssue::StaleCost {
+                        point: id,
+                        connection,
+                    });
+                }
+            }
+            if point.current_occupancy() > point.max_occupancy() {
+                issues.push(GraphIssue::OverOccupied {
+                    point: id,
+                    occupancy: point.current_occupancy(),
+                    max_occupancy: point.max_occupancy(),
+                });
+            }
+        }
+        issues.sort_by_key(|issue| match issue {
+            GraphIssue::DanglingConnection { point, connection }
+            | GraphIssue::OneWayConnection { point, connection }
+            | GraphIssue::StaleCost { point, connection } => (*point, *connection),
+            GraphIssue::SelfConnection(point) | GraphIssue::OverOccupied { point, .. } => {
+                (*point, *point)
+            }
+        });
+        issues
+    }
+
+    /// Fixes every issue [`NavGraph::validate`] finds, returning what was fixed.
+    ///
+    /// Dangling and self connections are removed, one-way connections are made two-way,
+    /// cached costs are brought back in line with the connections, and occupancy over the
+    /// maximum is reset to the maximum.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{GraphIssue, NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.occupy(1);
+    /// // e.g. from a bad save file.
+    /// nav_graph.modify(1, |point| point.set_max_occupancy(0));
+    ///
+    /// let fixed = nav_graph.repair();
+    /// assert_eq!(
+    ///     fixed,
+    ///     [GraphIssue::OverOccupied { point: 1, occupancy: 1, max_occupancy: 0 }]
+    /// );
+    /// assert!(nav_graph.validate().is_empty());
+    /// ```
+    pub fn repair(&mut self) -> Vec<GraphIssue> {
+        let issues = self.validate();
+        let mut recost = Vec::new();
+        for issue in &issues {
+            match *issue {
+                GraphIssue::DanglingConnection { point, connection } => {
+                    self.sever(point, connection);
+                }
+                GraphIssue::SelfConnection(point) => self.sever(point, point),
+                GraphIssue::OneWayConnection { point, connection } => {
+                    if let Some(other) = self.points.get_mut(&connection) {
+                        other.connections.insert(point);
+                    }
+                    recost.push((point, connection));
+                }
+                GraphIssue::StaleCost { point, connection } => {
+                    let connected = self.points[&point].connections.contains(&connection);
+                    if connected {
+                        recost.push((point, connection));
+                    } else {
+                        self.points
+                            .get_mut(&point)
+                            .unwrap()
+                            .costs
+                            .remove(&connection);
+                    }
+                }
+                GraphIssue::OverOccupied {
+                    point,
+                    max_occupancy,
+                    ..
+                } => {
+                    let point = self.points.get_mut(&point).unwrap();
+                    while point.current_occupancy() > max_occupancy {
+                        point.unoccupy();
+                    }
+                }
+            }
+        }
+        for (point, connection) in recost {
+            self.connect_points(point, connection);
+        }
+        if !issues.is_empty() {
+            self.topology_changed();
+        }
+        issues
+    }
+
+    /// Removes `connection` and its cost from `point` only.
+    fn sever(&mut self, point: u32, connection: u32) {
+        if let Some(point) = self.points.get_mut(&point) {
+            point.connections.remove(&connection);
+            point.costs.remove(&connection);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::NavPoint;
+
+    #[test]
+    pub fn test_repair_fixes_corrupt_connections() {
+        let mut nav_graph = NavGraph::new();
+        for id in 1..=3 {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
+        }
+        nav_graph.connect_points(1, 2);
+        let point = nav_graph.points.get_mut(&1).unwrap();
+        point.connections.extend([1, 9]);
+        let point = nav_graph.points.get_mut(&3).unwrap();
+        point.connections.insert(2);
+        point.costs.insert(1, 5);
+
+        assert_eq!(
+            nav_graph.validate(),
+            [
+                GraphIssue::SelfConnection(1),
+                GraphIssue::DanglingConnection {
+                    point: 1,
+                    connection: 9
+                },
+                GraphIssue::StaleCost {
+                    point: 3,
+                    connection: 1
+                },
+                GraphIssue::OneWayConnection {
+                    point: 3,
+                    connection: 2
+                },
+            ]
+        );
+        assert_eq!(nav_graph.repair().len(), 4);
+        assert!(nav_graph.validate().is_empty());
+        assert_eq!(nav_graph.find_path(1, 3).unwrap()[..], [1, 2, 3]);
+        assert_eq!(nav_graph.path_cost(&[2, 3]), nav_graph.path_cost(&[3, 2]));
+    }
+}
@@ -0,0 +1,228 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use bevy_utils::HashMap;
+
+use crate::{memory::map_bytes, NavDomain, NavGraph};
+
+/// Costs between a few chosen points and every other point, bounding the cost between any two
+/// points from below through the triangle inequality, for [`NavGraph::build_landmarks`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Landmarks {
+    topology_version: Option<u64>,
+    /// The cost of moving from each landmark to each point.
+    from: Vec<HashMap<u32, u32>>,
+    /// The cost of moving from each point to each landmark.
+    to: Vec<HashMap<u32, u32>>,
+}
+
+/// A target's costs to and from each landmark, looked up once per search.
+pub(crate) struct LandmarkTarget<'a> {
+    landmarks: &'a Landmarks,
+    costs: Vec<(Option<u32>, Option<u32>)>,
+}
+
+impl Landmarks {
+    /// Whether the landmarks were built for the graph as it is at `topology_version`.
+    pub fn is_current(&self, topology_version: u64) -> bool {
+        self.topology_version == Some(topology_version) && !self.from.is_empty()
+    }
+
+    pub fn target(&self, target: u32) -> LandmarkTarget<'_> {
+        LandmarkTarget {
+            landmarks: self,
+            costs: self
+                .from
+                .iter()
+                .zip(&self.to)
+                .map(|(from, to)| (from.get(&target).copied(), to.get(&target).copied()))
+                .collect(),
+        }
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        self.from.iter().chain(&self.to).map(map_bytes).sum()
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        for costs in self.from.iter_mut().chain(&mut self.to) {
+            costs.shrink_to_fit();
+        }
+    }
+}
+
+impl LandmarkTarget<'_> {
+    /// A lower bound on the cost of moving from `id` to the target.
+    pub fn estimate(&self, id: u32) -> u32 {
+        let landmarks = self.landmarks;
+        let mut estimate = 0;
+        for (i, (from_target, to_target)) in self.costs.iter().enumerate() {
+            // Going through `id` on the way from the landmark to the target can't be cheaper
+            // than going straight there, and likewise on the way from `id` to the landmark.
+            if let (Some(from_target), Some(from_id)) = (from_target, landmarks.from[i].get(&id)) {
+                estimate = estimate.max(from_target.saturating_sub(*from_id));
+            }
+            if let (Some(to_target), Some(to_id)) = (to_target, landmarks.to[i].get(&id)) {
+                estimate = estimate.max(to_id.saturating_sub(*to_target));
+            }
+        }
+        estimate
+    }
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Picks `count` landmarks spread across the graph and computes the cost between each of
+    /// them and every point, which [`NavGraph::find_path`] and the other searches then use to
+    /// estimate how far they have left to go.
+    ///
+    /// The estimate never exceeds the actual cost, so searches find the cheapest path while
+    /// expanding far fewer points on long routes than any [`Heuristic`](crate::Heuristic) does. Each
+    /// landmark costs two searches over the whole graph to build and two costs per point to
+    /// store, and a handful, e.g. 8 to 16, is usually enough.
+    ///
+    /// Landmarks are only used while the graph's topology is unchanged since they were built,
+    /// see [`NavGraph::topology_version`], so rebuild them after editing the graph.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_navigator::{GridConnectivity, NavGraph};
+    /// let (mut nav_graph, _) = NavGraph::<()>::from_grid(50, 50, 1.0, GridConnectivity::Four);
+    /// nav_graph.build_landmarks(4);
+    /// assert_eq!(nav_graph.landmark_count(), 4);
+    ///
+    /// let path = nav_graph.find_path(1, 2500).unwrap();
+    /// assert_eq!(path.len(), 99);
+    /// ```
+    pub fn build_landmarks(&mut self, count: usize) {
+        let mut ids: Vec<u32> = self.points.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut from = Vec::new();
+        let mut to = Vec::new();
+        // The smallest cost from any landmark so far to each point.
+        let mut closest = HashMap::<u32, u32>::new();
+        let mut next = ids.first().map(|first| {
+            // Start from whichever point is furthest from an arbitrary one.
+            let costs = self.landmark_costs(*first, false);
+            furthest(&ids, &costs).unwrap_or(*first)
+        });
+        while let Some(landmark) = next.filter(|_| from.len() < count) {
+            let from_landmark = self.landmark_costs(landmark, false);
+            for (id, cost) in &from_landmark {
+                let closest = closest.entry(*id).or_insert(u32::MAX);
+                *closest = (*closest).min(*cost);
+            }
+            to.push(self.landmark_costs(landmark, true));
+            from.push(from_landmark);
+            // Points no landmark reaches yet come first, so disconnected parts of the graph get
+            // landmarks of their own.
+            next = ids
+                .iter()
+                .find(|id| !closest.contains_key(id))
+                .copied()
+                .or_else(|| furthest(&ids, &closest).filter(|id| closest[id] > 0));
+        }
+
+        self.landmarks = Landmarks {
+            topology_version: Some(self.topology_version()),
+            from,
+            to,
+        };
+    }
+
+    /// The number of landmarks built by [`NavGraph::build_landmarks`], whether or not they're
+    /// still up to date.
+    pub fn landmark_count(&self) -> usize {
+        self.landmarks.from.len()
+    }
+
+    /// Drops the landmarks built by [`NavGraph::build_landmarks`], going back to the graph's
+    /// [`Heuristic`](crate::Heuristic).
+    pub fn clear_landmarks(&mut self) {
+        self.landmarks = Landmarks::default();
+    }
+
+    /// Runs Dijkstra's algorithm from `landmark` over the whole graph, ignoring occupancy. With
+    /// `reverse`, costs are of moving from each point to `landmark` instead.
+    fn landmark_costs(&self, landmark: u32, reverse: bool) -> HashMap<u32, u32> {
+        let mut costs = HashMap::<u32, u32>::new();
+        costs.insert(landmark, 0);
+        let mut open_set = BinaryHeap::new();
+        open_set.push(Reverse((0, landmark)));
+        while let Some(Reverse((cost, id))) = open_set.pop() {
+            if cost > costs[&id] {
+                continue;
+            }
+            for neighbor in &self.points[&id].connections {
+                if !self.points.contains_key(neighbor) {
+                    continue;
+                }
+                let step = if reverse {
+                    self.connection_cost(*neighbor, id)
+                } else {
+                    self.connection_cost(id, *neighbor)
+                };
+                let neighbor_cost = cost.saturating_add(step);
+                if costs
+                    .get(neighbor)
+                    .is_none_or(|known| neighbor_cost < *known)
+                {
+                    costs.insert(*neighbor, neighbor_cost);
+                    open_set.push(Reverse((neighbor_cost, *neighbor)));
+                }
+            }
+        }
+        costs
+    }
+}
+
+/// The point of `ids` with the highest cost in `costs`, preferring lower IDs.
+fn furthest(ids: &[u32], costs: &HashMap<u32, u32>) -> Option<u32> {
+    ids.iter()
+        .filter_map(|id| Some((*costs.get(id)?, Reverse(*id))))
+        .max()
+        .map(|(_, Reverse(id))| id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridConnectivity;
+
+    #[test]
+    pub fn test_landmark_paths_are_cheapest() {
+        let (mut nav_graph, ids) = NavGraph::<()>::from_grid(30, 30, 1.0, GridConnectivity::Eight);
+        let id = |x, y| ids.id(x, y).unwrap();
+        // A wall down the middle, with a single gap near the bottom, and an island.
+        for y in 0..28 {
+            nav_graph.remove_point(id(15, y));
+        }
+        nav_graph.add_nav_point(crate::NavPoint::new(
+            10_000,
+            bevy_math::Vec3::new(-10.0, -10.0, 0.0),
+            1.0,
+            1,
+        ));
+        nav_graph.build_landmarks(6);
+        assert_eq!(nav_graph.landmark_count(), 6);
+        assert!(nav_graph.landmarks.is_current(nav_graph.topology_version()));
+
+        for (a, b) in [
+            (id(0, 0), id(29, 0)),
+            (id(29, 29), id(0, 5)),
+            (id(3, 20), id(4, 21)),
+        ] {
+            let cheapest = nav_graph.cost_map(a, u32::MAX, None)[&b];
+            let target = nav_graph.landmarks.target(b);
+            assert!(target.estimate(a) <= cheapest);
+            assert!(target.estimate(a) > 0);
+            let path = nav_graph.find_path(a, b).unwrap();
+            assert_eq!(nav_graph.path_cost(&path), Some(cheapest));
+        }
+        assert_eq!(nav_graph.find_path(id(0, 0), 10_000), None);
+
+        // Editing the graph retires the landmarks until they're rebuilt.
+        nav_graph.remove_point(id(15, 28));
+        assert!(!nav_graph.landmarks.is_current(nav_graph.topology_version()));
+        assert!(nav_graph.find_path(id(0, 0), id(29, 0)).is_some());
+    }
+}
@@ -0,0 +1,78 @@
+use bevy_ecs::system::Resource;
+use bevy_math::Vec3;
+use bevy_reflect::{FromReflect, Reflect};
+
+/// Which side of a connection travelers keep to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+pub enum LaneSide {
+    #[default]
+    Right,
+    Left,
+}
+
+/// Lateral lane offsets for travelers moving along connections.
+///
+/// With a non-zero `offset`, travelers move along a line parallel to each connection, shifted
+/// toward `side`, so travelers heading in opposite directions along the same connection pass
+/// each other instead of overlapping. Travelers still end their path exactly on the final point.
+#[derive(Debug, Clone, Copy, Resource, Reflect, FromReflect)]
+pub struct TrafficLanes {
+    /// The distance from the center of the connection to travel at. Zero disables lanes.
+    pub offset: f32,
+    pub side: LaneSide,
+    /// The world's up axis, used to work out which way is right. The default of `Vec3::Z` suits
+    /// 2D scenes; use `Vec3::Y` for 3D ones.
+    pub up: Vec3,
+}
+
+impl Default for TrafficLanes {
+    fn default() -> Self {
+        Self {
+            offset: 0.0,
+            side: LaneSide::default(),
+            up: Vec3::Z,
+        }
+    }
+}
+
+impl TrafficLanes {
+    pub fn new(offset: f32, side: LaneSide) -> Self {
+        Self {
+            offset,
+            side,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_up(mut self, up: Vec3) -> Self {
+        self.up = up;
+        self
+    }
+
+    /// The offset from a connection's center line for a traveler moving in `direction`.
+    pub fn offset_for(&self, direction: Vec3) -> Vec3 {
+        if self.offset == 0.0 {
+            return Vec3::ZERO;
+        }
+
+        let right = direction.cross(self.up).normalize_or_zero();
+        match self.side {
+            LaneSide::Right => right * self.offset,
+            LaneSide::Left => -right * self.offset,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_opposite_directions_use_opposite_sides() {
+        let lanes = TrafficLanes::new(0.5, LaneSide::Right);
+
+        assert_eq!(lanes.offset_for(Vec3::X), Vec3::new(0.0, -0.5, 0.0));
+        assert_eq!(lanes.offset_for(-Vec3::X), Vec3::new(0.0, 0.5, 0.0));
+        assert_eq!(TrafficLanes::default().offset_for(Vec3::X), Vec3::ZERO);
+    }
+}
@@ -1,35 +1,397 @@
+mod area;
+#[cfg(feature = "asset")]
+mod asset;
+mod audit;
+mod availability;
+mod builders;
+mod calibration;
+#[cfg(feature = "travelers")]
+mod cargo;
+mod churn;
+#[cfg(feature = "travelers")]
+mod congestion;
+mod cooperative;
+mod cost;
+mod density;
+#[cfg(feature = "travelers")]
+mod displacement;
+mod domain;
+mod error;
+#[cfg(feature = "travelers")]
+mod events;
+mod explain;
+mod export;
+mod graph_log;
+mod graphs;
+mod hierarchy;
+#[cfg(feature = "travelers")]
+mod history;
+mod integrity;
+mod landmarks;
+#[cfg(feature = "travelers")]
+mod lanes;
+#[cfg(feature = "travelers")]
+mod lod;
+mod memory;
+mod nav_path;
 mod navigation;
+mod path_cache;
+#[cfg(feature = "travelers")]
+mod platform;
+mod preview;
+#[cfg(feature = "travelers")]
+mod profile;
+mod projection;
+#[cfg(feature = "travelers")]
+mod queue;
+mod quota;
+mod raycast;
+mod recording;
+mod regions;
+mod reservation;
+#[cfg(feature = "travelers")]
+mod revocation;
+mod search;
+mod spatial;
+mod spawn;
+#[cfg(feature = "travelers")]
+mod speed;
+#[cfg(feature = "travelers")]
+mod stagger;
+mod telemetry;
+mod territory;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "travelers")]
+mod transition;
+#[cfg(feature = "travelers")]
 mod traveler;
+#[cfg(feature = "travelers")]
+mod traveler_index;
+#[cfg(feature = "travelers")]
+mod turn;
 
+use std::marker::PhantomData;
+
+#[cfg(feature = "travelers")]
+use bevy_app::CoreStage;
 use bevy_app::{App, Plugin};
+#[cfg(feature = "travelers")]
 use bevy_ecs::schedule::IntoSystemDescriptor;
+#[cfg(feature = "travelers")]
+use bevy_tasks::{AsyncComputeTaskPool, TaskPool};
 
-pub use navigation::{NavGraph, NavPoint, NavPointRef};
-use traveler::{compute_initial_path, move_travelers};
-pub use traveler::{AutoTraveler, TravelingPaused};
+pub use area::AreaCosts;
+#[cfg(feature = "asset")]
+pub use asset::{
+    NavGraphAsset, NavGraphAssetPlugin, NavGraphLoader, NavGraphSource, NavPointAsset,
+};
+pub use availability::{Availability, AvailabilityMode, NavClock, NavEdge, Schedule, Traversal};
+pub use builders::{GridConnectivity, GridIds, WalkabilityMap};
+#[cfg(feature = "travelers")]
+use cargo::transfer_cargo;
+#[cfg(feature = "travelers")]
+pub use cargo::{CargoAction, CargoRule, CargoTrigger, Carrier};
+#[cfg(feature = "travelers")]
+use congestion::replan_congested_travelers;
+#[cfg(feature = "travelers")]
+pub use congestion::CongestionReplan;
+#[cfg(feature = "travelers")]
+use cooperative::plan_cooperative_travelers;
+#[cfg(feature = "travelers")]
+pub use cooperative::CooperativePlanning;
+pub use cooperative::{CooperativePath, CooperativeSearch, SpaceTimeReservations};
+pub use cost::{CostProvider, DistanceCost};
+pub use density::CrowdDensity;
+#[cfg(feature = "travelers")]
+use displacement::rejoin_displaced_travelers;
+#[cfg(feature = "travelers")]
+pub use displacement::DisplacementRejoin;
+pub use domain::NavDomain;
+pub use error::NavError;
+#[cfg(feature = "travelers")]
+pub use events::{
+    CargoTransferred, DestinationQueued, DestinationSubstituted, EnteredHostileTerritory,
+    ReservationRevoked, TravelAborted, TravelCompleted, TravelFailed,
+};
+pub use explain::SegmentCost;
+#[cfg(feature = "travelers")]
+use graph_log::record_graph_changes;
+pub use graph_log::{GraphChange, GraphLog, GraphLogEntry, PointState};
+pub use graphs::{NavGraphId, NavGraphs};
+#[cfg(feature = "travelers")]
+pub use history::{TravelHistory, TravelHistoryEntry};
+pub use integrity::GraphIssue;
+#[cfg(feature = "travelers")]
+pub use lanes::{LaneSide, TrafficLanes};
+#[cfg(feature = "travelers")]
+use lod::update_traveler_lod;
+#[cfg(feature = "travelers")]
+pub use lod::{LodFocus, LodImportance, LodPolicy, LowFidelity};
+pub use memory::GraphMemoryUsage;
+#[cfg(feature = "travelers")]
+use memory::{log_graph_memory, MemoryLogInterval};
+pub use nav_path::NavPath;
+pub use navigation::{EdgeSpeedModel, NavGraph, NavPoint, NavPointMut, NavPointRef};
+pub use path_cache::{CacheInvalidation, PathCache, PathCacheStats};
+#[cfg(feature = "travelers")]
+use platform::sync_nav_platforms;
+#[cfg(feature = "travelers")]
+pub use platform::{NavPlatform, PlatformDock};
+use preview::compute_path_previews;
+pub use preview::{PathPreview, PathPreviewRequest};
+#[cfg(feature = "travelers")]
+use profile::apply_navigation_profiles;
+#[cfg(feature = "travelers")]
+pub use profile::{NavigationProfile, NavigationProfiles};
+pub use projection::GraphProjection;
+#[cfg(feature = "travelers")]
+pub use queue::{PathRequestOrder, PathRequestQueue};
+#[cfg(feature = "travelers")]
+pub use quota::AwaitingDestination;
+pub use raycast::RaycastHit;
+#[cfg(feature = "travelers")]
+use recording::advance_expansion_playback;
+pub use recording::{ExpandedNode, ExpansionPlayback, SearchRecording};
+pub use reservation::CorridorReservation;
+#[cfg(feature = "travelers")]
+pub use reservation::PathReservation;
+#[cfg(feature = "travelers")]
+use reservation::{expire_corridor_reservations, reserve_paths_ahead};
+#[cfg(feature = "travelers")]
+use revocation::revoke_reservations;
+pub use search::{Heuristic, PathNoise, PathPolicy, PathResult, PathWarmStart, SearchOptions};
+pub use spawn::SpawnCriteria;
+#[cfg(feature = "travelers")]
+use speed::expire_speed_modifiers;
+#[cfg(feature = "travelers")]
+pub use speed::{SpeedModifier, SpeedModifiers};
+#[cfg(feature = "travelers")]
+use stagger::release_departures;
+#[cfg(feature = "travelers")]
+pub use stagger::{AwaitingDeparture, DepartureStagger};
+pub use telemetry::PathTelemetry;
+#[cfg(feature = "travelers")]
+use territory::detect_hostile_entries;
+pub use territory::TerritoryAccess;
+#[cfg(feature = "travelers")]
+pub use transition::{GraphTransition, ReplaceNavGraph};
+#[cfg(feature = "travelers")]
+use traveler::{
+    compute_initial_path, move_travelers, replan_travelers, resolve_traveler_origins,
+    resume_travelers, suspend_travelers,
+};
+#[cfg(feature = "travelers")]
+pub use traveler::{
+    AutoTraveler, BlockedBehavior, DestinationBehavior, DetourSettings, OriginClaim, PathBehavior,
+    Replan, ResumeTravel, SuspendTravel, TickInterval, TravelingPaused,
+};
+#[cfg(feature = "travelers")]
+use traveler_index::index_travelers;
+#[cfg(feature = "travelers")]
+pub use traveler_index::TravelerIndex;
+#[cfg(feature = "travelers")]
+use turn::advance_turn_travelers;
+#[cfg(feature = "travelers")]
+pub use turn::{AdvanceTurn, TurnBased};
 
-#[derive(Default, Clone, Copy)]
-pub struct NavigatorPlugin {
+/// Adds navigation to an app.
+///
+/// The plugin can be added once per [`NavDomain`], e.g. `NavigatorPlugin::<Vehicles>::default()`,
+/// to run several independent navigation domains side by side. Each domain gets its own
+/// [`NavGraph<M>`], [`NavGraphs<M>`], [`PathRequestQueue<M>`], [`TravelerIndex<M>`],
+/// [`TravelCompleted<M>`], [`TravelFailed<M>`], [`TravelAborted<M>`] and
+/// [`CargoTransferred<M>`], while [`TrafficLanes`], [`NavClock`], [`LodPolicy`] and
+/// [`AdvanceTurn`] are shared.
+///
+/// Without the `travelers` feature, only the [`NavGraph<M>`], [`NavGraphs<M>`] and path previews
+/// are set up, for apps which plan paths themselves and move entities with their own systems.
+pub struct NavigatorPlugin<M: NavDomain = ()> {
     pub initial_graph_capacity: usize,
+    /// Needs the `travelers` feature.
+    pub max_paths_per_frame: Option<usize>,
+    /// Needs the `travelers` feature.
+    pub async_pathfinding: bool,
+    /// How often, in seconds, to log the graph's memory usage, or `None` to never log it. Needs
+    /// the `travelers` feature, which brings in `bevy_time`.
+    pub memory_log_interval: Option<f32>,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> Default for NavigatorPlugin<M> {
+    fn default() -> Self {
+        Self {
+            initial_graph_capacity: 0,
+            max_paths_per_frame: None,
+            async_pathfinding: false,
+            memory_log_interval: None,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: NavDomain> Clone for NavigatorPlugin<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
+impl<M: NavDomain> Copy for NavigatorPlugin<M> {}
+
 impl NavigatorPlugin {
     pub fn new() -> Self {
         Self::default()
     }
+}
 
+impl<M: NavDomain> NavigatorPlugin<M> {
     pub fn with_capacity(mut self, capacity: usize) -> Self {
         self.initial_graph_capacity = capacity;
         self
     }
+
+    /// Limits how many traveler paths are computed each frame.
+    ///
+    /// Travelers over the limit are queued and planned in later frames, in the order they were
+    /// added. See [`PathRequestQueue`].
+    pub fn with_max_paths_per_frame(mut self, max_paths_per_frame: usize) -> Self {
+        self.max_paths_per_frame = Some(max_paths_per_frame);
+        self
+    }
+
+    /// Searches for traveler paths on the `AsyncComputeTaskPool`, so long searches don't stall
+    /// the frame. Travelers get their paths a frame or more after they're added. See
+    /// [`PathRequestQueue`].
+    pub fn with_async_pathfinding(mut self) -> Self {
+        self.async_pathfinding = true;
+        self
+    }
+
+    /// Logs the graph's memory usage every `interval` seconds. See [`NavGraph::memory_usage`].
+    pub fn with_memory_logging(mut self, interval: f32) -> Self {
+        self.memory_log_interval = Some(interval);
+        self
+    }
 }
 
-impl Plugin for NavigatorPlugin {
+impl<M: NavDomain> Plugin for NavigatorPlugin<M> {
     fn build(&self, app: &mut App) {
-        app.insert_resource(NavGraph::with_capacity(self.initial_graph_capacity))
-            .add_system(compute_initial_path.label("compute_path"))
-            .add_system(move_travelers.after("compute_path"))
+        let mut nav_graph = NavGraph::<M>::default();
+        nav_graph.reserve(self.initial_graph_capacity);
+
+        app.insert_resource(nav_graph)
+            .init_resource::<NavGraphs<M>>()
+            .init_resource::<NavClock>()
+            .register_type::<NavGraphId>()
+            .register_type::<NavPointRef>()
+            .register_type::<PathPreviewRequest>()
+            .register_type::<PathPreview>()
+            .register_type::<PathWarmStart>()
+            .register_type::<NavPath>()
+            .register_type::<NavClock>();
+
+        #[cfg(feature = "travelers")]
+        self.build_travelers(app);
+        #[cfg(not(feature = "travelers"))]
+        app.add_system(compute_path_previews::<M>);
+    }
+}
+
+#[cfg(feature = "travelers")]
+impl<M: NavDomain> NavigatorPlugin<M> {
+    fn build_travelers(&self, app: &mut App) {
+        let mut queue = PathRequestQueue::<M>::default();
+        queue.max_per_frame = self.max_paths_per_frame;
+        queue.asynchronous = self.async_pathfinding;
+        if self.async_pathfinding {
+            AsyncComputeTaskPool::init(TaskPool::default);
+        }
+
+        app.insert_resource(queue)
+            .init_resource::<TravelerIndex<M>>()
+            .init_resource::<TrafficLanes>()
+            .init_resource::<LodPolicy>()
+            .init_resource::<NavigationProfiles>()
+            .init_resource::<SpaceTimeReservations<M>>()
+            .add_event::<TravelCompleted<M>>()
+            .add_event::<TravelFailed<M>>()
+            .add_event::<TravelAborted<M>>()
+            .add_event::<AdvanceTurn>()
+            .add_event::<CargoTransferred<M>>()
+            .add_event::<EnteredHostileTerritory<M>>()
+            .add_event::<ReservationRevoked<M>>()
+            .add_event::<DestinationSubstituted<M>>()
+            .add_event::<DestinationQueued<M>>()
+            .add_system(expire_corridor_reservations::<M>.before("compute_path"))
+            .add_system(resolve_traveler_origins::<M>.before("compute_path"))
+            .add_system(sync_nav_platforms::<M>.before("compute_path"))
+            .add_system(update_traveler_lod::<M>.before("compute_path"))
+            .add_system(
+                apply_navigation_profiles::<M>
+                    .after(resolve_traveler_origins::<M>)
+                    .before("compute_path"),
+            )
+            .add_system(compute_initial_path::<M>.label("compute_path"))
+            // Previews get whatever budget travelers leave over.
+            .add_system(compute_path_previews::<M>.after("compute_path"))
+            .add_system(
+                replan_congested_travelers::<M>
+                    .after("compute_path")
+                    .before("replan"),
+            )
+            .add_system(
+                rejoin_displaced_travelers::<M>
+                    .after("compute_path")
+                    .before("replan"),
+            )
+            .add_system(replan_travelers::<M>.label("replan").after("compute_path"))
+            .add_system(
+                suspend_travelers::<M>
+                    .after("compute_path")
+                    .before("advance"),
+            )
+            .add_system(resume_travelers::<M>.after("replan").before("advance"))
+            .add_system(expire_speed_modifiers::<M>.before("advance"))
+            .add_system(release_departures::<M>.before("advance"))
+            .add_system(revoke_reservations::<M>.after("replan").before("advance"))
+            .add_system(reserve_paths_ahead::<M>.after("replan").before("advance"))
+            .add_system(
+                plan_cooperative_travelers::<M>
+                    .after("replan")
+                    .before("advance"),
+            )
+            .add_system(move_travelers::<M>.label("advance").after("replan"))
+            .add_system(advance_turn_travelers::<M>.label("advance").after("replan"))
+            .add_system(transfer_cargo::<M>.after("advance"))
+            .add_system(detect_hostile_entries::<M>.after("advance"))
+            .add_system(advance_expansion_playback::<M>)
+            .add_system_to_stage(CoreStage::PostUpdate, record_graph_changes::<M>)
+            .add_system_to_stage(CoreStage::PostUpdate, index_travelers::<M>)
             .register_type::<AutoTraveler>()
-            .register_type::<NavPointRef>();
+            .register_type::<Replan>()
+            .register_type::<CongestionReplan>()
+            .register_type::<PathReservation>()
+            .register_type::<CooperativePlanning>()
+            .register_type::<TravelHistory>()
+            .register_type::<TrafficLanes>()
+            .register_type::<NavPlatform>()
+            .register_type::<LodPolicy>()
+            .register_type::<NavigationProfiles>()
+            .register_type::<LowFidelity>()
+            .register_type::<TickInterval>()
+            .register_type::<TurnBased>()
+            .register_type::<Carrier>()
+            .register_type::<SuspendTravel>()
+            .register_type::<ResumeTravel>()
+            .register_type::<SpeedModifiers>()
+            .register_type::<DepartureStagger>()
+            .register_type::<AwaitingDeparture>()
+            .register_type::<DisplacementRejoin>()
+            .register_type::<AwaitingDestination>();
+
+        if let Some(interval) = self.memory_log_interval {
+            app.insert_resource(MemoryLogInterval::<M>::new(interval))
+                .add_system(log_graph_memory::<M>);
+        }
     }
 }
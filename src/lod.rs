@@ -0,0 +1,163 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::With,
+    system::{Commands, Query, Res, Resource},
+};
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_transform::prelude::Transform;
+
+use crate::{graphs::TravelerGraphsMut, traveler::TravelerPosition, AutoTraveler, NavDomain};
+
+/// Controls when travelers switch to low-fidelity navigation. See [`LowFidelity`].
+///
+/// Disabled by default.
+#[derive(Debug, Default, Clone, Copy, Resource, Reflect, FromReflect)]
+pub struct LodPolicy {
+    /// Travelers further than this from every [`LodFocus`] switch to low fidelity, or `None` to
+    /// keep every traveler at full fidelity.
+    pub far_distance: Option<f32>,
+    /// How often, in seconds, low-fidelity travelers have their transforms updated.
+    pub update_interval: f32,
+}
+
+impl LodPolicy {
+    pub fn new(far_distance: f32, update_interval: f32) -> Self {
+        Self {
+            far_distance: Some(far_distance),
+            update_interval,
+        }
+    }
+}
+
+/// Marks an entity, usually the camera, that travelers near it stay at full fidelity around.
+#[derive(Debug, Default, Clone, Copy, Component, Reflect, FromReflect)]
+pub struct LodFocus;
+
+/// Scales the [`LodPolicy::far_distance`] for a single traveler, so important travelers keep full
+/// fidelity further from the focus. A traveler with an importance of 2 switches to low fidelity
+/// at twice the usual distance.
+#[derive(Debug, Clone, Copy, Component, Reflect, FromReflect)]
+pub struct LodImportance(pub f32);
+
+/// Marks a traveler as navigating at low fidelity, for cheap background agents.
+///
+/// Low-fidelity travelers plan and move without regard to occupancy, neither waiting for nor
+/// taking up space at [`NavPoint`](crate::NavPoint)s, and only have their transforms updated every
+/// [`LodPolicy::update_interval`]. Their occupancy is released when they switch to low fidelity,
+/// and taken back when they switch to full fidelity again.
+///
+/// Added and removed automatically according to the [`LodPolicy`], but can also be added by hand,
+/// e.g. when spawning a traveler that's known to be far away.
+#[derive(Debug, Default, Clone, Copy, Component, Reflect, FromReflect)]
+pub struct LowFidelity {
+    pub(crate) elapsed: f32,
+}
+
+type LodTravelerQuery<'w, 's, M> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static AutoTraveler,
+        &'static Transform,
+        Option<&'static LodImportance>,
+        Option<&'static TravelerPosition>,
+        Option<&'static LowFidelity>,
+    ),
+    <M as NavDomain>::Filter,
+>;
+
+pub(crate) fn update_traveler_lod<M: NavDomain>(
+    travelers_query: LodTravelerQuery<M>,
+    focus_query: Query<&Transform, With<LodFocus>>,
+    policy: Res<LodPolicy>,
+    mut graphs: TravelerGraphsMut<M>,
+    mut commands: Commands,
+) {
+    let Some(far_distance) = policy.far_distance else {
+        return;
+    };
+
+    for (entity, auto_traveler, transform, importance, traveler_position, low_fidelity) in
+        travelers_query.iter()
+    {
+        let weight = auto_traveler.occupancy_weight;
+        let far_distance = far_distance * importance.map(|i| i.0).unwrap_or(1.0);
+        let near = focus_query.iter().any(|focus| {
+            focus.translation.distance_squared(transform.translation) <= far_distance * far_distance
+        });
+
+        match (near, low_fidelity.is_some()) {
+            (false, false) => {
+                if let (Some(position), Some(nav_graph)) =
+                    (traveler_position, graphs.get_mut(auto_traveler.graph))
+                {
+                    nav_graph.unoccupy_weighted(position.current_nav_point, weight);
+                    if let Some(next) = position.next_nav_point {
+                        nav_graph.unoccupy_weighted(next, weight);
+                    }
+                }
+                commands.entity(entity).insert(LowFidelity::default());
+            }
+            (true, true) => {
+                // Space may have filled up in the meantime, but the traveler is already there.
+                if let (Some(position), Some(nav_graph)) =
+                    (traveler_position, graphs.get_mut(auto_traveler.graph))
+                {
+                    nav_graph.occupy_unchecked(position.current_nav_point, weight);
+                    if let Some(next) = position.next_nav_point {
+                        nav_graph.occupy_unchecked(next, weight);
+                    }
+                }
+                commands.entity(entity).remove::<LowFidelity>();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::{NavGraph, NavPoint};
+
+    #[test]
+    pub fn test_far_travelers_release_occupancy() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(100.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.occupy(1);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .insert_resource(LodPolicy::new(10.0, 0.5))
+            .add_system(update_traveler_lod::<()>);
+        app.world.spawn((LodFocus, Transform::default()));
+        let traveler = app
+            .world
+            .spawn((
+                AutoTraveler::new(1, 1, 1.0),
+                TravelerPosition {
+                    current_nav_point: 1,
+                    next_nav_point: None,
+                },
+                Transform::from_xyz(100.0, 0.0, 0.0),
+            ))
+            .id();
+
+        app.update();
+        assert!(app.world.get::<LowFidelity>(traveler).is_some());
+        assert!(app.world.resource::<NavGraph>().can_occupy(1));
+
+        app.world
+            .get_mut::<Transform>(traveler)
+            .unwrap()
+            .translation = Vec3::new(5.0, 0.0, 0.0);
+        app.update();
+        assert!(app.world.get::<LowFidelity>(traveler).is_none());
+        assert!(!app.world.resource::<NavGraph>().can_occupy(1));
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(feature = "travelers")]
+use std::marker::PhantomData;
+use std::mem::size_of;
+
+#[cfg(feature = "travelers")]
+use bevy_ecs::system::{Local, Res, Resource};
+#[cfg(feature = "travelers")]
+use bevy_time::Time;
+#[cfg(feature = "travelers")]
+use bevy_utils::tracing::info;
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{NavDomain, NavGraph};
+
+/// The approximate memory used by a [`NavGraph`], in bytes. See [`NavGraph::memory_usage`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GraphMemoryUsage {
+    /// The points themselves.
+    pub points: usize,
+    /// The connections of every point, along with their cached costs.
+    pub adjacency: usize,
+    /// Everything else: availability windows, connection states, reservations, stock, ownership, quotas and caches.
+    pub indexes: usize,
+}
+
+impl GraphMemoryUsage {
+    pub fn total(&self) -> usize {
+        self.points + self.adjacency + self.indexes
+    }
+}
+
+/// The approximate heap size of a hash map's table, including the control byte of each bucket.
+pub(crate) fn map_bytes<K, V>(map: &HashMap<K, V>) -> usize {
+    map.capacity() * (size_of::<(K, V)>() + 1)
+}
+
+fn set_bytes<T>(set: &HashSet<T>) -> usize {
+    set.capacity() * (size_of::<T>() + 1)
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Estimates how much memory the graph is using, including space allocated but not in use.
+    ///
+    /// After removing many points, [`NavGraph::shrink_to_fit`] gives the unused space back.
+    pub fn memory_usage(&self) -> GraphMemoryUsage {
+        GraphMemoryUsage {
+            points: size_of::<Self>() + map_bytes(&self.points),
+            adjacency: self
+                .points
+                .values()
+                .map(|point| set_bytes(&point.connections) + map_bytes(&point.costs))
+                .sum(),
+            indexes: map_bytes(&self.corridor_reservations)
+                + map_bytes(&self.point_reservations)
+                + map_bytes(&self.point_availability)
+                + map_bytes(&self.edges)
+                + map_bytes(&self.stock)
+                + map_bytes(&self.owners)
+                + map_bytes(&self.destination_quotas)
+                + set_bytes(&self.hostilities)
+                + map_bytes(&self.churn)
+                + set_bytes(&self.revocation_candidates)
+                + map_bytes(&self.traversals)
+                + self.region_costs.memory_usage()
+                + self.hierarchy.memory_usage()
+                + self.heuristic_calibration.memory_usage()
+                + self.landmarks.memory_usage()
+                + self.spatial.memory_usage()
+                + self.id_freelist.memory_usage(),
+        }
+    }
+
+    /// Frees as much unused memory as possible, e.g. after removing many points.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// for id in 1..=1000 {
+    ///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// for id in 11..=1000 {
+    ///     nav_graph.remove_point(id);
+    /// }
+    ///
+    /// let before = nav_graph.memory_usage().total();
+    /// nav_graph.shrink_to_fit();
+    /// assert!(nav_graph.memory_usage().total() < before);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.points.shrink_to_fit();
+        for point in self.points.values_mut() {
+            point.connections.shrink_to_fit();
+            point.costs.shrink_to_fit();
+        }
+        self.corridor_reservations.shrink_to_fit();
+        self.point_reservations.shrink_to_fit();
+        self.point_availability.shrink_to_fit();
+        self.edges.shrink_to_fit();
+        self.stock.shrink_to_fit();
+        self.owners.shrink_to_fit();
+        self.destination_quotas.shrink_to_fit();
+        self.hostilities.shrink_to_fit();
+        self.churn.shrink_to_fit();
+        self.traversals.shrink_to_fit();
+        self.region_costs.shrink_to_fit();
+        self.hierarchy.shrink_to_fit();
+        self.heuristic_calibration.shrink_to_fit();
+        self.landmarks.shrink_to_fit();
+        self.spatial.shrink_to_fit();
+        self.id_freelist.shrink_to_fit();
+    }
+}
+
+#[cfg(feature = "travelers")]
+/// How often, in seconds, the memory usage of the graph of the domain `M` is logged.
+#[derive(Resource)]
+pub(crate) struct MemoryLogInterval<M: NavDomain> {
+    pub interval: f32,
+    marker: PhantomData<M>,
+}
+
+#[cfg(feature = "travelers")]
+impl<M: NavDomain> MemoryLogInterval<M> {
+    pub fn new(interval: f32) -> Self {
+        Self {
+            interval,
+            marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "travelers")]
+pub(crate) fn log_graph_memory<M: NavDomain>(
+    nav_graph: Res<NavGraph<M>>,
+    interval: Res<MemoryLogInterval<M>>,
+    time: Res<Time>,
+    mut elapsed: Local<f32>,
+) {
+    *elapsed += time.delta_seconds();
+    if *elapsed < interval.interval {
+        return;
+    }
+    *elapsed = 0.0;
+
+    let usage = nav_graph.memory_usage();
+    info!(
+        "Nav graph of {} points using ~{} bytes ({} points, {} adjacency, {} indexes)",
+        nav_graph.len(),
+        usage.total(),
+        usage.points,
+        usage.adjacency,
+        usage.indexes
+    );
+}
@@ -0,0 +1,175 @@
+use bevy_ecs::component::Component;
+use bevy_math::Vec3;
+use bevy_reflect::{FromReflect, Reflect};
+
+use crate::{NavDomain, NavGraph};
+
+/// A path followed by a mover of the caller's own, e.g. a custom character controller, which
+/// keeps the graph's occupancy up to date without an [`AutoTraveler`](crate::AutoTraveler).
+///
+/// The mover heads for [`NavPath::next_target`] and calls [`NavPath::notify_reached`] as it
+/// arrives at each point, which moves it from the point it left to the one it reached. Both
+/// take constant time. Unlike travelers, the mover isn't held back by full points; check
+/// [`NavPath::is_next_clear`] before moving on to respect them.
+///
+/// Point locations are taken when the path is started, so moving points afterwards isn't
+/// reflected in the targets.
+///
+/// ## Example
+/// ```
+/// # use bevy_math::Vec3;
+/// # use bevy_navigator::{GridConnectivity, NavGraph, NavPath};
+/// let (mut nav_graph, _) = NavGraph::<()>::from_grid(3, 1, 1.0, GridConnectivity::Four);
+/// let path = nav_graph.find_path(1, 3).unwrap();
+/// let mut nav_path = NavPath::start(&mut nav_graph, path).unwrap();
+///
+/// let mut position = nav_graph.get_nav_point(1).unwrap().location();
+/// while let Some(target) = nav_path.next_target(position) {
+///     // Teleporting, for brevity.
+///     position = target;
+///     if let Some(next) = nav_path.next_point() {
+///         nav_path.notify_reached(&mut nav_graph, next);
+///     }
+/// }
+/// assert!(nav_path.is_complete());
+/// assert_eq!(nav_graph.get_nav_point(3).unwrap().current_occupancy(), 1);
+/// ```
+#[derive(Debug, Clone, Component, Reflect, FromReflect)]
+pub struct NavPath {
+    points: Vec<u32>,
+    locations: Vec<Vec3>,
+    index: usize,
+    occupancy_weight: u32,
+}
+
+impl NavPath {
+    /// Starts following `path`, occupying its first point. `None` if `path` is empty or any of
+    /// its points aren't in the graph.
+    pub fn start<M: NavDomain>(nav_graph: &mut NavGraph<M>, path: Vec<u32>) -> Option<Self> {
+        Self::start_weighted(nav_graph, path, 1)
+    }
+
+    /// Like [`NavPath::start`], taking up `occupancy_weight` slots of each point. See
+    /// [`AutoTraveler::occupancy_weight`](crate::AutoTraveler::occupancy_weight).
+    pub fn start_weighted<M: NavDomain>(
+        nav_graph: &mut NavGraph<M>,
+        path: Vec<u32>,
+        occupancy_weight: u32,
+    ) -> Option<Self> {
+        let locations = path
+            .iter()
+            .map(|id| nav_graph.get_nav_point(*id).map(|point| point.location()))
+            .collect::<Option<Vec<_>>>()?;
+        nav_graph.occupy_unchecked(*path.first()?, occupancy_weight);
+        Some(Self {
+            points: path,
+            locations,
+            index: 0,
+            occupancy_weight,
+        })
+    }
+
+    pub fn path(&self) -> &[u32] {
+        &self.points
+    }
+
+    /// The point the mover last reached, which it occupies.
+    pub fn current_point(&self) -> u32 {
+        self.points[self.index]
+    }
+
+    /// The point the mover is heading for, or `None` once it has reached the last.
+    pub fn next_point(&self) -> Option<u32> {
+        self.points.get(self.index + 1).copied()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.index + 1 >= self.points.len()
+    }
+
+    /// Where a mover at `current_pos` should head: the location of the next point, or of the
+    /// last point while the mover hasn't settled onto it. `None` once it's there.
+    pub fn next_target(&self, current_pos: Vec3) -> Option<Vec3> {
+        match self.locations.get(self.index + 1) {
+            Some(location) => Some(*location),
+            None => {
+                let last = self.locations[self.index];
+                (current_pos.distance_squared(last) > 0.001_f32.powi(2)).then_some(last)
+            }
+        }
+    }
+
+    /// Whether the next point has room for the mover. `false` once the path is complete.
+    pub fn is_next_clear<M: NavDomain>(&self, nav_graph: &NavGraph<M>) -> bool {
+        self.next_point()
+            .is_some_and(|next| nav_graph.can_occupy_weighted(next, self.occupancy_weight))
+    }
+
+    /// Records that the mover has reached `node`, moving it off the point it left and onto
+    /// `node`, and counting the traversal (see [`NavGraph::traversal_count`]).
+    ///
+    /// Returns `false`, changing nothing, unless `node` is the next point of the path.
+    pub fn notify_reached<M: NavDomain>(&mut self, nav_graph: &mut NavGraph<M>, node: u32) -> bool {
+        if self.next_point() != Some(node) {
+            return false;
+        }
+        let left = self.current_point();
+        nav_graph.unoccupy_weighted(left, self.occupancy_weight);
+        nav_graph.occupy_unchecked(node, self.occupancy_weight);
+        nav_graph.record_traversal(left, node);
+        self.index += 1;
+        true
+    }
+
+    /// Stops following the path, releasing the point the mover occupies.
+    pub fn release<M: NavDomain>(self, nav_graph: &mut NavGraph<M>) {
+        nav_graph.unoccupy_weighted(self.current_point(), self.occupancy_weight);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridConnectivity;
+
+    #[test]
+    pub fn test_nav_path_bookkeeping() {
+        let (mut nav_graph, _) = NavGraph::<()>::from_grid(4, 1, 1.0, GridConnectivity::Four);
+        assert!(NavPath::start(&mut nav_graph, vec![]).is_none());
+        assert!(NavPath::start(&mut nav_graph, vec![1, 99]).is_none());
+
+        let occupancy = |nav_graph: &NavGraph| {
+            (1..=4)
+                .map(|id| nav_graph.get_nav_point(id).unwrap().current_occupancy())
+                .collect::<Vec<_>>()
+        };
+        let mut nav_path = NavPath::start(&mut nav_graph, vec![1, 2, 3, 4]).unwrap();
+        assert_eq!(occupancy(&nav_graph), [1, 0, 0, 0]);
+        assert_eq!(
+            nav_path.next_target(Vec3::ZERO),
+            Some(Vec3::new(1.0, 0.0, 0.0))
+        );
+
+        // Only the next point counts as reached.
+        assert!(!nav_path.notify_reached(&mut nav_graph, 3));
+        assert!(nav_path.notify_reached(&mut nav_graph, 2));
+        assert_eq!(occupancy(&nav_graph), [0, 1, 0, 0]);
+        assert_eq!(nav_graph.traversal_count(1, 2), 1);
+
+        // Another mover in the way.
+        nav_graph.occupy(3);
+        assert!(!nav_path.is_next_clear(&nav_graph));
+        nav_graph.unoccupy(3);
+        assert!(nav_path.is_next_clear(&nav_graph));
+
+        nav_path.notify_reached(&mut nav_graph, 3);
+        nav_path.notify_reached(&mut nav_graph, 4);
+        assert!(nav_path.is_complete());
+        let last = Vec3::new(3.0, 0.0, 0.0);
+        assert_eq!(nav_path.next_target(Vec3::new(2.5, 0.0, 0.0)), Some(last));
+        assert_eq!(nav_path.next_target(last), None);
+
+        nav_path.release(&mut nav_graph);
+        assert_eq!(occupancy(&nav_graph), [0, 0, 0, 0]);
+    }
+}
@@ -1,24 +1,50 @@
 use std::{
     cmp::{Ordering, Reverse},
     collections::{BinaryHeap, VecDeque},
+    f32::consts::PI,
+    marker::PhantomData,
+    ops::{Deref, DerefMut, Range},
+    sync::atomic::{AtomicU64, Ordering as AtomicOrdering},
 };
 
-use bevy_ecs::{component::Component, system::Resource};
-use bevy_math::Vec3;
+use bevy_ecs::{component::Component, entity::Entity, system::Resource};
+use bevy_math::{Vec3, Vec4};
 use bevy_reflect::prelude::*;
-use bevy_utils::{HashMap, HashSet};
+use bevy_utils::{HashMap, HashSet, Instant};
+
+use crate::{
+    availability::{edge_key, NavEdge},
+    calibration::{scale_heuristic, HeuristicCalibration},
+    churn::OccupancyChurn,
+    hierarchy::PortalGraph,
+    landmarks::Landmarks,
+    regions::RegionCostCache,
+    search::SearchTree,
+    spatial::SpatialGrid,
+    telemetry::{PathTelemetry, TelemetryHooks},
+    AreaCosts, Availability, CorridorReservation, ExpandedNode, Heuristic, NavDomain, PathPolicy,
+    PathResult, PathWarmStart, SearchOptions, TerritoryAccess,
+};
 
 #[derive(Debug, Default, Copy, Clone, Component, Reflect, FromReflect)]
 pub struct NavPointRef(pub u32);
 
-#[derive(Debug, Reflect, FromReflect)]
+#[derive(Debug, Clone, Reflect, FromReflect)]
 pub struct NavPoint {
     id: u32,
     location: Vec3,
     speed_modifier: f32,
-    connections: HashSet<u32>,
+    pub(crate) connections: HashSet<u32>,
     max_occupancy: u32,
     current_occupancy: u32,
+    region: u32,
+    tags: u32,
+    flags: u32,
+    area: u32,
+    clearance: f32,
+    /// The cost of moving to each connected point, kept up to date by the graph.
+    #[reflect(ignore)]
+    pub(crate) costs: HashMap<u32, u32>,
 }
 
 impl NavPoint {
@@ -30,6 +56,12 @@ impl NavPoint {
             connections: HashSet::new(),
             max_occupancy,
             current_occupancy: 0,
+            region: 0,
+            tags: 0,
+            flags: 0,
+            area: 0,
+            clearance: f32::INFINITY,
+            costs: HashMap::new(),
         }
     }
 
@@ -60,17 +92,113 @@ impl NavPoint {
 
     #[inline(always)]
     pub fn can_occupy(&self) -> bool {
-        self.current_occupancy < self.max_occupancy
+        self.can_occupy_weighted(1)
+    }
+
+    /// Whether there's room left for an occupant taking up `weight` slots.
+    #[inline(always)]
+    pub fn can_occupy_weighted(&self, weight: u32) -> bool {
+        self.current_occupancy.saturating_add(weight) <= self.max_occupancy
+    }
+
+    /// How full the point is, from 0.0 when empty to 1.0 when full. A point without room for
+    /// anyone counts as full.
+    #[inline(always)]
+    pub fn occupancy_ratio(&self) -> f32 {
+        if self.max_occupancy == 0 {
+            return 1.0;
+        }
+        self.current_occupancy as f32 / self.max_occupancy as f32
     }
 
     pub fn connections(&self) -> &HashSet<u32> {
         &self.connections
     }
 
+    /// The region the point belongs to. See [`NavGraph::assign_grid_regions`].
+    #[inline(always)]
+    pub fn region(&self) -> u32 {
+        self.region
+    }
+
+    pub fn set_region(&mut self, region: u32) {
+        self.region = region;
+    }
+
+    /// User-defined tag bits, e.g. marking spawn points or cover.
+    #[inline(always)]
+    pub fn tags(&self) -> u32 {
+        self.tags
+    }
+
+    /// Returns true if the point has every bit in `tags` set.
+    #[inline(always)]
+    pub fn has_tags(&self, tags: u32) -> bool {
+        self.tags & tags == tags
+    }
+
+    pub fn set_tags(&mut self, tags: u32) {
+        self.tags = tags;
+    }
+
+    /// Traversal flag bits, e.g. marking points only flying or swimming travelers can use.
+    /// Points with flags are only passable to searches whose
+    /// [`SearchOptions::traversal_mask`] shares at least one of them. Points without flags are
+    /// open to everyone.
+    #[inline(always)]
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn set_flags(&mut self, flags: u32) {
+        self.flags = flags;
+    }
+
+    /// The area type of the point, e.g. road, grass or mud, which travelers weigh according to
+    /// their [`AreaCosts`](crate::AreaCosts).
+    #[inline(always)]
+    pub fn area(&self) -> u32 {
+        self.area
+    }
+
+    pub fn set_area(&mut self, area: u32) {
+        self.area = area;
+    }
+
+    /// The radius of the largest traveler that fits through the point, unlimited by default.
+    /// See [`SearchOptions::agent_size`].
+    #[inline(always)]
+    pub fn clearance(&self) -> f32 {
+        self.clearance
+    }
+
+    pub fn set_clearance(&mut self, clearance: f32) {
+        self.clearance = clearance;
+    }
+
+    pub fn set_location(&mut self, location: Vec3) {
+        self.location = location;
+    }
+
+    pub fn set_speed_modifier(&mut self, speed_modifier: f32) {
+        self.speed_modifier = speed_modifier;
+    }
+
+    /// Sets the maximum occupancy. Existing occupants are kept even if this lowers it below
+    /// the current occupancy.
+    pub fn set_max_occupancy(&mut self, max_occupancy: u32) {
+        self.max_occupancy = max_occupancy;
+    }
+
     #[inline(always)]
     pub fn occupy(&mut self) -> bool {
-        if self.can_occupy() {
-            self.current_occupancy += 1;
+        self.occupy_weighted(1)
+    }
+
+    #[inline(always)]
+    pub fn occupy_weighted(&mut self, weight: u32) -> bool {
+        if self.can_occupy_weighted(weight) {
+            self.current_occupancy += weight;
             true
         } else {
             false
@@ -78,29 +206,171 @@ impl NavPoint {
     }
 
     pub fn unoccupy(&mut self) {
-        self.current_occupancy = if self.current_occupancy > 0 {
-            self.current_occupancy - 1
-        } else {
-            0
-        };
+        self.unoccupy_weighted(1);
+    }
+
+    pub fn unoccupy_weighted(&mut self, weight: u32) {
+        self.current_occupancy = self.current_occupancy.saturating_sub(weight);
+    }
+
+    pub(crate) fn set_current_occupancy(&mut self, current_occupancy: u32) {
+        self.current_occupancy = current_occupancy;
     }
 }
 
-pub(crate) struct NavPointIdCounter(u32);
+/// How the speed modifiers of a connection's two points combine into the speed along it, both
+/// for what searches take it to cost and for how fast travelers move along it. See
+/// [`NavGraph::set_edge_speed_model`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+#[cfg_attr(feature = "asset", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdgeSpeedModel {
+    /// The speed modifier of the point the connection leads from.
+    From,
+    /// The speed modifier of the point the connection leads to.
+    #[default]
+    To,
+    /// The mean of both points' speed modifiers.
+    Average,
+    /// Changes evenly from one point's speed modifier to the other's along the connection, so
+    /// travelers speed up or slow down gradually. Costs go by the time this takes to cross.
+    Blend,
+}
 
-impl Default for NavPointIdCounter {
-    fn default() -> Self {
-        Self(1)
+impl EdgeSpeedModel {
+    /// The speed modifier crossing the whole connection averages out to, by time taken.
+    pub fn effective(self, from: f32, to: f32) -> f32 {
+        match self {
+            Self::From => from,
+            Self::To => to,
+            Self::Average => (from + to) / 2.0,
+            Self::Blend => {
+                if from <= 0.0 || to <= 0.0 {
+                    0.0
+                } else if (to - from).abs() <= f32::EPSILON * from.max(to) {
+                    from
+                } else {
+                    // Crossing a distance `d` at a rate changing linearly takes
+                    // `d * ln(to / from) / (to - from)`.
+                    (to - from) / (to / from).ln()
+                }
+            }
+        }
+    }
+
+    /// The speed modifier `progress` of the way along the connection, from 0.0 at the point it
+    /// leads from to 1.0 at the point it leads to.
+    pub fn at(self, from: f32, to: f32, progress: f32) -> f32 {
+        match self {
+            Self::Blend => from + (to - from) * progress.clamp(0.0, 1.0),
+            _ => self.effective(from, to),
+        }
     }
 }
 
-pub(crate) struct NavPointIdFreelist(VecDeque<u32>);
+/// The cost of moving from `from` onto a point at `to` with the given speed modifier.
+#[inline(always)]
+pub(crate) fn travel_cost(from: Vec3, to: Vec3, speed_modifier: f32) -> u32 {
+    (from.distance_squared(to) / speed_modifier * 100.0) as u32
+}
 
-impl NavPointIdFreelist {
-    pub fn new() -> Self {
-        Self(VecDeque::with_capacity(500))
+/// The cost of a connection which takes `duration` seconds to cross, as much as one a traveler
+/// at `speed` would take as long to walk. See [`NavGraph::set_connection_duration`].
+#[inline(always)]
+fn duration_cost(duration: f32, speed: f32) -> u32 {
+    let distance = duration * speed;
+    (distance * distance * 100.0) as u32
+}
+
+/// The extra cost of turning at `at` when coming from `from` and heading on to `to`. See
+/// [`SearchOptions::turn_penalty`].
+fn turn_cost(from: Vec3, at: Vec3, to: Vec3, turn_penalty: f32) -> u32 {
+    let angle = (at - from).angle_between(to - at);
+    if angle.is_nan() {
+        return 0;
+    }
+    let distance = turn_penalty * angle / PI;
+    (distance * distance * 100.0) as u32
+}
+
+/// Estimates the cost from `from` to `to` with `heuristic`, given the least any connection costs
+/// per unit of length.
+#[inline(always)]
+fn estimate(heuristic: Heuristic, from: &NavPoint, to: &NavPoint, cost_per_unit: f32) -> u32 {
+    let delta = (to.location - from.location).abs();
+    let length = match heuristic {
+        Heuristic::Euclidean => delta.length(),
+        Heuristic::Manhattan => delta.x + delta.y + delta.z,
+        Heuristic::Octile => {
+            let (long, short) = (delta.x.max(delta.y), delta.x.min(delta.y));
+            long + (std::f32::consts::SQRT_2 - 1.0) * short + delta.z
+        }
+        Heuristic::SquaredLegacy => {
+            return travel_cost(from.location, to.location, to.speed_modifier)
+        }
+    };
+    (length * cost_per_unit) as u32
+}
+
+/// Computes the `heuristic` estimate from each of `points` to `goal` into `costs`, four at a
+/// time.
+///
+/// The arithmetic is the same as [`estimate`]'s, lane by lane, so the results are identical.
+fn batch_estimates(
+    heuristic: Heuristic,
+    points: &[&NavPoint],
+    goal: &NavPoint,
+    cost_per_unit: f32,
+    costs: &mut Vec<u32>,
+) {
+    costs.clear();
+    let mut chunks = points.chunks_exact(4);
+    let (gx, gy, gz) = (
+        Vec4::splat(goal.location.x),
+        Vec4::splat(goal.location.y),
+        Vec4::splat(goal.location.z),
+    );
+    for chunk in &mut chunks {
+        let lanes = |f: fn(Vec3) -> f32| {
+            Vec4::new(
+                f(chunk[0].location),
+                f(chunk[1].location),
+                f(chunk[2].location),
+                f(chunk[3].location),
+            )
+        };
+        let dx = (gx - lanes(|l| l.x)).abs();
+        let dy = (gy - lanes(|l| l.y)).abs();
+        let dz = (gz - lanes(|l| l.z)).abs();
+        let length = match heuristic {
+            Heuristic::Euclidean => {
+                Vec4::from_array((dx * dx + dy * dy + dz * dz).to_array().map(f32::sqrt))
+            }
+            Heuristic::Manhattan => dx + dy + dz,
+            Heuristic::Octile => {
+                let (long, short) = (dx.max(dy), dx.min(dy));
+                long + (std::f32::consts::SQRT_2 - 1.0) * short + dz
+            }
+            Heuristic::SquaredLegacy => {
+                let cost = (dx * dx + dy * dy + dz * dz) / goal.speed_modifier * 100.0;
+                costs.extend(cost.to_array().map(|c| c as u32));
+                continue;
+            }
+        };
+        costs.extend((length * cost_per_unit).to_array().map(|c| c as u32));
     }
+    costs.extend(
+        chunks
+            .remainder()
+            .iter()
+            .map(|point| estimate(heuristic, point, goal, cost_per_unit)),
+    );
+}
 
+/// The IDs of removed points, for [`NavGraph::add_nav_point_auto`] to reuse, oldest first.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct NavPointIdFreelist(VecDeque<u32>);
+
+impl NavPointIdFreelist {
     pub fn freed(&mut self, id: u32) {
         self.0.push_back(id);
     }
@@ -108,20 +378,188 @@ impl NavPointIdFreelist {
     pub fn next(&mut self) -> Option<u32> {
         self.0.pop_front()
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        self.0.capacity() * std::mem::size_of::<u32>()
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit();
+    }
+}
+
+/// The graph of [`NavPoint`]s travelers navigate.
+///
+/// Each [`NavDomain`] has its own graph resource; `NavGraph` on its own is the graph of the
+/// default domain.
+/// The version given to the next topology change of any graph, see
+/// [`NavGraph::topology_version`].
+static NEXT_TOPOLOGY_VERSION: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Resource, Reflect, FromReflect)]
+pub struct NavGraph<M: NavDomain = ()> {
+    pub(crate) points: HashMap<u32, NavPoint>,
+    pub(crate) highest_id: u32,
+    #[reflect(ignore)]
+    pub(crate) id_freelist: NavPointIdFreelist,
+    topology_version: u64,
+    capacity_hint: f32,
+    pub(crate) corridor_reservations: HashMap<u32, CorridorReservation>,
+    pub(crate) point_reservations: HashMap<u32, Entity>,
+    pub(crate) point_availability: HashMap<u32, Availability>,
+    pub(crate) edges: HashMap<(u32, u32), NavEdge>,
+    pub(crate) stock: HashMap<u32, u32>,
+    pub(crate) owners: HashMap<u32, u32>,
+    pub(crate) destination_quotas: HashMap<u32, u32>,
+    pub(crate) hostilities: HashSet<(u32, u32)>,
+    heuristic: Heuristic,
+    edge_speed_model: EdgeSpeedModel,
+    #[reflect(ignore)]
+    pub(crate) region_costs: RegionCostCache,
+    #[reflect(ignore)]
+    pub(crate) hierarchy: PortalGraph,
+    #[reflect(ignore)]
+    pub(crate) heuristic_calibration: HeuristicCalibration,
+    #[reflect(ignore)]
+    pub(crate) landmarks: Landmarks,
+    #[reflect(ignore)]
+    pub(crate) churn: HashMap<u32, OccupancyChurn>,
+    #[reflect(ignore)]
+    pub(crate) revocation_candidates: HashSet<u32>,
+    #[reflect(ignore)]
+    pub(crate) traversals: HashMap<(u32, u32), u32>,
+    #[reflect(ignore)]
+    pub(crate) spatial: SpatialGrid,
+    #[reflect(ignore)]
+    telemetry_hooks: TelemetryHooks,
+    #[reflect(ignore)]
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> Default for NavGraph<M> {
+    fn default() -> Self {
+        Self {
+            points: HashMap::default(),
+            highest_id: 0,
+            id_freelist: NavPointIdFreelist::default(),
+            topology_version: 0,
+            capacity_hint: DEFAULT_CAPACITY_HINT,
+            corridor_reservations: HashMap::default(),
+            point_reservations: HashMap::default(),
+            point_availability: HashMap::default(),
+            edges: HashMap::default(),
+            stock: HashMap::default(),
+            owners: HashMap::default(),
+            destination_quotas: HashMap::default(),
+            hostilities: HashSet::default(),
+            heuristic: Heuristic::default(),
+            edge_speed_model: EdgeSpeedModel::default(),
+            region_costs: RegionCostCache::default(),
+            hierarchy: PortalGraph::default(),
+            heuristic_calibration: HeuristicCalibration::default(),
+            landmarks: Landmarks::default(),
+            churn: HashMap::default(),
+            revocation_candidates: HashSet::default(),
+            traversals: HashMap::default(),
+            spatial: SpatialGrid::default(),
+            telemetry_hooks: TelemetryHooks::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: NavDomain> Clone for NavGraph<M> {
+    fn clone(&self) -> Self {
+        Self {
+            points: self.points.clone(),
+            highest_id: self.highest_id,
+            id_freelist: self.id_freelist.clone(),
+            topology_version: self.topology_version,
+            capacity_hint: self.capacity_hint,
+            corridor_reservations: self.corridor_reservations.clone(),
+            point_reservations: self.point_reservations.clone(),
+            point_availability: self.point_availability.clone(),
+            edges: self.edges.clone(),
+            stock: self.stock.clone(),
+            owners: self.owners.clone(),
+            destination_quotas: self.destination_quotas.clone(),
+            hostilities: self.hostilities.clone(),
+            heuristic: self.heuristic,
+            edge_speed_model: self.edge_speed_model,
+            region_costs: self.region_costs.clone(),
+            hierarchy: self.hierarchy.clone(),
+            heuristic_calibration: self.heuristic_calibration.clone(),
+            landmarks: self.landmarks.clone(),
+            churn: self.churn.clone(),
+            revocation_candidates: self.revocation_candidates.clone(),
+            traversals: self.traversals.clone(),
+            spatial: self.spatial.clone(),
+            telemetry_hooks: self.telemetry_hooks.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Two points per unit of distance, i.e. connected points about half a unit apart.
+pub(crate) const DEFAULT_CAPACITY_HINT: f32 = 2.0;
+
+/// Mutable access to a [`NavPoint`], from [`NavGraph::get_nav_point_mut`].
+///
+/// The graph updates whatever it tracks about the point, such as its connection costs and
+/// [`NavGraph::topology_version`], when this is dropped.
+pub struct NavPointMut<'a, M: NavDomain = ()> {
+    nav_graph: &'a mut NavGraph<M>,
+    id: u32,
+    before: (Vec3, f32, u32),
+}
+
+impl<M: NavDomain> Deref for NavPointMut<'_, M> {
+    type Target = NavPoint;
+
+    fn deref(&self) -> &NavPoint {
+        &self.nav_graph.points[&self.id]
+    }
+}
+
+impl<M: NavDomain> DerefMut for NavPointMut<'_, M> {
+    fn deref_mut(&mut self) -> &mut NavPoint {
+        self.nav_graph.points.get_mut(&self.id).unwrap()
+    }
 }
 
-#[derive(Debug, Default, Resource, Reflect, FromReflect)]
-pub struct NavGraph {
-    points: HashMap<u32, NavPoint>,
-    highest_id: u32,
+impl<M: NavDomain> Drop for NavPointMut<'_, M> {
+    fn drop(&mut self) {
+        self.nav_graph.track_point_changes(self.id, self.before);
+    }
 }
 
 #[derive(Eq)]
 struct PathNode {
     id: u32,
+    /// The number of points in the path to this one, when searches track it. See
+    /// [`SearchKey`].
+    depth: usize,
     f: u32,
 }
 
+impl PathNode {
+    fn key(&self) -> SearchKey {
+        (self.id, self.depth)
+    }
+}
+
+/// What searches keep their state by: a point, and the number of points in the path to it.
+///
+/// The number is only tracked with [`SearchOptions::max_nodes`], and is otherwise always 0, so
+/// each point has a single state. With a node limit, a point reached by a cheap but long route
+/// and by a dearer but shorter one keeps both, so the shorter route can still finish within the
+/// limit.
+type SearchKey = (u32, usize);
+
 impl PartialEq for PathNode {
     fn eq(&self, other: &Self) -> bool {
         self.f == other.f
@@ -130,7 +568,7 @@ impl PartialEq for PathNode {
 
 impl PartialOrd for PathNode {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.f.cmp(&other.f))
+        Some(self.cmp(other))
     }
 }
 
@@ -156,10 +594,35 @@ impl NavGraph {
     /// it may be useful to preallocate an estimated minimum to avoid lots of small copying as the
     /// structure upsizes.
     pub fn with_capacity(capacity: usize) -> Self {
-        Self {
-            points: HashMap::with_capacity(capacity),
-            ..Default::default()
-        }
+        let mut nav_graph = Self::default();
+        nav_graph.reserve(capacity);
+        nav_graph
+    }
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Reserves space for at least `additional` more [`NavPoint`]s.
+    pub fn reserve(&mut self, additional: usize) {
+        self.points.reserve(additional);
+    }
+
+    /// How many points a path is expected to pass through per unit of straight-line distance.
+    /// See [`NavGraph::set_capacity_hint`].
+    pub fn capacity_hint(&self) -> f32 {
+        self.capacity_hint
+    }
+
+    /// Sets how many points a path is expected to pass through per unit of straight-line
+    /// distance, which sizes the cells of the spatial grid behind [`NavGraph::nearest_point`].
+    /// [`NavGraph::find_path`] preallocates its search from the spacing measured by the grid,
+    /// falling back to this hint.
+    ///
+    /// The default of `2.0` suits points about half a unit apart. Graphs with wider spacing
+    /// should lower it, or the grid's cells are too small to hold more than one point each;
+    /// e.g. points 16 units apart suit roughly `2.0 / 16.0`.
+    pub fn set_capacity_hint(&mut self, capacity_hint: f32) {
+        self.capacity_hint = capacity_hint.max(0.0);
+        self.spatial.resize_for(self.capacity_hint);
     }
 
     /// Returns the number of [`NavPoint`]s currently in the graph.
@@ -204,7 +667,105 @@ impl NavGraph {
         if point.id > self.highest_id {
             self.highest_id = point.id;
         }
-        self.points.insert(point.id, point);
+        let id = point.id;
+        let location = point.location;
+        if let Some(replaced) = self.points.insert(id, point) {
+            self.spatial.remove(id, replaced.location);
+        }
+        self.spatial.insert(id, location);
+        self.refresh_connection_costs(id);
+        self.topology_changed();
+    }
+
+    /// Adds a new [`NavPoint`] to the graph with an ID allocated by the graph, returning the ID.
+    ///
+    /// IDs of removed points are reused, oldest first, before new IDs above
+    /// [`NavGraph::highest_id`] are handed out. Like [`NavGraph::add_nav_point`], the point isn't
+    /// connected to anything.
+    ///
+    /// Returns `None`, adding nothing, if no freed IDs are left and [`NavGraph::highest_id`] is
+    /// already `u32::MAX`.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// let a = nav_graph.add_nav_point_auto(Vec3::new(0.0, 0.0, 0.0), 1.0, 1);
+    /// let b = nav_graph.add_nav_point_auto(Vec3::new(1.0, 0.0, 0.0), 1.0, 1);
+    /// assert_eq!((a, b), (Some(1), Some(2)));
+    ///
+    /// nav_graph.remove_point(1);
+    /// assert_eq!(nav_graph.add_nav_point_auto(Vec3::new(2.0, 0.0, 0.0), 1.0, 1), Some(1));
+    /// assert_eq!(nav_graph.add_nav_point_auto(Vec3::new(3.0, 0.0, 0.0), 1.0, 1), Some(3));
+    ///
+    /// nav_graph.add_nav_point(NavPoint::new(u32::MAX, Vec3::new(4.0, 0.0, 0.0), 1.0, 1));
+    /// assert_eq!(nav_graph.next_free_id(), None);
+    /// assert_eq!(nav_graph.add_nav_point_auto(Vec3::new(5.0, 0.0, 0.0), 1.0, 1), None);
+    /// ```
+    pub fn add_nav_point_auto(
+        &mut self,
+        location: Vec3,
+        speed_modifier: f32,
+        max_occupancy: u32,
+    ) -> Option<u32> {
+        // Freed IDs may since have been taken by points added with explicit IDs.
+        let id = std::iter::from_fn(|| self.id_freelist.next())
+            .find(|id| !self.points.contains_key(id))
+            .or_else(|| self.highest_id.checked_add(1))?;
+        self.add_nav_point(NavPoint::new(id, location, speed_modifier, max_occupancy));
+        Some(id)
+    }
+
+    /// The highest ID ever added to or reserved in the graph.
+    ///
+    /// This never goes down: removing the point with the highest ID leaves it as is, so IDs
+    /// above it are never handed out by [`NavGraph::add_nav_point_auto`] before they're used.
+    pub fn highest_id(&self) -> u32 {
+        self.highest_id
+    }
+
+    /// The ID [`NavGraph::add_nav_point_auto`] would allocate next, without allocating it, or
+    /// `None` if it would fail.
+    pub fn next_free_id(&self) -> Option<u32> {
+        self.id_freelist
+            .iter()
+            .find(|id| !self.points.contains_key(id))
+            .or_else(|| self.highest_id.checked_add(1))
+    }
+
+    /// Reserves `count` consecutive IDs above [`NavGraph::highest_id`] and returns them, so
+    /// content can be generated elsewhere, e.g. on other threads, and added later with
+    /// [`NavGraph::add_nav_point`] without colliding with IDs the graph allocates meanwhile.
+    ///
+    /// Reserved IDs are never allocated by the graph. Only [`NavGraph::remove_point`] returns an
+    /// ID to the pool [`NavGraph::add_nav_point_auto`] reuses, so reserved IDs that end up unused
+    /// are simply skipped.
+    ///
+    /// Returns `None`, reserving nothing, if the range wouldn't fit below `u32::MAX`.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// let reserved = nav_graph.reserve_id_range(10).unwrap();
+    /// assert_eq!(reserved, 1..11);
+    ///
+    /// // The graph allocates IDs past the reservation...
+    /// assert_eq!(nav_graph.add_nav_point_auto(Vec3::ZERO, 1.0, 1), Some(11));
+    /// // ...while the generator fills it in.
+    /// for id in reserved {
+    ///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 1.0, 0.0), 1.0, 1));
+    /// }
+    /// assert_eq!(nav_graph.len(), 11);
+    /// assert_eq!(nav_graph.reserve_id_range(u32::MAX), None);
+    /// ```
+    pub fn reserve_id_range(&mut self, count: u32) -> Option<Range<u32>> {
+        let start = self.highest_id.checked_add(1)?;
+        let end = start.checked_add(count)?;
+        self.highest_id = end - 1;
+        Some(start..end)
     }
 
     /// Connects two [`NavPoint`]s in the graph, making a travelable path between them.
@@ -273,70 +834,58 @@ impl NavGraph {
         self.points.entry(b).and_modify(|point| {
             point.connections.insert(a);
         });
+        self.cache_connection_cost(a, b);
+        self.cache_connection_cost(b, a);
+        self.topology_changed();
     }
 
-    /// Returns true if a node with the current ID is in the graph.
-    #[inline(always)]
-    pub fn has_nav_point(&self, id: u32) -> bool {
-        self.points.contains_key(&id)
-    }
-
-    /// Returns the specified [`NavPoint`] if it exists in the graph.
-    #[inline(always)]
-    pub fn get_nav_point(&self, id: u32) -> Option<&NavPoint> {
-        self.points.get(&id)
-    }
-
-    /// Removes the specified point from the graph and all related connections.
+    /// Severs the connection between two [`NavPoint`]s in both directions, e.g. when a bridge
+    /// collapses, leaving both points otherwise intact.
     ///
-    /// Note that this function is `O(n)` with the number of connected points.
+    /// Any attributes of the connection, such as its availability window or weight, are
+    /// forgotten. Paths cached against the old topology (see [`NavGraph::topology_version`]) are
+    /// invalidated, but travelers already following a path across the connection need to be
+    /// re-planned, e.g. with [`Replan`](crate::Replan).
     ///
     /// ## Example
-    ///
-    /// If we create the following graph:
-    ///
-    /// 1
-    /// |\
-    /// | \
-    /// 2  3
-    /// | /
-    /// |/
-    /// 4
-    ///
-    /// The initial path between 1 and 4 should be `[2, 4]`. Removing node 2
-    /// should then result in `[3, 4]`.
-    ///
     /// ```
     /// # use bevy_math::Vec3;
     /// # use bevy_navigator::{NavGraph, NavPoint};
     /// let mut nav_graph = NavGraph::new();
     /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
-    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(0.0, -1.0, 0.0), 1.0, 1));
-    /// nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(-1.0, -1.0, 0.0), 1.0, 1));
-    /// nav_graph.add_nav_point(NavPoint::new(4, Vec3::new(0.0, -2.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
     /// nav_graph.connect_points(1, 2);
-    /// nav_graph.connect_points(1, 3);
-    /// nav_graph.connect_points(2, 4);
-    /// nav_graph.connect_points(3, 4);
     ///
-    /// assert_eq!(nav_graph.find_path(1, 4).unwrap()[..], [1, 2, 4]);
-    /// nav_graph.remove_point(2);
-    /// assert_eq!(nav_graph.find_path(1, 4).unwrap()[..], [1, 3, 4]);
+    /// nav_graph.disconnect_points(2, 1);
+    /// assert!(nav_graph.find_path(1, 2).is_none());
+    /// assert!(nav_graph.get(1).unwrap().connections().is_empty());
     /// ```
-    ///
-    pub fn remove_point(&mut self, id: u32) {
-        if let Some(point) = self.points.remove(&id) {
-            for connection in &point.connections {
-                self.points.entry(*connection).and_modify(|b| {
-                    b.connections.remove(&point.id);
-                });
+    pub fn disconnect_points(&mut self, a: u32, b: u32) {
+        let connected = self
+            .get_nav_point(a)
+            .map(|point| point.connections.contains(&b))
+            .unwrap_or(false);
+        if !connected {
+            return;
+        }
+
+        for (from, to) in [(a, b), (b, a)] {
+            if let Some(point) = self.points.get_mut(&from) {
+                point.connections.remove(&to);
+                point.costs.remove(&to);
             }
         }
+        self.edges.remove(&edge_key(a, b));
+        self.revocation_candidates.extend([a, b]);
+        self.topology_changed();
     }
 
-    /// Checks whether the specified point has capacity for more occupants.
+    /// Connects two [`NavPoint`]s like [`NavGraph::connect_points`], but with an explicit cost
+    /// for moving between them in either direction, e.g. for a toll road or a dangerous
+    /// corridor.
     ///
-    /// Also returns false if the specified point doesn't exist.
+    /// The weight is kept even if either point is moved. Weights below the distance-based cost
+    /// loosen the heuristic estimates for the whole graph, so searches expand more points.
     ///
     /// ## Example
     /// ```
@@ -344,161 +893,1590 @@ impl NavGraph {
     /// # use bevy_navigator::{NavGraph, NavPoint};
     /// let mut nav_graph = NavGraph::new();
     /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
-    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(0.0, 0.0, 0.0), 1.0, 2));
-    ///
-    /// assert!(nav_graph.can_occupy(1));
-    /// // Can't occupy points which don't exist.
-    /// assert!(!nav_graph.can_occupy(3));
-    ///
-    /// // The NavPoint has a max_occupancy of 1, so this will mark it as full.
-    /// nav_graph.occupy(1);
-    /// assert!(!nav_graph.can_occupy(1));
-    ///
-    /// nav_graph.occupy(2);
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(2.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(4, Vec3::new(1.0, 1.0, 0.0), 1.0, 1));
+    /// nav_graph.connect_points_weighted(1, 2, 1000);
+    /// nav_graph.connect_points(2, 3);
+    /// nav_graph.connect_points(1, 4);
+    /// nav_graph.connect_points(4, 3);
     ///
-    /// // NavPoint 2 has a max_occupancy of 2, so this should still return true with 1 occupant.
-    /// assert!(nav_graph.can_occupy(2));
+    /// // The toll makes the direct route dearer than going around.
+    /// assert_eq!(nav_graph.find_path(1, 3).unwrap()[..], [1, 4, 3]);
+    /// assert_eq!(nav_graph.path_cost(&[1, 2]), Some(1000));
     /// ```
-    pub fn can_occupy(&self, id: u32) -> bool {
-        self.points
-            .get(&id)
-            .map(|p| p.can_occupy())
-            .unwrap_or(false)
+    pub fn connect_points_weighted(&mut self, a: u32, b: u32, weight: u32) {
+        self.connect_points(a, b);
+        self.set_connection_weight(a, b, Some(weight));
     }
 
-    /// Attempts to increase the occupant count for a node and returns whether it succeeded.
+    /// Sets the explicit cost of the connection between `a` and `b`, or goes back to deriving it
+    /// from the points with `None`. See [`NavGraph::connect_points_weighted`].
     ///
-    /// Also returns false if the specified NavPoint doesn't exist.
+    /// Does nothing if the points aren't connected.
+    pub fn set_connection_weight(&mut self, a: u32, b: u32, weight: Option<u32>) {
+        let connected = self
+            .get_nav_point(a)
+            .map(|point| point.connections.contains(&b))
+            .unwrap_or(false);
+        if !connected {
+            return;
+        }
+
+        match weight {
+            Some(_) => self.edges.entry(edge_key(a, b)).or_default().weight = weight,
+            None => {
+                if let Some(edge) = self.edges.get_mut(&edge_key(a, b)) {
+                    edge.weight = None;
+                }
+            }
+        }
+        self.cache_connection_cost(a, b);
+        self.cache_connection_cost(b, a);
+        self.topology_changed();
+    }
+
+    /// Makes moving along the connection between `a` and `b` take `duration` seconds in either
+    /// direction, whatever its length and the traveler's speed, e.g. for an elevator ride or a
+    /// ferry crossing. `None` goes back to moving at the traveler's speed.
+    ///
+    /// Travelers move along the connection at a steady pace which gets them across in
+    /// `duration`. Unless the connection has a weight, searches cost it as a connection a
+    /// traveler would take as long to cross at a speed of 1.0, or at the speed of the search's
+    /// [`SearchOptions::schedule`]. As with weights, durations shorter than that of a walk
+    /// along the connection can make searches miss the cheapest path.
+    ///
+    /// Does nothing if the points aren't connected.
     ///
     /// ## Example
     /// ```
     /// # use bevy_math::Vec3;
     /// # use bevy_navigator::{NavGraph, NavPoint};
     /// let mut nav_graph = NavGraph::new();
-    ///
     /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(0.0, 0.0, 30.0), 1.0, 1));
+    /// nav_graph.connect_points(1, 2);
     ///
-    /// // The NavPoint has a max_occupancy of 1, so the first call should succeed and the second
-    /// // should fail.
-    /// assert!(nav_graph.occupy(1));
-    /// assert!(!nav_graph.occupy(1));
-    ///
-    /// // This should return false for NavPoints that aren't in the graph.
-    /// assert!(!nav_graph.occupy(1));
-    ///
+    /// // The elevator takes 5 seconds, however many floors it climbs.
+    /// nav_graph.set_connection_duration(1, 2, Some(5.0));
+    /// assert_eq!(nav_graph.connection_duration(2, 1), Some(5.0));
+    /// assert_eq!(nav_graph.path_cost(&[1, 2]), Some(2500));
     /// ```
-    pub fn occupy(&mut self, id: u32) -> bool {
-        let mut occupied = false;
-        self.points.entry(id).and_modify(|p| {
-            occupied = p.occupy();
+    pub fn set_connection_duration(&mut self, a: u32, b: u32, duration: Option<f32>) {
+        let connected = self
+            .get_nav_point(a)
+            .map(|point| point.connections.contains(&b))
+            .unwrap_or(false);
+        if !connected {
+            return;
+        }
+
+        match duration {
+            Some(_) => self.edges.entry(edge_key(a, b)).or_default().duration = duration,
+            None => {
+                if let Some(edge) = self.edges.get_mut(&edge_key(a, b)) {
+                    edge.duration = None;
+                }
+            }
+        }
+        self.cache_connection_cost(a, b);
+        self.cache_connection_cost(b, a);
+        self.topology_changed();
+    }
+
+    /// The fixed time moving between `a` and `b` takes, if it has one. See
+    /// [`NavGraph::set_connection_duration`].
+    #[inline(always)]
+    pub fn connection_duration(&self, a: u32, b: u32) -> Option<f32> {
+        self.edge(a, b).and_then(|edge| edge.duration)
+    }
+
+    /// A version which changes whenever points or connections are added, removed, moved or have
+    /// their speed modifier or region changed.
+    ///
+    /// Versions are shared by all graphs, so a graph swapped in for another never picks up its
+    /// version by making the same number of edits. Occupancy changes don't affect it, so results computed against the same topology version
+    /// only need their occupancy rechecked to stay valid.
+    #[inline(always)]
+    pub fn topology_version(&self) -> u64 {
+        self.topology_version
+    }
+
+    #[inline(always)]
+    pub(crate) fn topology_changed(&mut self) {
+        self.topology_version = NEXT_TOPOLOGY_VERSION.fetch_add(1, AtomicOrdering::Relaxed);
+    }
+
+    /// Inserts points whose connections and costs have already been worked out, e.g. by a
+    /// builder, without linking them to anything already in the graph.
+    pub(crate) fn insert_linked_points(&mut self, points: impl IntoIterator<Item = NavPoint>) {
+        for point in points {
+            self.highest_id = self.highest_id.max(point.id);
+            self.spatial.insert(point.id, point.location);
+            let id = point.id;
+            self.points.insert(id, point);
+            self.note_connections(id);
+        }
+        self.topology_changed();
+    }
+
+    /// Returns true if a node with the current ID is in the graph.
+    #[inline(always)]
+    pub fn has_nav_point(&self, id: u32) -> bool {
+        self.points.contains_key(&id)
+    }
+
+    /// Returns the specified [`NavPoint`] if it exists in the graph.
+    #[inline(always)]
+    pub fn get_nav_point(&self, id: u32) -> Option<&NavPoint> {
+        self.points.get(&id)
+    }
+
+    /// Returns the specified [`NavPoint`] if it exists in the graph.
+    ///
+    /// Shorthand for [`NavGraph::get_nav_point`].
+    #[inline(always)]
+    pub fn get(&self, id: u32) -> Option<&NavPoint> {
+        self.get_nav_point(id)
+    }
+
+    /// Returns mutable access to the specified [`NavPoint`] if it exists in the graph.
+    ///
+    /// Like [`NavGraph::modify`], changes are tracked when the returned [`NavPointMut`] is
+    /// dropped.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// let version = nav_graph.topology_version();
+    ///
+    /// if let Some(mut point) = nav_graph.get_nav_point_mut(1) {
+    ///     point.set_speed_modifier(0.5);
+    ///     point.set_max_occupancy(3);
+    /// }
+    ///
+    /// assert_eq!(nav_graph.get(1).unwrap().max_occupancy(), 3);
+    /// assert_ne!(nav_graph.topology_version(), version);
+    /// ```
+    pub fn get_nav_point_mut(&mut self, id: u32) -> Option<NavPointMut<'_, M>> {
+        let point = self.points.get(&id)?;
+        let before = (point.location, point.speed_modifier, point.region);
+        Some(NavPointMut {
+            nav_graph: self,
+            id,
+            before,
+        })
+    }
+
+    /// Iterates over every [`NavPoint`] in the graph, in no particular order.
+    pub fn iter_points(&self) -> impl Iterator<Item = &NavPoint> {
+        self.points.values()
+    }
+
+    /// Moves the specified [`NavPoint`]. Does nothing if it doesn't exist.
+    pub fn set_location(&mut self, id: u32, location: Vec3) {
+        self.modify(id, |point| point.set_location(location));
+    }
+
+    /// Sets the speed modifier of the specified [`NavPoint`]. Does nothing if it doesn't exist.
+    pub fn set_speed_modifier(&mut self, id: u32, speed_modifier: f32) {
+        self.modify(id, |point| point.set_speed_modifier(speed_modifier));
+    }
+
+    /// Sets the maximum occupancy of the specified [`NavPoint`]. Does nothing if it doesn't
+    /// exist. See [`NavPoint::set_max_occupancy`].
+    ///
+    /// Lowering it below the current occupancy revokes the point from travelers moving onto it,
+    /// sending [`ReservationRevoked`](crate::ReservationRevoked).
+    pub fn set_max_occupancy(&mut self, id: u32, max_occupancy: u32) {
+        self.modify(id, |point| point.set_max_occupancy(max_occupancy));
+    }
+
+    /// Calls `f` with mutable access to the specified [`NavPoint`], returning its result, or
+    /// `None` if the point doesn't exist.
+    ///
+    /// Points can't be mutated directly, as the graph tracks changes to them. Going through this
+    /// method keeps that tracking (such as [`NavGraph::topology_version`]) consistent with
+    /// whatever `f` changes.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// let version = nav_graph.topology_version();
+    ///
+    /// nav_graph.modify(1, |point| point.set_location(Vec3::new(2.0, 0.0, 0.0)));
+    ///
+    /// assert_eq!(nav_graph.get(1).unwrap().location(), Vec3::new(2.0, 0.0, 0.0));
+    /// assert_ne!(nav_graph.topology_version(), version);
+    /// ```
+    pub fn modify<R>(&mut self, id: u32, f: impl FnOnce(&mut NavPoint) -> R) -> Option<R> {
+        let point = self.points.get_mut(&id)?;
+        let (location, speed_modifier, region) =
+            (point.location, point.speed_modifier, point.region);
+
+        let result = f(point);
+        self.track_point_changes(id, (location, speed_modifier, region));
+        Some(result)
+    }
+
+    /// Updates whatever the graph tracks about the specified point after it's been mutated,
+    /// given its location, speed modifier and region from before.
+    fn track_point_changes(
+        &mut self,
+        id: u32,
+        (location, speed_modifier, region): (Vec3, f32, u32),
+    ) {
+        let Some(point) = self.points.get(&id) else {
+            return;
+        };
+        if point.current_occupancy > point.max_occupancy {
+            self.revocation_candidates.insert(id);
+        }
+        let new_location = point.location;
+        let moved = new_location != location || point.speed_modifier != speed_modifier;
+        if moved || point.region != region {
+            self.topology_changed();
+        }
+        if new_location != location {
+            self.spatial.remove(id, location);
+            self.spatial.insert(id, new_location);
+        }
+        if moved {
+            self.refresh_connection_costs(id);
+        }
+    }
+
+    /// Removes the specified point from the graph and all related connections.
+    ///
+    /// Note that this function is `O(n)` with the number of connected points.
+    ///
+    /// The ID becomes free for [`NavGraph::add_nav_point_auto`] to reuse, unless a point is added
+    /// with it again first.
+    ///
+    /// ## Example
+    ///
+    /// If we create the following graph:
+    ///
+    /// 1
+    /// |\
+    /// | \
+    /// 2  3
+    /// | /
+    /// |/
+    /// 4
+    ///
+    /// The initial path between 1 and 4 should be `[2, 4]`. Removing node 2
+    /// should then result in `[3, 4]`.
+    ///
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(0.0, -1.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(-1.0, -1.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(4, Vec3::new(0.0, -2.0, 0.0), 1.0, 1));
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(1, 3);
+    /// nav_graph.connect_points(2, 4);
+    /// nav_graph.connect_points(3, 4);
+    ///
+    /// assert_eq!(nav_graph.find_path(1, 4).unwrap()[..], [1, 2, 4]);
+    /// nav_graph.remove_point(2);
+    /// assert_eq!(nav_graph.find_path(1, 4).unwrap()[..], [1, 3, 4]);
+    /// ```
+    ///
+    pub fn remove_point(&mut self, id: u32) {
+        if let Some(point) = self.points.remove(&id) {
+            self.spatial.remove(id, point.location);
+            for connection in &point.connections {
+                self.points.entry(*connection).and_modify(|b| {
+                    b.connections.remove(&point.id);
+                    b.costs.remove(&point.id);
+                });
+            }
+            if point.current_occupancy > 0 {
+                self.revocation_candidates.insert(id);
+            }
+            self.clear_availability(id);
+            self.point_reservations.remove(&id);
+            self.stock.remove(&id);
+            self.owners.remove(&id);
+            self.destination_quotas.remove(&id);
+            self.churn.remove(&id);
+            if !self.traversals.is_empty() {
+                self.traversals
+                    .retain(|(from, to), _| *from != id && *to != id);
+            }
+            self.id_freelist.freed(id);
+            self.topology_changed();
+        }
+    }
+
+    /// Checks whether the specified point has capacity for more occupants.
+    ///
+    /// Also returns false if the specified point doesn't exist.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(0.0, 0.0, 0.0), 1.0, 2));
+    ///
+    /// assert!(nav_graph.can_occupy(1));
+    /// // Can't occupy points which don't exist.
+    /// assert!(!nav_graph.can_occupy(3));
+    ///
+    /// // The NavPoint has a max_occupancy of 1, so this will mark it as full.
+    /// nav_graph.occupy(1);
+    /// assert!(!nav_graph.can_occupy(1));
+    ///
+    /// nav_graph.occupy(2);
+    ///
+    /// // NavPoint 2 has a max_occupancy of 2, so this should still return true with 1 occupant.
+    /// assert!(nav_graph.can_occupy(2));
+    /// ```
+    pub fn can_occupy(&self, id: u32) -> bool {
+        self.can_occupy_weighted(id, 1)
+    }
+
+    /// Like [`NavGraph::can_occupy`], but for an occupant taking up `weight` slots of the
+    /// point's max_occupancy, such as a large creature.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 3));
+    ///
+    /// assert!(nav_graph.occupy_weighted(1, 2));
+    /// assert!(nav_graph.can_occupy_weighted(1, 1));
+    /// assert!(!nav_graph.can_occupy_weighted(1, 2));
+    /// ```
+    pub fn can_occupy_weighted(&self, id: u32, weight: u32) -> bool {
+        self.points
+            .get(&id)
+            .map(|p| p.can_occupy_weighted(weight))
+            .unwrap_or(false)
+    }
+
+    /// Attempts to increase the occupant count for a node and returns whether it succeeded.
+    ///
+    /// Also returns false if the specified NavPoint doesn't exist.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    ///
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    ///
+    /// // The NavPoint has a max_occupancy of 1, so the first call should succeed and the second
+    /// // should fail.
+    /// assert!(nav_graph.occupy(1));
+    /// assert!(!nav_graph.occupy(1));
+    ///
+    /// // This should return false for NavPoints that aren't in the graph.
+    /// assert!(!nav_graph.occupy(1));
+    ///
+    /// ```
+    pub fn occupy(&mut self, id: u32) -> bool {
+        self.occupy_weighted(id, 1)
+    }
+
+    /// Like [`NavGraph::occupy`], but takes up `weight` slots at once. Fails unless all of them
+    /// are free.
+    pub fn occupy_weighted(&mut self, id: u32, weight: u32) -> bool {
+        let mut occupied = false;
+        self.points.entry(id).and_modify(|p| {
+            occupied = p.occupy_weighted(weight);
+        });
+        occupied
+    }
+
+    /// Adds an occupant taking up `weight` slots to the specified [`NavPoint`] even if it's
+    /// already full.
+    pub(crate) fn occupy_unchecked(&mut self, id: u32, weight: u32) {
+        self.points.entry(id).and_modify(|p| {
+            p.current_occupancy += weight;
+        });
+    }
+
+    /// Reduces the current_occupancy of the specified [`NavPoint`] by 1, to a minimum of zero.
+    ///
+    /// Has no effect on [`NavPoint`]s which are not in the graph or already have 0 occupants.
+    ///
+    /// If a [`NavPoint`] is at max_occupancy, calling this will allow it to be used in pathing
+    /// again.
+    pub fn unoccupy(&mut self, id: u32) {
+        self.unoccupy_weighted(id, 1);
+    }
+
+    /// Releases `weight` slots of the specified [`NavPoint`], to a minimum of zero occupants.
+    pub fn unoccupy_weighted(&mut self, id: u32, weight: u32) {
+        self.points.entry(id).and_modify(|p| {
+            p.unoccupy_weighted(weight);
+        });
+    }
+
+    /// Returns the ID of the [`NavPoint`] closest to `position`, or `None` if the graph is empty.
+    /// Of several equally close points, the one with the lowest ID is returned.
+    ///
+    /// Points are bucketed into a grid as they're added and moved, so this only looks at the
+    /// points near `position` rather than scanning the whole graph. The grid's cells are sized
+    /// from the [`NavGraph::capacity_hint`].
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+    ///
+    /// assert_eq!(nav_graph.nearest_point(Vec3::new(0.75, 0.5, 0.0)), Some(2));
+    /// ```
+    pub fn nearest_point(&self, position: Vec3) -> Option<u32> {
+        self.spatial.nearest(position)
+    }
+
+    /// Returns the IDs of the [`NavPoint`]s within `radius` of `position`, closest first. Of
+    /// several equally close points, the one with the lowest ID comes first.
+    ///
+    /// Like [`NavGraph::nearest_point`], this only looks at the grid cells the radius overlaps.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(5.0, 0.0, 0.0), 1.0, 1));
+    ///
+    /// assert_eq!(nav_graph.points_within_radius(Vec3::new(0.75, 0.0, 0.0), 2.0), [2, 1]);
+    /// ```
+    pub fn points_within_radius(&self, position: Vec3, radius: f32) -> Vec<u32> {
+        self.spatial.within(position, radius)
+    }
+
+    /// Rebuckets every point into the spatial grid behind [`NavGraph::nearest_point`] and
+    /// [`NavGraph::points_within_radius`].
+    ///
+    /// The grid is kept in sync with every edit, but the area it spans only grows, so lookups
+    /// far from the remaining points get slower after large parts of the graph are removed or
+    /// moved away. Rebuilding after such bulk edits shrinks it back to the points it holds.
+    pub fn rebuild_spatial_index(&mut self) {
+        self.spatial
+            .rebuild(self.points.values().map(|point| (point.id, point.location)));
+        let ids: Vec<u32> = self.points.keys().copied().collect();
+        for id in ids {
+            self.note_connections(id);
+        }
+    }
+
+    /// Picks the [`NavPoint`] closest to `position` out of the points reachable from `from`.
+    ///
+    /// This is intended for resolving world-space positions (e.g. from a mouse click) into
+    /// destinations. Unlike a raw nearest-point lookup, points which can't currently be reached
+    /// from `from` are ignored, so a position just beyond a wall resolves to a point on the
+    /// traveler's side of it.
+    ///
+    /// Returns `None` if `from` isn't in the graph.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+    /// // Point 3 is closest to the click, but is on the other side of a wall.
+    /// nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(2.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.connect_points(1, 2);
+    ///
+    /// assert_eq!(nav_graph.pick_reachable_point(1, Vec3::new(1.9, 0.0, 0.0)), Some(2));
+    /// ```
+    pub fn pick_reachable_point(&self, from: u32, position: Vec3) -> Option<u32> {
+        let start = self.points.get(&from)?;
+
+        let mut best = (from, start.location.distance_squared(position));
+        let mut visited = HashSet::<u32>::with_capacity(self.points.len());
+        let mut queue = VecDeque::new();
+        visited.insert(from);
+        queue.push_back(start);
+
+        while let Some(current) = queue.pop_front() {
+            for neighbor_id in &current.connections {
+                if !visited.insert(*neighbor_id) {
+                    continue;
+                }
+                let neighbor = &self.points[neighbor_id];
+                if !neighbor.can_occupy() {
+                    continue;
+                }
+
+                let dist_squared = neighbor.location.distance_squared(position);
+                if dist_squared < best.1 {
+                    best = (neighbor.id, dist_squared);
+                }
+                queue.push_back(neighbor);
+            }
+        }
+
+        Some(best.0)
+    }
+
+    /// Sets the heuristic searches use unless their [`SearchOptions::heuristic`] says otherwise.
+    ///
+    /// Defaults to [`Heuristic::Euclidean`]. Landmarks take precedence while they're up to date,
+    /// see [`NavGraph::build_landmarks`].
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_navigator::{GridConnectivity, Heuristic, NavGraph};
+    /// let (mut nav_graph, _) = NavGraph::<()>::from_grid(20, 20, 1.0, GridConnectivity::Eight);
+    /// nav_graph.set_heuristic(Heuristic::Octile);
+    /// assert_eq!(nav_graph.heuristic(), Heuristic::Octile);
+    ///
+    /// let path = nav_graph.find_path(1, 400).unwrap();
+    /// assert_eq!(path.len(), 20);
+    /// ```
+    pub fn set_heuristic(&mut self, heuristic: Heuristic) {
+        self.heuristic = heuristic;
+    }
+
+    pub fn heuristic(&self) -> Heuristic {
+        self.heuristic
+    }
+
+    /// Sets how the speed modifiers of each connection's points combine. Defaults to
+    /// [`EdgeSpeedModel::To`].
+    ///
+    /// Searches cost connections by the effective speed modifier, and travelers move along them
+    /// at the model's speed modifier for where they are, so paths are planned by how long they
+    /// actually take.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{EdgeSpeedModel, NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(2.0, 0.0, 0.0), 0.25, 1));
+    /// nav_graph.connect_points(1, 2);
+    ///
+    /// // Leaving the road into the swamp.
+    /// assert_eq!(nav_graph.path_cost(&[1, 2]), Some(1600));
+    /// nav_graph.set_edge_speed_model(EdgeSpeedModel::From);
+    /// assert_eq!(nav_graph.path_cost(&[1, 2]), Some(400));
+    /// nav_graph.set_edge_speed_model(EdgeSpeedModel::Average);
+    /// assert_eq!(nav_graph.path_cost(&[1, 2]), Some(640));
+    /// ```
+    pub fn set_edge_speed_model(&mut self, edge_speed_model: EdgeSpeedModel) {
+        if self.edge_speed_model == edge_speed_model {
+            return;
+        }
+        self.edge_speed_model = edge_speed_model;
+        let ids: Vec<u32> = self.points.keys().copied().collect();
+        for id in ids {
+            self.refresh_connection_costs(id);
+        }
+        self.topology_changed();
+    }
+
+    pub fn edge_speed_model(&self) -> EdgeSpeedModel {
+        self.edge_speed_model
+    }
+
+    /// The speed modifier the connection from `a` to `b` averages out to under the graph's
+    /// [`EdgeSpeedModel`], or `None` if either point doesn't exist.
+    pub fn edge_speed_modifier(&self, a: u32, b: u32) -> Option<f32> {
+        let (from, to) = (self.points.get(&a)?, self.points.get(&b)?);
+        Some(
+            self.edge_speed_model
+                .effective(from.speed_modifier, to.speed_modifier),
+        )
+    }
+
+    /// The graph's heuristic estimate of the cost from `a` to `b`.
+    #[inline(always)]
+    pub(crate) fn h_func(&self, a: &u32, b: &u32) -> u32 {
+        if let (Some(a_node), Some(b_node)) = (self.points.get(a), self.points.get(b)) {
+            self.estimate(self.heuristic, a_node, b_node)
+        } else {
+            u32::MAX
+        }
+    }
+
+    /// Estimates the cost from `from` to `to` with `heuristic`.
+    #[inline(always)]
+    fn estimate(&self, heuristic: Heuristic, from: &NavPoint, to: &NavPoint) -> u32 {
+        estimate(heuristic, from, to, self.spatial.min_cost_per_unit())
+    }
+
+    /// The cost of moving along the connection from `a` to `b` by distance and speed alone.
+    #[inline(always)]
+    fn distance_cost(&self, a: u32, b: u32) -> u32 {
+        if let (Some(a_node), Some(b_node)) = (self.points.get(&a), self.points.get(&b)) {
+            let speed_modifier = self
+                .edge_speed_model
+                .effective(a_node.speed_modifier, b_node.speed_modifier);
+            travel_cost(a_node.location, b_node.location, speed_modifier)
+        } else {
+            u32::MAX
+        }
+    }
+
+    /// The cost of moving along the connection from `a` to `b`, from the cache if possible.
+    #[inline(always)]
+    pub(crate) fn connection_cost(&self, a: u32, b: u32) -> u32 {
+        match self.points.get(&a).and_then(|point| point.costs.get(&b)) {
+            Some(cost) => *cost,
+            None => self.distance_cost(a, b),
+        }
+    }
+
+    fn cache_connection_cost(&mut self, a: u32, b: u32) {
+        let cost = match self.edge(a, b) {
+            Some(NavEdge {
+                weight: Some(weight),
+                ..
+            }) => *weight,
+            Some(NavEdge {
+                duration: Some(duration),
+                ..
+            }) => duration_cost(*duration, 1.0),
+            _ => self.distance_cost(a, b),
+        };
+        if let Some(point) = self.points.get_mut(&a) {
+            point.costs.insert(b, cost);
+        }
+        if let (Some(a), Some(b)) = (self.points.get(&a), self.points.get(&b)) {
+            self.spatial.note_connection(
+                a.location.distance(b.location),
+                a.speed_modifier.max(b.speed_modifier),
+                cost,
+            );
+        }
+    }
+
+    /// Notes the connections from `id` to points already in the graph in the spatial grid.
+    fn note_connections(&mut self, id: u32) {
+        let Some(point) = self.points.get(&id) else {
+            return;
+        };
+        let connections: Vec<(f32, f32, u32)> = point
+            .connections
+            .iter()
+            .filter_map(|connection| {
+                let other = self.points.get(connection)?;
+                Some((
+                    point.location.distance(other.location),
+                    point.speed_modifier.max(other.speed_modifier),
+                    self.connection_cost(id, *connection),
+                ))
+            })
+            .collect();
+        for (length, speed_modifier, cost) in connections {
+            self.spatial.note_connection(length, speed_modifier, cost);
+        }
+    }
+
+    /// Recomputes the cached costs of every connection to and from `id`, after it's been moved
+    /// or had its speed modifier changed.
+    fn refresh_connection_costs(&mut self, id: u32) {
+        let Some(point) = self.points.get(&id) else {
+            return;
+        };
+        let connections: Vec<u32> = point.connections.iter().copied().collect();
+        for connection in connections {
+            self.cache_connection_cost(id, connection);
+            self.cache_connection_cost(connection, id);
+        }
+    }
+
+    /// Computes a path from between two [`NavPoint`]s based on their IDs.
+    ///
+    /// If a valid path exists, a [`Vec`] of node IDs is returned.
+    ///
+    /// The path returned is not guaranteed to continue being valid for the duration of travel
+    /// across it, so validity of each node should be checked before moving. If a particular
+    /// [`NavPoint`] is blocked by the time it is reached, one could wait or simply recompute a
+    /// new path from the current position.
+    ///
+    /// The occupancy of a tile is taken into account when computing the path initially. For long
+    /// paths or when multiple parties are moving at during the travel duration, this may result in a
+    /// suboptimal or odd pathing.
+    ///
+    /// A path from a point to itself is just that point, without running a full search.
+    pub fn find_path(&self, a: u32, b: u32) -> Option<Vec<u32>> {
+        self.find_path_with(a, b, &SearchOptions::default())
+    }
+
+    /// Computes a path from `start` visiting each of `waypoints` in order, by joining the paths
+    /// between consecutive stops. Returns `None` if any of them can't be reached from the last.
+    ///
+    /// Each stretch is planned on its own, so the route is the cheapest way to visit the stops in
+    /// the given order, not the cheapest order to visit them in.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// for i in 1..=4 {
+    ///     nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    /// nav_graph.connect_points(3, 4);
+    ///
+    /// assert_eq!(nav_graph.find_route(2, &[4, 1]).unwrap()[..], [2, 3, 4, 3, 2, 1]);
+    /// ```
+    pub fn find_route(&self, start: u32, waypoints: &[u32]) -> Option<Vec<u32>> {
+        if !self.points.contains_key(&start) {
+            return None;
+        }
+        let mut route = vec![start];
+        for waypoint in waypoints {
+            let path = self.find_path(*route.last().unwrap(), *waypoint)?;
+            route.extend(path.into_iter().skip(1));
+        }
+        Some(route)
+    }
+
+    /// Like [`NavGraph::find_path`], giving up after expanding `max_expanded_nodes` points, so
+    /// a single search for an unreachable destination can't stall a frame on a large graph.
+    ///
+    /// See [`SearchOptions::max_expanded`].
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_navigator::{GridConnectivity, NavGraph};
+    /// let (mut nav_graph, ids) = NavGraph::<()>::from_grid(100, 100, 1.0, GridConnectivity::Four);
+    /// let (start, walled_off) = (ids.id(0, 0).unwrap(), ids.id(99, 99).unwrap());
+    /// nav_graph.remove_point(ids.id(98, 99).unwrap());
+    /// nav_graph.remove_point(ids.id(99, 98).unwrap());
+    ///
+    /// assert_eq!(nav_graph.find_path_bounded(start, walled_off, 500), None);
+    /// assert!(nav_graph.find_path_bounded(start, ids.id(3, 3).unwrap(), 500).is_some());
+    /// ```
+    pub fn find_path_bounded(&self, a: u32, b: u32, max_expanded_nodes: usize) -> Option<Vec<u32>> {
+        self.find_path_with(
+            a,
+            b,
+            &SearchOptions::new().with_max_expanded(max_expanded_nodes),
+        )
+    }
+
+    /// Computes a path between two [`NavPoint`]s, subject to the constraints in `options`.
+    ///
+    /// See [`NavGraph::find_path`] for details on the path returned.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint, SearchOptions};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(0.0, -1.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(-1.0, -1.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(4, Vec3::new(0.0, -2.0, 0.0), 1.0, 1));
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(1, 3);
+    /// nav_graph.connect_points(2, 4);
+    /// nav_graph.connect_points(3, 4);
+    ///
+    /// let options = SearchOptions::new().excluding([2]);
+    /// assert_eq!(nav_graph.find_path_with(1, 4, &options).unwrap()[..], [1, 3, 4]);
+    /// ```
+    pub fn find_path_with(&self, a: u32, b: u32, options: &SearchOptions) -> Option<Vec<u32>> {
+        self.find_path_result(a, b, options)
+            .map(PathResult::into_path)
+    }
+
+    /// Computes a path like [`NavGraph::find_path`] for an agent with the traversal flags in
+    /// `traversal_mask`. See [`NavPoint::flags`].
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_navigator::{GridConnectivity, NavGraph};
+    /// const WATER: u32 = 1 << 0;
+    /// const AIR: u32 = 1 << 1;
+    /// let (mut nav_graph, _) = NavGraph::<()>::from_grid(3, 1, 1.0, GridConnectivity::Four);
+    /// nav_graph.modify(2, |point| point.set_flags(WATER | AIR));
+    ///
+    /// assert_eq!(nav_graph.find_path(1, 3), None);
+    /// assert_eq!(nav_graph.find_path_masked(1, 3, AIR), Some(vec![1, 2, 3]));
+    /// ```
+    pub fn find_path_masked(&self, a: u32, b: u32, traversal_mask: u32) -> Option<Vec<u32>> {
+        self.find_path_with(
+            a,
+            b,
+            &SearchOptions::new().with_traversal_mask(traversal_mask),
+        )
+    }
+
+    /// Computes a path like [`NavGraph::find_path`] for a traveler of radius `size`, only
+    /// passing through points with at least that much clearance. See [`NavPoint::clearance`].
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_navigator::{GridConnectivity, NavGraph};
+    /// let (mut nav_graph, ids) = NavGraph::<()>::from_grid(3, 2, 1.0, GridConnectivity::Four);
+    /// // A narrow gap along the bottom row.
+    /// nav_graph.modify(2, |point| point.set_clearance(0.5));
+    ///
+    /// assert_eq!(nav_graph.find_path_sized(1, 3, 0.4), Some(vec![1, 2, 3]));
+    /// assert_eq!(nav_graph.find_path_sized(1, 3, 1.0), Some(vec![1, 4, 5, 6, 3]));
+    /// ```
+    pub fn find_path_sized(&self, a: u32, b: u32, size: f32) -> Option<Vec<u32>> {
+        self.find_path_with(a, b, &SearchOptions::new().with_agent_size(size))
+    }
+
+    /// Like [`NavGraph::find_path_with`], but tells complete paths apart from the partial ones
+    /// returned under [`PathPolicy::PartialAllowed`].
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint, PathPolicy, PathResult, SearchOptions};
+    /// let mut nav_graph = NavGraph::new();
+    /// for id in 1..=4 {
+    ///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    ///
+    /// let options = SearchOptions::new().with_path_policy(PathPolicy::PartialAllowed);
+    /// assert_eq!(
+    ///     nav_graph.find_path_result(1, 4, &options),
+    ///     Some(PathResult::Partial(vec![1, 2, 3]))
+    /// );
+    /// assert_eq!(nav_graph.find_path_result(1, 4, &SearchOptions::default()), None);
+    /// ```
+    pub fn find_path_result(&self, a: u32, b: u32, options: &SearchOptions) -> Option<PathResult> {
+        self.find_path_counted(a, b, options, &mut 0)
+    }
+
+    /// Like [`NavGraph::find_path_result`], adding the number of points expanded to `expanded`.
+    pub(crate) fn find_path_counted(
+        &self,
+        a: u32,
+        b: u32,
+        options: &SearchOptions,
+        expanded: &mut usize,
+    ) -> Option<PathResult> {
+        self.observe(
+            a,
+            b,
+            || self.search_recorded(a, b, options, None, None, expanded),
+            |(path, cost)| (path.path().len(), Some(*cost)),
+        )
+        .map(|(path, _)| path)
+    }
+
+    /// Runs `compute` as a single path computation from `a` to `b`, reporting it to the
+    /// telemetry hooks. `measure` gives the length and cost of the path found.
+    pub(crate) fn observe<R>(
+        &self,
+        a: u32,
+        b: u32,
+        compute: impl FnOnce() -> Option<R>,
+        measure: impl FnOnce(&R) -> (usize, Option<u32>),
+    ) -> Option<R> {
+        if self.telemetry_hooks.is_empty() {
+            return compute();
+        }
+
+        let start = Instant::now();
+        let result = compute();
+        let duration = start.elapsed();
+        let (path_len, cost) = match result.as_ref().map(measure) {
+            Some((path_len, cost)) => (Some(path_len), cost),
+            None => (None, None),
+        };
+        self.telemetry_hooks.fire(&PathTelemetry {
+            origin: a,
+            destination: b,
+            path_len,
+            cost,
+            duration,
         });
-        occupied
+        result
     }
 
-    /// Reduces the current_occupancy of the specified [`NavPoint`] by 1, to a minimum of zero.
+    /// Computes a path like [`NavGraph::find_path_with`], reusing the result of a previous search
+    /// stored in `warm_start` where possible.
     ///
-    /// Has no effect on [`NavPoint`]s which are not in the graph or already have 0 occupants.
+    /// If the graph's topology hasn't changed since the previous search, the previous path passes
+    /// through `a`, every point on the rest of it is still passable and none of the points the
+    /// search couldn't pass through has freed up, the remainder of that path is returned without
+    /// searching.
     ///
-    /// If a [`NavPoint`] is at max_occupancy, calling this will allow it to be used in pathing
-    /// again.
-    pub fn unoccupy(&mut self, id: u32) {
-        self.points.entry(id).and_modify(|p| {
-            p.unoccupy();
-        });
+    /// Otherwise, if `a` is one of the points the previous search expanded, the search carries on
+    /// from the cost of reaching each of those through `a`, dropping those reached through a
+    /// point which has since become impassable, and only expands points again where the
+    /// occupancy changes open up or close off a way. This makes repeated re-planning along a long
+    /// route (see [`PathBehavior::ProgressiveRecompute`](crate::PathBehavior::ProgressiveRecompute))
+    /// cheap while only occupancy changes. Searches with a turn penalty, a node limit, a goal
+    /// tolerance or a [`CostProvider`](crate::CostProvider), whose costs depend on more than the
+    /// point reached, run in full, as does any search after the topology changes.
+    ///
+    /// The paths found are as cheap as a full search would find, unless `options` include a turn
+    /// penalty. `warm_start` assumes the same `options` each time, so
+    /// [`clear`](PathWarmStart::clear) it after changing them.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint, PathWarmStart, SearchOptions};
+    /// let mut nav_graph = NavGraph::new();
+    /// for (id, x, y) in [(1, 0.0, 0.0), (2, 1.0, 0.0), (3, 2.0, 0.0), (4, 1.0, 1.0)] {
+    ///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 1));
+    /// }
+    /// for (a, b) in [(1, 2), (2, 3), (1, 4), (4, 3)] {
+    ///     nav_graph.connect_points(a, b);
+    /// }
+    /// let options = SearchOptions::new();
+    /// let mut warm_start = PathWarmStart::new();
+    ///
+    /// // A crowd at 2 makes for a detour through 4...
+    /// nav_graph.occupy(2);
+    /// let path = nav_graph.find_path_warm(1, 3, &options, &mut warm_start);
+    /// assert_eq!(path.unwrap()[..], [1, 4, 3]);
+    /// // ...which is kept to while the crowd stays...
+    /// let path = nav_graph.find_path_warm(4, 3, &options, &mut warm_start);
+    /// assert_eq!(path.unwrap()[..], [4, 3]);
+    /// assert_eq!(warm_start.reused(), 1);
+    /// // ...but not once it has cleared, when the search carries on from where the last one left
+    /// // off.
+    /// nav_graph.unoccupy(2);
+    /// let path = nav_graph.find_path_warm(1, 3, &options, &mut warm_start);
+    /// assert_eq!(path.unwrap()[..], [1, 2, 3]);
+    /// assert_eq!(warm_start.resumed(), 1);
+    /// ```
+    pub fn find_path_warm(
+        &self,
+        a: u32,
+        b: u32,
+        options: &SearchOptions,
+        warm_start: &mut PathWarmStart,
+    ) -> Option<Vec<u32>> {
+        warm_start.find_path_counted(self, a, b, options, &mut 0)
+    }
+
+    /// Registers a hook which is called after every path computation on this graph.
+    ///
+    /// This can be used to forward navigation telemetry (timings, path sizes, failures) into
+    /// analytics without wrapping each call site. Paths answered from a
+    /// [`PathCache`](crate::PathCache) or a [`PathWarmStart`] are reported like any other, as are
+    /// cooperative and recorded searches. The extra searches run to explain a failure in
+    /// [`NavGraph::try_find_path`] or to audit one in [`NavGraph::find_path_checked`] aren't.
+    ///
+    /// ## Example
+    /// ```
+    /// # use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.connect_points(1, 2);
+    ///
+    /// let failures = Arc::new(AtomicUsize::new(0));
+    /// let counter = failures.clone();
+    /// nav_graph.add_telemetry_hook(move |telemetry| {
+    ///     if telemetry.path_len.is_none() {
+    ///         counter.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// });
+    ///
+    /// nav_graph.find_path(1, 2);
+    /// nav_graph.find_path(1, 3);
+    /// assert_eq!(failures.load(Ordering::Relaxed), 1);
+    /// ```
+    pub fn add_telemetry_hook(&mut self, hook: impl Fn(&PathTelemetry) + Send + Sync + 'static) {
+        self.telemetry_hooks.add(hook);
+    }
+
+    /// Removes all hooks registered with [`NavGraph::add_telemetry_hook`].
+    pub fn clear_telemetry_hooks(&mut self) {
+        self.telemetry_hooks.clear();
+    }
+
+    /// Returns the total cost of traveling along `path`, as used when searching.
+    ///
+    /// Returns `None` if any consecutive [`NavPoint`]s in the path aren't connected.
+    pub fn path_cost(&self, path: &[u32]) -> Option<u32> {
+        path.windows(2).try_fold(0_u32, |cost, pair| {
+            let from = self.points.get(&pair[0])?;
+            if !from.connections.contains(&pair[1]) {
+                return None;
+            }
+            Some(cost.saturating_add(self.connection_cost(pair[0], pair[1])))
+        })
+    }
+
+    /// Returns the cost of the cheapest path from `a` to `b` if it's at most `max_cost`, or
+    /// `None` if there's no such path.
+    ///
+    /// The search gives up once everything cheaper than `max_cost` has been looked at, so it
+    /// stays cheap enough to run every frame for small budgets, e.g. to muffle sounds by how far
+    /// they'd have to travel around walls. Occupancy is ignored, as for
+    /// [`NavGraph::distance_map`].
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// for i in 1..=3 {
+    ///     nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    ///
+    /// // Each connection costs 100.
+    /// assert_eq!(nav_graph.path_cost_or_none(1, 3, 200), Some(200));
+    /// assert_eq!(nav_graph.path_cost_or_none(1, 3, 199), None);
+    /// ```
+    pub fn path_cost_or_none(&self, a: u32, b: u32, max_cost: u32) -> Option<u32> {
+        self.bounded_costs(a, max_cost, None, Some(b))
+            .get(&b)
+            .copied()
+    }
+
+    /// Returns every [`NavPoint`] reachable from `start` for a total cost of at most `max_cost`,
+    /// along with the cost of reaching it, cheapest first.
+    ///
+    /// Costs are measured the same way as [`NavGraph::path_cost`], and full points block the way
+    /// just as they do for [`NavGraph::find_path`]. `start` itself is always included at a cost of
+    /// 0, as long as it exists. This suits move-range highlighting and "what can this unit reach
+    /// this turn" queries.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// for i in 1..=4 {
+    ///     nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    /// nav_graph.connect_points(3, 4);
+    ///
+    /// // Each connection costs 100.
+    /// assert_eq!(nav_graph.reachable_within(1, 200), [(1, 0), (2, 100), (3, 200)]);
+    ///
+    /// nav_graph.occupy(2);
+    /// assert_eq!(nav_graph.reachable_within(1, 200), [(1, 0)]);
+    /// ```
+    pub fn reachable_within(&self, start: u32, max_cost: u32) -> Vec<(u32, u32)> {
+        let mut reachable: Vec<(u32, u32)> = self
+            .cost_map(start, max_cost, Some(&SearchOptions::default()))
+            .into_iter()
+            .collect();
+        reachable.sort_unstable_by_key(|(id, cost)| (*cost, *id));
+        reachable
+    }
+
+    /// Returns the cost of reaching every point reachable from `source`, including `source`
+    /// itself at a cost of 0, or an empty map if it doesn't exist.
+    ///
+    /// Unlike [`NavGraph::reachable_within`], occupancy is ignored, so this describes the layout
+    /// of the graph, for queries like scoring spawn points by how far they are from a base.
+    /// Costs are measured the same way as [`NavGraph::path_cost`].
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// for i in 1..=4 {
+    ///     nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    /// nav_graph.occupy(2);
+    ///
+    /// let distances = nav_graph.distance_map(1);
+    /// assert_eq!((distances[&1], distances[&2], distances[&3]), (0, 100, 200));
+    /// assert!(!distances.contains_key(&4));
+    /// ```
+    pub fn distance_map(&self, source: u32) -> HashMap<u32, u32> {
+        self.cost_map(source, u32::MAX, None)
+    }
+
+    /// Returns the IDs of all points at most `hops` connections away from `id`, including `id`.
+    fn points_within_hops(&self, id: u32, hops: u32) -> HashSet<u32> {
+        let mut found = HashSet::new();
+        let mut frontier = vec![id];
+        found.insert(id);
+        for _ in 0..hops {
+            let mut next_frontier = Vec::new();
+            for current in frontier {
+                if let Some(point) = self.points.get(&current) {
+                    for connection in &point.connections {
+                        if found.insert(*connection) {
+                            next_frontier.push(*connection);
+                        }
+                    }
+                }
+            }
+            frontier = next_frontier;
+        }
+        found
     }
 
-    /// The heuristic function for estimating [`NavPoint`] path cost.
+    /// The cost of moving from `a` to `b` in a search with `options`.
     #[inline(always)]
-    fn h_func(&self, a: &u32, b: &u32) -> u32 {
-        if let (Some(a_node), Some(b_node)) = (self.points.get(a), self.points.get(b)) {
-            (a_node.location.distance_squared(b_node.location) / b_node.speed_modifier * 100.0)
-                as u32
-        } else {
-            u32::MAX
+    pub(crate) fn edge_cost(&self, a: u32, b: u32, options: &SearchOptions) -> u32 {
+        let mut cost = match (options.schedule, self.edge(a, b)) {
+            (
+                Some(schedule),
+                Some(NavEdge {
+                    weight: None,
+                    duration: Some(duration),
+                    ..
+                }),
+            ) => duration_cost(*duration, schedule.speed),
+            _ => self.connection_cost(a, b),
+        };
+        if let Some(cost_provider) = &options.cost_provider {
+            if let (Some(from), Some(to)) = (self.points.get(&a), self.points.get(&b)) {
+                cost = cost_provider.cost(from, to, cost);
+            }
+        }
+        if let (Some(area_costs), Some(from), Some(to)) = (
+            &options.area_costs,
+            self.points.get(&a),
+            self.points.get(&b),
+        ) {
+            let multiplier = area_costs.edge_multiplier(self.edge_speed_model, from.area, to.area);
+            cost = (cost as f32 * multiplier) as u32;
+        }
+        if let TerritoryAccess::Penalize(factor) = options.territory {
+            if self.is_hostile_to(b, options.faction) {
+                cost = (cost as f32 * factor) as u32;
+            }
+        }
+        if let (true, Some(to)) = (options.congestion_cost > 0.0, self.points.get(&b)) {
+            cost = (cost as f32 * options.congestion_factor(to)) as u32;
+        }
+        match options.noise {
+            Some(noise) if noise.magnitude > 0.0 => (cost as f32 * noise.factor(a, b)) as u32,
+            _ => cost,
         }
     }
 
-    /// Computes a path from between two [`NavPoint`]s based on their IDs.
-    ///
-    /// If a valid path exists, a [`Vec`] of node IDs is returned.
+    /// Whether a search with `options` may path through `point`.
+    #[inline(always)]
+    pub(crate) fn is_passable(&self, point: &NavPoint, options: &SearchOptions) -> bool {
+        let weight = options.occupancy_weight.max(1);
+        (options.ignore_occupancy
+            || point.can_occupy_weighted(weight)
+            || (options.congestion_cost > 0.0 && point.max_occupancy >= weight))
+            && !options.excluded.contains(&point.id)
+            && !self.is_reserved_against(point.id, options.group)
+            && self.may_enter(point.id, options.faction, options.territory)
+            && (point.flags == 0 || point.flags & options.traversal_mask != 0)
+            && point.clearance >= options.agent_size
+            && options
+                .allowed_regions
+                .as_ref()
+                .is_none_or(|regions| regions.contains(&point.region))
+    }
+
+    /// Runs Dijkstra's algorithm from `start`, returning the cost of reaching every point within
+    /// `max_cost`.
     ///
-    /// The path returned is not guaranteed to continue being valid for the duration of travel
-    /// across it, so validity of each node should be checked before moving. If a particular
-    /// [`NavPoint`] is blocked by the time it is reached, one could wait or simply recompute a
-    /// new path from the current position.
+    /// Without `options`, occupancy is ignored, so this describes the graph's layout rather than
+    /// what's currently passable.
+    pub(crate) fn cost_map(
+        &self,
+        start: u32,
+        max_cost: u32,
+        options: Option<&SearchOptions>,
+    ) -> HashMap<u32, u32> {
+        self.bounded_costs(start, max_cost, options, None)
+    }
+
+    /// Like [`NavGraph::cost_map`], stopping as soon as the cost of reaching `target` is known.
+    fn bounded_costs(
+        &self,
+        start: u32,
+        max_cost: u32,
+        options: Option<&SearchOptions>,
+        target: Option<u32>,
+    ) -> HashMap<u32, u32> {
+        let mut costs = HashMap::new();
+        if !self.points.contains_key(&start) {
+            return costs;
+        }
+
+        let mut open_set = BinaryHeap::new();
+        costs.insert(start, 0);
+        open_set.push(Reverse(PathNode {
+            id: start,
+            depth: 0,
+            f: 0,
+        }));
+        while let Some(Reverse(current)) = open_set.pop() {
+            if current.f > costs[&current.id] {
+                continue;
+            }
+            if Some(current.id) == target {
+                break;
+            }
+            for neighbor_id in &self.points[&current.id].connections {
+                let step = match options {
+                    Some(options) if !self.is_passable(&self.points[neighbor_id], options) => {
+                        continue;
+                    }
+                    Some(options) => self.edge_cost(current.id, *neighbor_id, options),
+                    None => self.connection_cost(current.id, *neighbor_id),
+                };
+                let cost = current.f.saturating_add(step);
+                if cost <= max_cost && cost < *costs.get(neighbor_id).unwrap_or(&u32::MAX) {
+                    costs.insert(*neighbor_id, cost);
+                    open_set.push(Reverse(PathNode {
+                        id: *neighbor_id,
+                        depth: 0,
+                        f: cost,
+                    }));
+                }
+            }
+        }
+        costs
+    }
+
+    /// Runs A* between `a` and `b`, returning the path and its total cost.
+    pub(crate) fn search(
+        &self,
+        a: u32,
+        b: u32,
+        options: &SearchOptions,
+    ) -> Option<(PathResult, u32)> {
+        self.search_recorded(a, b, options, None, None, &mut 0)
+    }
+
+    /// Like [`NavGraph::search`], also pushing every point expanded onto `recording`, if given,
+    /// and adding the number of points expanded to `expanded`.
     ///
-    /// The occupancy of a tile is taken into account when computing the path initially. For long
-    /// paths or when multiple parties are moving at during the travel duration, this may result in a
-    /// suboptimal or odd pathing.
-    pub fn find_path(&self, a: u32, b: u32) -> Option<Vec<u32>> {
-        let mut cap_guess = 0_usize;
-        if let (Some(a_node), Some(b_node)) = (self.points.get(&a), self.points.get(&b)) {
-            // Straight line dist * 2 as a general estimate.
-            // This may over-allocate in some scenarios but accounts for a 15-20% reduction
-            // in computation time to keep from having to resize all of the collections frequently.
-            cap_guess = (a_node.location().distance(b_node.location()) * 2.0) as usize;
-        } else {
+    /// With a `tree` from a previous search starting from `a`, the search carries on from the
+    /// points it expanded instead of starting over. Either way, a search which reaches `b` stores
+    /// the points it expanded in `tree`.
+    pub(crate) fn search_recorded(
+        &self,
+        a: u32,
+        b: u32,
+        options: &SearchOptions,
+        mut recording: Option<&mut Vec<ExpandedNode>>,
+        mut tree: Option<&mut SearchTree>,
+        expanded: &mut usize,
+    ) -> Option<(PathResult, u32)> {
+        if options.excluded.contains(&a)
+            || (options.goal_tolerance == 0 && options.excluded.contains(&b))
+        {
+            return None;
+        }
+
+        let (a_node, b_node) = (self.points.get(&a)?, self.points.get(&b)?);
+
+        // Trivial queries don't need any of the search state below.
+        if options.max_nodes == Some(0) {
+            return None;
+        }
+        if a == b {
+            return Some((PathResult::Complete(vec![a]), 0));
+        }
+        if options.max_nodes == Some(1) {
             return None;
         }
+        // Straight line dist scaled by how densely the graph's points are packed, as measured
+        // by the spatial grid, or by the capacity hint for lack of that, as a general estimate.
+        // This may over-allocate in some scenarios but accounts for a 15-20% reduction
+        // in computation time to keep from having to resize all of the collections frequently.
+        let points_per_unit = self.spatial.points_per_unit().unwrap_or(self.capacity_hint);
+        let cap_guess = (a_node.location().distance(b_node.location()) * points_per_unit) as usize;
 
-        let mut search_ids = HashSet::<u32>::with_capacity(cap_guess);
         let mut open_set = BinaryHeap::with_capacity(cap_guess);
-        let mut came_from = HashMap::<u32, u32>::with_capacity(cap_guess);
-        let mut g_score = HashMap::<u32, u32>::with_capacity(cap_guess);
-        let mut f_score = HashMap::<u32, u32>::with_capacity(cap_guess);
+        let mut came_from = HashMap::<SearchKey, SearchKey>::with_capacity(cap_guess);
+        let mut g_score = HashMap::<SearchKey, u32>::with_capacity(cap_guess);
+        let mut f_score = HashMap::<SearchKey, u32>::with_capacity(cap_guess);
+
+        let goals = (options.goal_tolerance > 0)
+            .then(|| self.points_within_hops(b, options.goal_tolerance));
+
+        // Depths start from the origin counting as one point, only with a node limit.
+        let start = (a, options.max_nodes.map_or(0, |_| 1));
+        // The time each point is reached, only tracked when availability windows are checked.
+        let mut arrival = HashMap::<SearchKey, f64>::new();
+        if let Some(schedule) = &options.schedule {
+            arrival.insert(start, schedule.departure);
+        }
+        // The fewest points in any path to each point expanded so far, with a node limit. A point
+        // reached again by a longer path only costs more, so there's no need to expand it again.
+        let mut shallowest = HashMap::<u32, usize>::new();
+
+        // Without landmarks, heuristics for every passable neighbor of a point are computed
+        // together, see `batch_estimates`.
+        let mut neighbors = Vec::<&NavPoint>::new();
+        let mut heuristics = Vec::<u32>::new();
+
+        let expanded_before = *expanded;
+        // The expanded point closest to `b` in a straight line, for partial paths.
+        let mut closest = (a_node.location.distance_squared(b_node.location), start);
+        let partial = |closest: SearchKey,
+                       came_from: &HashMap<SearchKey, SearchKey>,
+                       g_score: &HashMap<SearchKey, u32>| {
+            (options.path_policy == PathPolicy::PartialAllowed).then(|| {
+                (
+                    PathResult::Partial(trace_path(came_from, start, closest)),
+                    g_score[&closest],
+                )
+            })
+        };
+        // Landmarks give a better estimate than the straight line, calibrated or not.
+        let landmarks = self
+            .landmarks
+            .is_current(self.topology_version)
+            .then(|| self.landmarks.target(b));
+        let heuristic = options.heuristic.unwrap_or(self.heuristic);
+        // Weighted by epsilon, and scaled down by the cheapest area multiplier, since those
+        // below 1.0 make connections cheaper than the heuristic assumes.
+        let h_weight = options.epsilon.unwrap_or(1.0)
+            * options
+                .area_costs
+                .as_ref()
+                .map_or(1.0, AreaCosts::min_multiplier);
+        let h_scale = self.heuristic_calibration.scale(a_node.region()) * h_weight;
+        let start_h = match &landmarks {
+            Some(landmarks) => scale_heuristic(landmarks.estimate(a), h_weight),
+            None => scale_heuristic(self.estimate(heuristic, a_node, b_node), h_scale),
+        };
+        let start_node = PathNode {
+            id: a,
+            depth: start.1,
+            f: start_h,
+        };
+        g_score.insert(start, 0);
+        f_score.insert(start, start_node.f);
 
-        let start_h = self.h_func(&a, &b);
-        let start_node = PathNode { id: a, f: start_h };
-        g_score.insert(a, 0);
-        f_score.insert(a, start_node.f);
-        search_ids.insert(start_node.id);
-        open_set.push(Reverse(start_node));
+        // Carrying on from a previous search, its expanded points are already settled, and the
+        // search picks up from the points next to them.
+        let seed = tree.as_deref().filter(|tree| {
+            tree.origin == a && tree.g_score.contains_key(&a) && options.max_nodes.is_none()
+        });
+        // The points expanded by the search, for `tree`, starting with any carried on from.
+        let mut closed = Vec::<SearchKey>::new();
+        if let Some(seed) = seed {
+            closed.extend(seed.g_score.keys().map(|id| (*id, 0)));
+            for (&id, &g) in &seed.g_score {
+                g_score.insert((id, 0), g);
+            }
+            for (&id, &previous) in &seed.came_from {
+                came_from.insert((id, 0), (previous, 0));
+            }
+            for &id in seed.g_score.keys() {
+                let key = (id, 0);
+                for neighbor in self.points[&id]
+                    .connections
+                    .iter()
+                    .filter(|neighbor_id| !seed.g_score.contains_key(*neighbor_id))
+                    .map(|neighbor_id| &self.points[neighbor_id])
+                    .filter(|neighbor| self.is_passable(neighbor, options))
+                {
+                    let neighbor_key = (neighbor.id, 0);
+                    let tentative_g_score =
+                        g_score[&key].saturating_add(self.edge_cost(id, neighbor.id, options));
+                    if tentative_g_score < *g_score.get(&neighbor_key).unwrap_or(&u32::MAX) {
+                        let h = match &landmarks {
+                            Some(landmarks) => {
+                                scale_heuristic(landmarks.estimate(neighbor.id), h_weight)
+                            }
+                            None => {
+                                scale_heuristic(self.estimate(heuristic, neighbor, b_node), h_scale)
+                            }
+                        };
+                        let f = tentative_g_score.saturating_add(h);
+                        came_from.insert(neighbor_key, key);
+                        g_score.insert(neighbor_key, tentative_g_score);
+                        f_score.insert(neighbor_key, f);
+                        open_set.push(Reverse(PathNode {
+                            id: neighbor.id,
+                            depth: 0,
+                            f,
+                        }));
+                    }
+                }
+            }
+        } else {
+            open_set.push(Reverse(start_node));
+        }
 
         while let Some(Reverse(current)) = open_set.pop() {
-            if current.id == b {
-                let mut total_path = VecDeque::with_capacity(cap_guess);
-                let mut prev = current.id;
-                while prev != a {
-                    total_path.push_front(prev);
-                    prev = came_from[&prev];
+            let key = current.key();
+            // Points are pushed again each time a cheaper way to them is found, leaving the
+            // older entries behind.
+            if current.f > f_score[&key] {
+                continue;
+            }
+            if options.max_nodes.is_some() {
+                match shallowest.get(&current.id) {
+                    Some(&depth) if depth <= current.depth => continue,
+                    _ => shallowest.insert(current.id, current.depth),
+                };
+            }
+            if let Some(recording) = recording.as_mut() {
+                recording.push(ExpandedNode {
+                    id: current.id,
+                    g: g_score[&key],
+                    f: current.f,
+                });
+            }
+            let reached_goal = match &goals {
+                Some(goals) => goals.contains(&current.id),
+                None => current.id == b,
+            };
+            if reached_goal {
+                if let Some(tree) = tree.as_deref_mut() {
+                    tree.store(a, &closed, &g_score, &came_from);
                 }
-                total_path.push_front(a);
-                return Some(total_path.into());
+                return Some((
+                    PathResult::Complete(trace_path(&came_from, start, key)),
+                    g_score[&key],
+                ));
             }
 
-            search_ids.remove(&current.id);
+            *expanded += 1;
+            if tree.is_some() {
+                closed.push(key);
+            }
+            if options
+                .max_expanded
+                .is_some_and(|max| *expanded - expanded_before > max)
+            {
+                return partial(closest.1, &came_from, &g_score);
+            }
 
-            if !self.points.contains_key(&current.id) {
+            let Some(current_point) = self.points.get(&current.id) else {
                 continue;
+            };
+            let distance_squared = current_point.location.distance_squared(b_node.location);
+            if distance_squared < closest.0 {
+                closest = (distance_squared, key);
             }
 
-            for neighbor_id in &self.points[&current.id].connections {
-                let neighbor = &self.points[neighbor_id];
-                if !neighbor.can_occupy() {
-                    continue;
+            if options.max_nodes.is_some_and(|max| current.depth >= max) {
+                continue;
+            }
+            neighbors.clear();
+            neighbors.extend(
+                self.points[&current.id]
+                    .connections
+                    .iter()
+                    .map(|id| &self.points[id])
+                    .filter(|neighbor| self.is_passable(neighbor, options)),
+            );
+            // Landmark estimates take several lookups each, so they're only worked out for
+            // neighbors reached more cheaply than before, below.
+            if landmarks.is_none() {
+                batch_estimates(
+                    heuristic,
+                    &neighbors,
+                    b_node,
+                    self.spatial.min_cost_per_unit(),
+                    &mut heuristics,
+                );
+                if h_scale != 1.0 {
+                    for h in &mut heuristics {
+                        *h = scale_heuristic(*h, h_scale);
+                    }
                 }
-                let tentative_g_score =
-                    g_score[&current.id] + self.h_func(&current.id, &neighbor.id);
-                if tentative_g_score < *g_score.entry(*neighbor_id).or_insert(u32::MAX) {
-                    came_from.insert(*neighbor_id, current.id);
-                    let cur_h_score = self.h_func(neighbor_id, &b);
-                    let cur_f_score = tentative_g_score + cur_h_score;
-
-                    g_score.insert(*neighbor_id, tentative_g_score);
-                    f_score.insert(*neighbor_id, cur_f_score);
-
-                    if !search_ids.contains(neighbor_id) {
-                        search_ids.insert(*neighbor_id);
-                        open_set.push(Reverse(PathNode {
-                            id: *neighbor_id,
-                            f: cur_f_score,
-                        }));
+            }
+            let next_depth = options.max_nodes.map_or(0, |_| current.depth + 1);
+            for (i, neighbor) in neighbors.iter().enumerate() {
+                let neighbor_id = &neighbor.id;
+                let neighbor_key = (*neighbor_id, next_depth);
+                let mut tentative_g_score =
+                    g_score[&key].saturating_add(self.edge_cost(current.id, neighbor.id, options));
+                if options.turn_penalty > 0.0 {
+                    if let Some((previous, _)) = came_from.get(&key) {
+                        tentative_g_score = tentative_g_score.saturating_add(turn_cost(
+                            self.points[previous].location,
+                            current_point.location,
+                            neighbor.location,
+                            options.turn_penalty,
+                        ));
                     }
                 }
+                let mut reached_at = None;
+                if let Some(schedule) = &options.schedule {
+                    let Some((time, waited)) =
+                        self.traverse(current.id, *neighbor_id, arrival[&key], schedule)
+                    else {
+                        continue;
+                    };
+                    // Waiting costs the same as a connection as long as the distance which could
+                    // have been covered in the meantime.
+                    let waited_distance = waited as f32 * schedule.speed;
+                    tentative_g_score = tentative_g_score
+                        .saturating_add((waited_distance * waited_distance * 100.0) as u32);
+                    reached_at = Some(time);
+                }
+                if tentative_g_score < *g_score.entry(neighbor_key).or_insert(u32::MAX) {
+                    came_from.insert(neighbor_key, key);
+                    if let Some(time) = reached_at {
+                        arrival.insert(neighbor_key, time);
+                    }
+                    let cur_h_score = match &landmarks {
+                        Some(landmarks) => {
+                            scale_heuristic(landmarks.estimate(neighbor.id), h_weight)
+                        }
+                        None => heuristics[i],
+                    };
+                    let cur_f_score = tentative_g_score.saturating_add(cur_h_score);
+
+                    g_score.insert(neighbor_key, tentative_g_score);
+                    f_score.insert(neighbor_key, cur_f_score);
+
+                    open_set.push(Reverse(PathNode {
+                        id: *neighbor_id,
+                        depth: next_depth,
+                        f: cur_f_score,
+                    }));
+                }
             }
         }
-        None
+        partial(closest.1, &came_from, &g_score)
+    }
+}
+
+/// The path from `start` to `end` through the states each was reached from in a search.
+fn trace_path(
+    came_from: &HashMap<SearchKey, SearchKey>,
+    start: SearchKey,
+    end: SearchKey,
+) -> Vec<u32> {
+    let mut path = vec![end];
+    while *path.last().unwrap() != start {
+        path.push(came_from[path.last().unwrap()]);
     }
+    path.into_iter().rev().map(|(id, _)| id).collect()
 }
 
 #[cfg(test)]
@@ -523,6 +2501,21 @@ mod tests {
         assert_eq!(p[2], 3);
     }
 
+    #[test]
+    pub fn test_direct_connection_not_always_cheapest() {
+        let mut nav_graph = NavGraph::new();
+        for i in 1..=3 {
+            nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+        nav_graph.connect_points(1, 3);
+
+        // Costs go by squared distance, so two short hops beat one long one.
+        assert_eq!(nav_graph.find_path(1, 3), Some(vec![1, 2, 3]));
+        assert_eq!(nav_graph.path_cost(&[1, 2, 3]), Some(200));
+    }
+
     #[test]
     pub fn test_occupancy() {
         let mut nav_graph = NavGraph::new();
@@ -566,4 +2559,196 @@ mod tests {
         nav_graph.remove_point(2);
         assert_eq!(nav_graph.find_path(1, 4).unwrap()[1], 3);
     }
+
+    #[test]
+    pub fn test_cached_costs_follow_changes() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+        assert_eq!(nav_graph.path_cost(&[1, 2]), Some(100));
+
+        nav_graph.modify(2, |point| point.set_location(Vec3::new(2.0, 0.0, 0.0)));
+        assert_eq!(nav_graph.path_cost(&[1, 2]), Some(400));
+        assert_eq!(nav_graph.path_cost(&[2, 1]), Some(400));
+
+        nav_graph.modify(1, |point| point.set_speed_modifier(2.0));
+        assert_eq!(nav_graph.path_cost(&[2, 1]), Some(200));
+        assert_eq!(nav_graph.path_cost(&[1, 2]), Some(400));
+    }
+
+    #[test]
+    pub fn test_max_nodes() {
+        let mut nav_graph = NavGraph::new();
+        for i in 1..=5 {
+            nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+        }
+        for i in 1..5 {
+            nav_graph.connect_points(i, i + 1);
+        }
+
+        let options = SearchOptions::new().with_max_nodes(4);
+        assert_eq!(
+            nav_graph.find_path_with(1, 4, &options).unwrap()[..],
+            [1, 2, 3, 4]
+        );
+        assert!(nav_graph.find_path_with(1, 5, &options).is_none());
+        assert!(nav_graph
+            .find_path_with(1, 2, &SearchOptions::new().with_max_nodes(1))
+            .is_none());
+
+        // A cheaper way to 3 through 6 takes one point too many, but the direct one fits.
+        nav_graph.add_nav_point(NavPoint::new(6, Vec3::new(2.0, 0.1, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 6);
+        nav_graph.connect_points(6, 3);
+        nav_graph.connect_points(1, 3);
+        nav_graph.disconnect_points(1, 2);
+        assert_eq!(nav_graph.find_path(1, 4), Some(vec![1, 6, 3, 4]));
+        assert_eq!(
+            nav_graph.find_path_with(1, 4, &options),
+            Some(vec![1, 6, 3, 4])
+        );
+        let options = SearchOptions::new().with_max_nodes(3);
+        assert_eq!(
+            nav_graph.find_path_with(1, 4, &options),
+            Some(vec![1, 3, 4])
+        );
+    }
+
+    #[test]
+    pub fn test_huge_weights_saturate() {
+        let mut nav_graph = NavGraph::new();
+        for i in 1..=4 {
+            nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+        }
+        nav_graph.connect_points_weighted(1, 2, u32::MAX - 10);
+        nav_graph.connect_points(2, 3);
+        nav_graph.connect_points(3, 4);
+        nav_graph.connect_points(1, 4);
+
+        // The long way round costs more than can be counted, so the direct connection wins
+        // without overflowing.
+        assert_eq!(nav_graph.path_cost(&[1, 2, 3, 4]), Some(u32::MAX));
+        let options = SearchOptions::new();
+        assert_eq!(nav_graph.find_path_with(1, 4, &options), Some(vec![1, 4]));
+        assert_eq!(
+            nav_graph.find_path_with(1, 3, &options),
+            Some(vec![1, 4, 3])
+        );
+    }
+
+    #[test]
+    pub fn test_telemetry_covers_every_entry_point() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::{GridConnectivity, PathCache};
+
+        let (mut nav_graph, _) = NavGraph::<()>::from_grid(3, 1, 1.0, GridConnectivity::Four);
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        nav_graph.add_telemetry_hook(move |telemetry| {
+            sink.lock()
+                .unwrap()
+                .push((telemetry.path_len, telemetry.cost));
+        });
+        let options = SearchOptions::new();
+
+        // Answered from the cache and the warm start the second time round.
+        let mut path_cache = PathCache::new();
+        path_cache.find_path_with(&nav_graph, 1, 3, &options);
+        path_cache.find_path_with(&nav_graph, 1, 3, &options);
+        let mut warm_start = PathWarmStart::new();
+        nav_graph.find_path_warm(1, 3, &options, &mut warm_start);
+        nav_graph.find_path_warm(2, 3, &options, &mut warm_start);
+        assert_eq!(path_cache.stats().hits, 1);
+        assert_eq!(warm_start.reused(), 1);
+
+        // Failures are only reported once, however many searches go into explaining them.
+        nav_graph.occupy(2);
+        assert!(nav_graph.try_find_path(1, 3).is_err());
+        nav_graph.find_path_checked(1, 3, &options);
+
+        let cost = nav_graph.path_cost(&[1, 2, 3]);
+        assert_eq!(
+            reports.lock().unwrap()[..],
+            [
+                (Some(3), cost),
+                (Some(3), cost),
+                (Some(3), cost),
+                (Some(2), nav_graph.path_cost(&[2, 3])),
+                (None, None),
+                (None, None),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_goal_tolerance() {
+        let mut nav_graph = NavGraph::new();
+        for i in 1..=4 {
+            nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+        nav_graph.connect_points(3, 4);
+        nav_graph.occupy(4);
+
+        assert!(nav_graph.find_path(1, 4).is_none());
+
+        let options = SearchOptions::new().with_goal_tolerance(1);
+        assert_eq!(
+            nav_graph.find_path_with(1, 4, &options).unwrap()[..],
+            [1, 2, 3]
+        );
+    }
+
+    #[test]
+    pub fn test_batch_estimates_match() {
+        let points: Vec<NavPoint> = (0..11)
+            .map(|i| {
+                let location = Vec3::new(i as f32 * 1.7, (i * i) as f32 * 0.3, -(i as f32));
+                NavPoint::new(i, location, 1.0, 1)
+            })
+            .collect();
+        let points: Vec<&NavPoint> = points.iter().collect();
+        let goal = NavPoint::new(11, Vec3::new(3.1, -2.0, 0.5), 0.7, 1);
+
+        let mut costs = Vec::new();
+        for heuristic in [
+            Heuristic::Euclidean,
+            Heuristic::Manhattan,
+            Heuristic::Octile,
+            Heuristic::SquaredLegacy,
+        ] {
+            batch_estimates(heuristic, &points, &goal, 37.3, &mut costs);
+            let expected: Vec<u32> = points
+                .iter()
+                .map(|point| estimate(heuristic, point, &goal, 37.3))
+                .collect();
+            assert_eq!(costs, expected, "{heuristic:?}");
+        }
+    }
+
+    #[test]
+    pub fn test_euclidean_estimate_is_admissible() {
+        // Each connection costs 142 after rounding, rather than 142.857.
+        let mut nav_graph = NavGraph::new();
+        for id in 1..=4 {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 0.7, 1));
+        }
+        for id in 1..4 {
+            nav_graph.connect_points(id, id + 1);
+        }
+        let estimate = |nav_graph: &NavGraph| {
+            let (from, to) = (nav_graph.get_nav_point(1), nav_graph.get_nav_point(4));
+            nav_graph.estimate(Heuristic::Euclidean, from.unwrap(), to.unwrap())
+        };
+        assert_eq!(nav_graph.path_cost(&[1, 2, 3, 4]), Some(426));
+        assert!(estimate(&nav_graph) <= 426);
+
+        // Connections crossed in a fixed time may be cheaper than their length.
+        nav_graph.set_connection_duration(2, 3, Some(0.1));
+        let cost = nav_graph.path_cost(&[1, 2, 3, 4]).unwrap();
+        assert!(estimate(&nav_graph) <= cost);
+    }
 }
@@ -0,0 +1,358 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::system::Resource;
+use bevy_utils::HashMap;
+
+use crate::{NavDomain, NavGraph, PathPolicy, SearchOptions};
+
+/// How a [`PathCache`] reacts to changes to the graph's topology, see
+/// [`NavGraph::topology_version`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CacheInvalidation {
+    /// Every cached path is dropped.
+    #[default]
+    Full,
+    /// Only paths through points or connections which were removed or whose costs changed are
+    /// dropped. Paths elsewhere are kept, even though a change may have opened up a cheaper
+    /// route for them.
+    Regional,
+}
+
+/// How well a [`PathCache`] is doing, from [`PathCache::stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PathCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// The number of cached paths dropped because the graph changed.
+    pub invalidated: u64,
+}
+
+impl PathCacheStats {
+    /// The fraction of lookups answered from the cache, or 0.0 before any lookups.
+    pub fn hit_rate(&self) -> f32 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f32 / lookups as f32,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedPath {
+    path: Vec<u32>,
+    cost: u32,
+}
+
+/// Remembers the paths found between pairs of points, for games where many travelers head
+/// between the same few places.
+///
+/// The cache holds the cheapest path between each pair as if every point were empty, which stays
+/// the cheapest as long as the graph's topology doesn't change in a way that affects it (see
+/// [`CacheInvalidation`]). It's returned while every point on it is passable for the search
+/// asking, and otherwise the search runs as usual, without touching the cache. Searches with
+/// noise, a schedule, a goal tolerance or allowing partial paths aren't cached, as their paths
+/// don't carry over between travelers, and neither are those restricted to some of the points
+/// or with a heuristic of their own. Nothing is cached while the graph has corridor or point
+/// reservations, which come and go without changing its topology.
+///
+/// Opt in by adding the resource; travelers then use it when planning their initial path, unless
+/// pathfinding is asynchronous (see [`PathRequestQueue`](crate::PathRequestQueue)).
+///
+/// ## Example
+/// ```
+/// # use bevy_math::Vec3;
+/// # use bevy_navigator::{NavGraph, NavPoint, PathCache};
+/// let mut nav_graph = NavGraph::new();
+/// for id in 1..=3 {
+///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
+/// }
+/// nav_graph.connect_points(1, 2);
+/// nav_graph.connect_points(2, 3);
+///
+/// let mut cache = PathCache::new();
+/// assert_eq!(cache.find_path(&nav_graph, 1, 3), Some(vec![1, 2, 3]));
+/// assert_eq!(cache.find_path(&nav_graph, 1, 3), Some(vec![1, 2, 3]));
+/// assert_eq!((cache.stats().hits, cache.stats().misses), (1, 1));
+/// ```
+#[derive(Debug, Resource)]
+pub struct PathCache<M: NavDomain = ()> {
+    pub invalidation: CacheInvalidation,
+    topology_version: Option<u64>,
+    paths: HashMap<(u32, u32), CachedPath>,
+    stats: PathCacheStats,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> Default for PathCache<M> {
+    fn default() -> Self {
+        Self {
+            invalidation: CacheInvalidation::default(),
+            topology_version: None,
+            paths: HashMap::default(),
+            stats: PathCacheStats::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl PathCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<M: NavDomain> PathCache<M> {
+    pub fn with_invalidation(mut self, invalidation: CacheInvalidation) -> Self {
+        self.invalidation = invalidation;
+        self
+    }
+
+    /// Like [`NavGraph::find_path`], answered from the cache where possible.
+    pub fn find_path(&mut self, nav_graph: &NavGraph<M>, a: u32, b: u32) -> Option<Vec<u32>> {
+        self.find_path_with(nav_graph, a, b, &SearchOptions::default())
+    }
+
+    /// Like [`NavGraph::find_path_with`], answered from the cache where possible.
+    pub fn find_path_with(
+        &mut self,
+        nav_graph: &NavGraph<M>,
+        a: u32,
+        b: u32,
+        options: &SearchOptions,
+    ) -> Option<Vec<u32>> {
+        self.find_path_counted(nav_graph, a, b, options, &mut 0)
+    }
+
+    /// Like [`PathCache::find_path_with`], adding the number of points expanded to `expanded`.
+    pub(crate) fn find_path_counted(
+        &mut self,
+        nav_graph: &NavGraph<M>,
+        a: u32,
+        b: u32,
+        options: &SearchOptions,
+        expanded: &mut usize,
+    ) -> Option<Vec<u32>> {
+        nav_graph.observe(
+            a,
+            b,
+            || self.lookup(nav_graph, a, b, options, expanded),
+            |path| (path.len(), nav_graph.path_cost(path)),
+        )
+    }
+
+    fn lookup(
+        &mut self,
+        nav_graph: &NavGraph<M>,
+        a: u32,
+        b: u32,
+        options: &SearchOptions,
+        expanded: &mut usize,
+    ) -> Option<Vec<u32>> {
+        let search = |expanded: &mut usize| {
+            nav_graph
+                .search_recorded(a, b, options, None, None, expanded)
+                .map(|(path, _)| path.into_path())
+        };
+        if !is_cacheable(options) {
+            return search(expanded);
+        }
+
+        self.sync(nav_graph);
+        if let Some(cached) = self.paths.get(&(a, b)) {
+            if is_usable(nav_graph, cached, options) {
+                self.stats.hits += 1;
+                return Some(cached.path.clone());
+            }
+            // Still the cheapest once whatever is in the way moves on.
+            self.stats.misses += 1;
+            return search(expanded);
+        }
+
+        self.stats.misses += 1;
+        if !nav_graph.corridor_reservations.is_empty() || !nav_graph.point_reservations.is_empty() {
+            return search(expanded);
+        }
+        let layout = SearchOptions {
+            ignore_occupancy: true,
+            ..options.clone()
+        };
+        let path = nav_graph
+            .search_recorded(a, b, &layout, None, None, expanded)
+            .map(|(path, _)| path.into_path())?;
+        let cost = nav_graph.path_cost(&path)?;
+        let cached = CachedPath { path, cost };
+        let usable = is_usable(nav_graph, &cached, options);
+        let path = usable.then(|| cached.path.clone());
+        self.paths.insert((a, b), cached);
+        match path {
+            Some(path) => Some(path),
+            None => search(expanded),
+        }
+    }
+
+    /// Drops the cached paths through any point of `region`, e.g. after opening up a shortcut
+    /// there, which [`CacheInvalidation::Regional`] doesn't catch.
+    pub fn invalidate_region(&mut self, nav_graph: &NavGraph<M>, region: u32) {
+        let before = self.paths.len();
+        self.paths.retain(|_, cached| {
+            !cached.path.iter().any(|id| {
+                nav_graph
+                    .get_nav_point(*id)
+                    .is_none_or(|point| point.region() == region)
+            })
+        });
+        self.stats.invalidated += (before - self.paths.len()) as u64;
+    }
+
+    /// Drops every cached path.
+    pub fn clear(&mut self) {
+        self.stats.invalidated += self.paths.len() as u64;
+        self.paths.clear();
+    }
+
+    /// The number of paths cached.
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Returns true if no paths are cached.
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    pub fn stats(&self) -> PathCacheStats {
+        self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = PathCacheStats::default();
+    }
+
+    /// Drops the paths invalidated by changes to the graph since the last lookup.
+    fn sync(&mut self, nav_graph: &NavGraph<M>) {
+        let version = nav_graph.topology_version();
+        if self.topology_version == Some(version) {
+            return;
+        }
+        self.topology_version = Some(version);
+        match self.invalidation {
+            CacheInvalidation::Full => self.clear(),
+            CacheInvalidation::Regional => {
+                let before = self.paths.len();
+                self.paths
+                    .retain(|_, cached| nav_graph.path_cost(&cached.path) == Some(cached.cost));
+                self.stats.invalidated += (before - self.paths.len()) as u64;
+            }
+        }
+    }
+}
+
+/// Whether the paths of searches with `options` can be shared between searches.
+fn is_cacheable(options: &SearchOptions) -> bool {
+    options.noise.is_none_or(|noise| noise.magnitude <= 0.0)
+        && options.schedule.is_none()
+        && options.goal_tolerance == 0
+        && options.path_policy == PathPolicy::CompleteOnly
+        && options.epsilon.is_none_or(|epsilon| epsilon <= 1.0)
+        && options.turn_penalty <= 0.0
+        && options.cost_provider.is_none()
+        && options.area_costs.is_none()
+        && options.agent_size <= 0.0
+        && options.congestion_cost <= 0.0
+        && options.max_nodes.is_none()
+        && options.excluded.is_empty()
+        && options.allowed_regions.is_none()
+        && options.traversal_mask == 0
+        && options.group.is_none()
+        && options.faction.is_none()
+        && options.heuristic.is_none()
+}
+
+/// Whether a search with `options` may take `cached`.
+fn is_usable<M: NavDomain>(
+    nav_graph: &NavGraph<M>,
+    cached: &CachedPath,
+    options: &SearchOptions,
+) -> bool {
+    cached.path[1..].iter().all(|id| {
+        nav_graph
+            .get_nav_point(*id)
+            .is_some_and(|point| nav_graph.is_passable(point, options))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::NavPoint;
+
+    #[test]
+    pub fn test_regional_invalidation() {
+        // Two separate corridors, 1-2-3 and 4-5-6.
+        let mut nav_graph = NavGraph::new();
+        for id in 1..=6 {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
+        }
+        for (a, b) in [(1, 2), (2, 3), (4, 5), (5, 6)] {
+            nav_graph.connect_points(a, b);
+        }
+
+        let mut cache = PathCache::new().with_invalidation(CacheInvalidation::Regional);
+        cache.find_path(&nav_graph, 1, 3);
+        cache.find_path(&nav_graph, 4, 6);
+        assert_eq!(cache.len(), 2);
+
+        // Slowing down the second corridor only drops the path through it.
+        nav_graph.modify(5, |point| point.set_speed_modifier(0.5));
+        assert_eq!(cache.find_path(&nav_graph, 1, 3), Some(vec![1, 2, 3]));
+        assert_eq!(cache.find_path(&nav_graph, 4, 6), Some(vec![4, 5, 6]));
+        assert_eq!(
+            cache.stats(),
+            PathCacheStats {
+                hits: 1,
+                misses: 3,
+                invalidated: 1,
+            }
+        );
+
+        // Occupied points are searched around rather than taken from the cache.
+        nav_graph.occupy(2);
+        assert_eq!(cache.find_path(&nav_graph, 1, 3), None);
+        assert_eq!(cache.stats().misses, 4);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    pub fn test_detours_around_occupancy_are_not_cached() {
+        // 1 - 2 - 3 straight across, or round through 4.
+        let mut nav_graph = NavGraph::new();
+        for (id, x, y) in [(1, 0.0, 0.0), (2, 1.0, 0.0), (3, 2.0, 0.0), (4, 1.0, 1.0)] {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 1));
+        }
+        for (a, b) in [(1, 2), (2, 3), (1, 4), (4, 3)] {
+            nav_graph.connect_points(a, b);
+        }
+
+        let mut cache = PathCache::new();
+        nav_graph.occupy(2);
+        assert_eq!(cache.find_path(&nav_graph, 1, 3), Some(vec![1, 4, 3]));
+        nav_graph.unoccupy(2);
+        assert_eq!(cache.find_path(&nav_graph, 1, 3), Some(vec![1, 2, 3]));
+
+        // Restricted searches neither take from nor add to the cache.
+        let options = SearchOptions {
+            excluded: [2].into_iter().collect(),
+            ..SearchOptions::default()
+        };
+        assert_eq!(
+            cache.find_path_with(&nav_graph, 1, 3, &options),
+            Some(vec![1, 4, 3])
+        );
+        assert_eq!(cache.find_path(&nav_graph, 1, 3), Some(vec![1, 2, 3]));
+        assert_eq!(cache.stats().hits, 2);
+    }
+}
@@ -0,0 +1,164 @@
+use bevy_ecs::{
+    component::Component,
+    query::Without,
+    system::{Query, ResMut},
+};
+use bevy_math::Vec3;
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_transform::prelude::Transform;
+
+use crate::{traveler::TravelerPosition, NavDomain, NavGraph};
+
+/// A connection from a [`NavPlatform`] which can only be used while the platform is at `position`.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, FromReflect)]
+pub struct PlatformDock {
+    /// The [`NavPoint`](crate::NavPoint) the platform connects to.
+    pub connection: u32,
+    /// Where the platform has to be for the connection to be usable.
+    pub position: Vec3,
+}
+
+/// Makes a [`NavPoint`](crate::NavPoint) follow this entity's [`Transform`], for moving platforms
+/// and elevators.
+///
+/// Each frame the point is moved to the entity's translation, and travelers standing on the
+/// point are moved along with it. Connections listed in `docks` are closed (see
+/// [`NavEdge::closed`](crate::NavEdge::closed)) unless the platform is within `tolerance` of the
+/// dock's position, so travelers wait on either side until the platform arrives. Connections
+/// which aren't listed are always usable.
+///
+/// The entity's [`Transform`] is treated as being in world space, so platforms shouldn't be
+/// parented to other moving entities.
+#[derive(Debug, Clone, Component, Reflect, FromReflect)]
+pub struct NavPlatform {
+    pub nav_point: u32,
+    pub docks: Vec<PlatformDock>,
+    pub tolerance: f32,
+}
+
+impl NavPlatform {
+    pub fn new(nav_point: u32) -> Self {
+        Self {
+            nav_point,
+            docks: Vec::new(),
+            tolerance: 0.01,
+        }
+    }
+
+    /// Only allows travel between the platform and `connection` while the platform is at
+    /// `position`.
+    pub fn with_dock(mut self, connection: u32, position: Vec3) -> Self {
+        self.docks.push(PlatformDock {
+            connection,
+            position,
+        });
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+type RiderQuery<'w, 's, M> = Query<
+    'w,
+    's,
+    (&'static TravelerPosition, &'static mut Transform),
+    (<M as NavDomain>::Filter, Without<NavPlatform>),
+>;
+
+pub(crate) fn sync_nav_platforms<M: NavDomain>(
+    platforms_query: Query<(&NavPlatform, &Transform), M::Filter>,
+    mut riders_query: RiderQuery<M>,
+    mut nav_graph: ResMut<NavGraph<M>>,
+) {
+    for (platform, transform) in platforms_query.iter() {
+        let Some(point) = nav_graph.get_nav_point(platform.nav_point) else {
+            continue;
+        };
+
+        let delta = transform.translation - point.location();
+        if delta != Vec3::ZERO {
+            nav_graph.modify(platform.nav_point, |point| {
+                point.set_location(transform.translation)
+            });
+            for (traveler_position, mut rider_transform) in riders_query.iter_mut() {
+                if traveler_position.current_nav_point == platform.nav_point
+                    && traveler_position.next_nav_point.is_none()
+                {
+                    rider_transform.translation += delta;
+                }
+            }
+        }
+
+        for dock in &platform.docks {
+            let docked = transform.translation.distance_squared(dock.position)
+                <= platform.tolerance * platform.tolerance;
+            nav_graph.set_connection_closed(platform.nav_point, dock.connection, !docked);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+
+    use super::*;
+    use crate::NavPoint;
+
+    #[test]
+    pub fn test_platform_docks_and_carries_riders() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(1.0, 5.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .add_system(sync_nav_platforms::<()>);
+        let platform = app
+            .world
+            .spawn((
+                NavPlatform::new(2)
+                    .with_dock(1, Vec3::new(1.0, 0.0, 0.0))
+                    .with_dock(3, Vec3::new(1.0, 4.0, 0.0)),
+                Transform::from_xyz(1.0, 0.0, 0.0),
+            ))
+            .id();
+        let rider = app
+            .world
+            .spawn((
+                TravelerPosition {
+                    current_nav_point: 2,
+                    next_nav_point: None,
+                },
+                Transform::from_xyz(1.0, 0.0, 0.0),
+            ))
+            .id();
+
+        app.update();
+        let nav_graph = app.world.resource::<NavGraph>();
+        assert!(nav_graph.is_open(1, 2, 0.0));
+        assert!(!nav_graph.is_open(2, 3, 0.0));
+
+        app.world
+            .get_mut::<Transform>(platform)
+            .unwrap()
+            .translation = Vec3::new(1.0, 4.0, 0.0);
+        app.update();
+        let nav_graph = app.world.resource::<NavGraph>();
+        assert!(!nav_graph.is_open(1, 2, 0.0));
+        assert!(nav_graph.is_open(2, 3, 0.0));
+        assert_eq!(
+            nav_graph.get_nav_point(2).unwrap().location(),
+            Vec3::new(1.0, 4.0, 0.0)
+        );
+        assert_eq!(
+            app.world.get::<Transform>(rider).unwrap().translation,
+            Vec3::new(1.0, 4.0, 0.0)
+        );
+    }
+}
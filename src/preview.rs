@@ -0,0 +1,191 @@
+use std::collections::VecDeque;
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::Changed,
+    system::{Commands, Local, Query, ResMut},
+};
+use bevy_reflect::{FromReflect, Reflect};
+
+#[cfg(feature = "travelers")]
+use bevy_ecs::system::Res;
+
+use crate::{graphs::TravelerGraphs, NavDomain, PathCache, PathResult, SearchOptions};
+#[cfg(feature = "travelers")]
+use crate::{AutoTraveler, LowFidelity, NavClock, PathRequestQueue};
+
+/// Requests a path between two [`NavPoint`](crate::NavPoint)s for display purposes only.
+///
+/// Unlike [`AutoTraveler`](crate::AutoTraveler), a preview never occupies any nodes and never
+/// moves the entity. The computed path is stored in a [`PathPreview`] on the same entity, and is
+/// recomputed whenever the request changes.
+///
+/// Previews are answered from the [`PathCache`], if there is one. With the `travelers` feature,
+/// they're computed from whatever is left of the
+/// [`PathRequestQueue`](crate::PathRequestQueue)'s budget once travelers waiting in it have been
+/// planned, waiting for a later frame if there's none, and a preview on an
+/// [`AutoTraveler`](crate::AutoTraveler) is planned with its search options, so it shows the
+/// route the traveler would take, on its [`graph`](crate::AutoTraveler::graph).
+#[derive(Debug, Default, Clone, Copy, Component, Reflect, FromReflect)]
+pub struct PathPreviewRequest {
+    pub origin: u32,
+    pub destination: u32,
+}
+
+impl PathPreviewRequest {
+    pub fn new(origin: u32, destination: u32) -> Self {
+        Self {
+            origin,
+            destination,
+        }
+    }
+}
+
+/// The result of a [`PathPreviewRequest`].
+///
+/// `path` is `None` if no path currently exists between the requested points.
+#[derive(Debug, Default, Clone, Component, Reflect, FromReflect)]
+pub struct PathPreview {
+    pub origin: u32,
+    pub destination: u32,
+    pub path: Option<Vec<u32>>,
+}
+
+type PreviewRequestQuery<'w, 's, M> = Query<
+    'w,
+    's,
+    (Entity, &'static PathPreviewRequest),
+    (<M as NavDomain>::Filter, Changed<PathPreviewRequest>),
+>;
+
+#[cfg(feature = "travelers")]
+type PreviewTravelerQuery<'w, 's, M> =
+    Query<'w, 's, (&'static AutoTraveler, Option<&'static LowFidelity>), <M as NavDomain>::Filter>;
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn compute_path_previews<M: NavDomain>(
+    changed_query: PreviewRequestQuery<M>,
+    requests_query: Query<&PathPreviewRequest, <M as NavDomain>::Filter>,
+    #[cfg(feature = "travelers")] travelers_query: PreviewTravelerQuery<M>,
+    #[cfg(feature = "travelers")] mut queue: ResMut<PathRequestQueue<M>>,
+    #[cfg(feature = "travelers")] clock: Res<NavClock>,
+    graphs: TravelerGraphs<M>,
+    mut path_cache: Option<ResMut<PathCache<M>>>,
+    mut pending: Local<VecDeque<Entity>>,
+    mut commands: Commands,
+) {
+    for (entity, _) in changed_query.iter() {
+        if !pending.contains(&entity) {
+            pending.push_back(entity);
+        }
+    }
+
+    while let Some(&entity) = pending.front() {
+        #[cfg(feature = "travelers")]
+        if !queue.has_budget() {
+            break;
+        }
+        pending.pop_front();
+        // The request may have been removed while waiting.
+        let Ok(request) = requests_query.get(entity) else {
+            continue;
+        };
+
+        // As `compute_initial_path` plans the traveler's path.
+        #[cfg(feature = "travelers")]
+        let (options, graph) = match travelers_query.get(entity) {
+            Ok((auto_traveler, low_fidelity)) => {
+                let mut options = auto_traveler.search_options_at(clock.now);
+                options.ignore_occupancy = low_fidelity.is_some();
+                (options, auto_traveler.graph)
+            }
+            Err(_) => (SearchOptions::default(), None),
+        };
+        #[cfg(not(feature = "travelers"))]
+        let (options, graph) = (SearchOptions::default(), None);
+
+        let (a, b) = (request.origin, request.destination);
+        let mut expanded = 0;
+        let path = match (graphs.get(graph), path_cache.as_deref_mut()) {
+            // The traveler's graph isn't loaded, so there's no path on it.
+            (None, _) => None,
+            (Some(nav_graph), Some(path_cache)) if graph.is_none() => {
+                path_cache.find_path_counted(nav_graph, a, b, &options, &mut expanded)
+            }
+            (Some(nav_graph), _) => nav_graph
+                .find_path_counted(a, b, &options, &mut expanded)
+                .map(PathResult::into_path),
+        };
+        #[cfg(feature = "travelers")]
+        {
+            queue.budget.searches += 1;
+            queue.budget.expanded += expanded;
+        }
+
+        commands.entity(entity).insert(PathPreview {
+            origin: a,
+            destination: b,
+            path,
+        });
+    }
+}
+
+#[cfg(all(test, feature = "travelers"))]
+mod tests {
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::{testing::test_app, GridConnectivity, NavGraph, NavPoint};
+
+    #[test]
+    pub fn test_preview_does_not_occupy() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(0.0, 1.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+
+        let mut app = test_app();
+        app.insert_resource(nav_graph);
+        let entity = app.world.spawn(PathPreviewRequest::new(1, 2)).id();
+        app.update();
+
+        let preview = app.world.get::<PathPreview>(entity).unwrap();
+        assert_eq!(preview.path.as_ref().unwrap()[..], [1, 2]);
+        assert_eq!(
+            app.world
+                .resource::<NavGraph>()
+                .get_nav_point(2)
+                .unwrap()
+                .current_occupancy(),
+            0
+        );
+    }
+
+    #[test]
+    pub fn test_preview_waits_for_budget_and_follows_traveler() {
+        const WATER: u32 = 1 << 0;
+        let (mut nav_graph, _) = NavGraph::<()>::from_grid(3, 1, 1.0, GridConnectivity::Four);
+        nav_graph.modify(2, |point| point.set_flags(WATER));
+
+        let mut app = test_app();
+        app.insert_resource(nav_graph)
+            .insert_resource(PathRequestQueue::new(Some(1)));
+        let entity = app
+            .world
+            .spawn((
+                AutoTraveler::new(1, 3, 1.0).with_traversal_mask(WATER),
+                PathPreviewRequest::new(1, 3),
+            ))
+            .id();
+
+        // Planning the traveler itself takes up the frame's only search.
+        app.update();
+        assert!(app.world.get::<PathPreview>(entity).is_none());
+
+        // Swimming across is only open to the traveler.
+        app.update();
+        let preview = app.world.get::<PathPreview>(entity).unwrap();
+        assert_eq!(preview.path.as_ref().unwrap()[..], [1, 2, 3]);
+    }
+}
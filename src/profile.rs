@@ -0,0 +1,257 @@
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    system::{Query, Res, Resource},
+};
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_utils::{tracing::info, HashMap};
+#[cfg(feature = "asset")]
+use serde::{Deserialize, Serialize};
+
+use crate::{AreaCosts, AutoTraveler, BlockedBehavior, Heuristic, NavDomain};
+
+/// A movement archetype shared by many travelers, e.g. "infantry" or "cavalry", stored by name
+/// in [`NavigationProfiles`].
+///
+/// A traveler referring to a profile with [`AutoTraveler::with_profile`] has the profile's
+/// values replace its own when it's added, and again whenever [`NavigationProfiles`] changes.
+#[derive(Debug, Clone, PartialEq, Reflect, FromReflect)]
+#[cfg_attr(feature = "asset", derive(Serialize, Deserialize))]
+pub struct NavigationProfile {
+    pub speed: f32,
+    /// See [`SearchOptions::heuristic`](crate::SearchOptions::heuristic).
+    #[cfg_attr(feature = "asset", serde(default))]
+    pub heuristic: Option<Heuristic>,
+    /// See [`SearchOptions::epsilon`](crate::SearchOptions::epsilon).
+    #[cfg_attr(feature = "asset", serde(default))]
+    pub epsilon: Option<f32>,
+    #[cfg_attr(feature = "asset", serde(default))]
+    pub blocked_behavior: BlockedBehavior,
+    /// See [`SearchOptions::traversal_mask`](crate::SearchOptions::traversal_mask).
+    #[cfg_attr(feature = "asset", serde(default))]
+    pub traversal_mask: u32,
+    /// See [`SearchOptions::turn_penalty`](crate::SearchOptions::turn_penalty).
+    #[cfg_attr(feature = "asset", serde(default))]
+    pub turn_penalty: f32,
+    /// See [`AutoTraveler::area_costs`].
+    #[cfg_attr(feature = "asset", serde(default))]
+    pub area_costs: Option<AreaCosts>,
+    /// See [`AutoTraveler::size`].
+    #[cfg_attr(feature = "asset", serde(default))]
+    pub size: f32,
+    /// See [`SearchOptions::congestion_cost`](crate::SearchOptions::congestion_cost).
+    #[cfg_attr(feature = "asset", serde(default))]
+    pub congestion_cost: f32,
+}
+
+impl NavigationProfile {
+    pub fn new(speed: f32) -> Self {
+        Self {
+            speed,
+            heuristic: None,
+            epsilon: None,
+            blocked_behavior: BlockedBehavior::default(),
+            traversal_mask: 0,
+            turn_penalty: 0.0,
+            area_costs: None,
+            size: 0.0,
+            congestion_cost: 0.0,
+        }
+    }
+
+    pub fn with_heuristic(mut self, heuristic: Heuristic) -> Self {
+        self.heuristic = Some(heuristic);
+        self
+    }
+
+    pub fn with_epsilon(mut self, epsilon: f32) -> Self {
+        self.epsilon = Some(epsilon);
+        self
+    }
+
+    pub fn with_blocked_behavior(mut self, blocked_behavior: BlockedBehavior) -> Self {
+        self.blocked_behavior = blocked_behavior;
+        self
+    }
+
+    pub fn with_traversal_mask(mut self, traversal_mask: u32) -> Self {
+        self.traversal_mask = traversal_mask;
+        self
+    }
+
+    pub fn with_turn_penalty(mut self, turn_penalty: f32) -> Self {
+        self.turn_penalty = turn_penalty;
+        self
+    }
+
+    pub fn with_area_costs(mut self, area_costs: AreaCosts) -> Self {
+        self.area_costs = Some(area_costs);
+        self
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_congestion_cost(mut self, congestion_cost: f32) -> Self {
+        self.congestion_cost = congestion_cost;
+        self
+    }
+
+    /// Copies the profile's values onto `auto_traveler`.
+    fn apply(&self, auto_traveler: &mut AutoTraveler) {
+        auto_traveler.speed = self.speed;
+        auto_traveler.heuristic = self.heuristic;
+        auto_traveler.epsilon = self.epsilon;
+        auto_traveler.blocked_behavior = self.blocked_behavior;
+        auto_traveler.traversal_mask = self.traversal_mask;
+        auto_traveler.turn_penalty = self.turn_penalty;
+        auto_traveler.area_costs = self.area_costs.clone();
+        auto_traveler.size = self.size;
+        auto_traveler.congestion_cost = self.congestion_cost;
+    }
+}
+
+/// The [`NavigationProfile`]s travelers refer to by name, so movement archetypes are tuned in one
+/// place rather than at every spawn.
+///
+/// With the `asset` feature, profiles can be read from data files, as a map from name to
+/// profile:
+/// ```ron
+/// {
+///     "infantry": (speed: 2.0, blocked_behavior: Adaptive),
+///     "cavalry": (speed: 5.0, epsilon: Some(1.5), turn_penalty: 2.0),
+///     "boat": (speed: 3.0, traversal_mask: 2),
+/// }
+/// ```
+///
+/// ## Example
+/// ```
+/// # use bevy_ecs::system::{Commands, ResMut};
+/// # use bevy_navigator::{AutoTraveler, NavigationProfile, NavigationProfiles};
+/// const WATER: u32 = 1 << 1;
+///
+/// fn setup(mut profiles: ResMut<NavigationProfiles>, mut commands: Commands) {
+///     profiles.insert("boat", NavigationProfile::new(3.0).with_traversal_mask(WATER));
+///     commands.spawn(AutoTraveler::new(1, 42, 1.0).with_profile("boat"));
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Resource, Reflect)]
+#[cfg_attr(feature = "asset", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "asset", serde(transparent))]
+pub struct NavigationProfiles {
+    profiles: HashMap<String, NavigationProfile>,
+}
+
+impl NavigationProfiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces the profile called `name`.
+    pub fn insert(&mut self, name: impl Into<String>, profile: NavigationProfile) {
+        self.profiles.insert(name.into(), profile);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&NavigationProfile> {
+        self.profiles.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut NavigationProfile> {
+        self.profiles.get_mut(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<NavigationProfile> {
+        self.profiles.remove(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+}
+
+pub(crate) fn apply_navigation_profiles<M: NavDomain>(
+    mut travelers_query: Query<&mut AutoTraveler, <M as NavDomain>::Filter>,
+    profiles: Res<NavigationProfiles>,
+) {
+    let profiles_changed = profiles.is_changed();
+    for mut auto_traveler in travelers_query.iter_mut() {
+        let added = auto_traveler.is_added();
+        if !profiles_changed && !added {
+            continue;
+        }
+        let Some(name) = auto_traveler.profile.as_deref() else {
+            continue;
+        };
+        match profiles.get(name) {
+            Some(profile) => profile.apply(&mut auto_traveler),
+            None if added => info!("No navigation profile named {}", name),
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_math::Vec3;
+    use bevy_transform::prelude::Transform;
+
+    use super::*;
+    use crate::{NavGraph, NavPoint, NavigatorPlugin};
+
+    #[test]
+    pub fn test_profiles_apply_to_travelers() {
+        const WATER: u32 = 1 << 1;
+        let mut app = App::new();
+        app.add_plugin(NavigatorPlugin::new())
+            .insert_resource(bevy_time::Time::default());
+        // A short way from 1 to 3 across water through 2, and a long way round through 4 and 5.
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        for (id, x, y) in [
+            (1, 0.0, 0.0),
+            (2, 1.0, 0.0),
+            (3, 2.0, 0.0),
+            (4, 0.0, 3.0),
+            (5, 2.0, 3.0),
+        ] {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 4));
+        }
+        nav_graph.get_nav_point_mut(2).unwrap().set_flags(WATER);
+        for (a, b) in [(1, 2), (2, 3), (1, 4), (4, 5), (5, 3)] {
+            nav_graph.connect_points(a, b);
+        }
+        app.world.resource_mut::<NavigationProfiles>().insert(
+            "swimmer",
+            NavigationProfile::new(2.0).with_traversal_mask(WATER),
+        );
+
+        let swimmer = app
+            .world
+            .spawn((
+                Transform::default(),
+                AutoTraveler::new(1, 3, 1.0).with_profile("swimmer"),
+            ))
+            .id();
+        let walker = app
+            .world
+            .spawn((Transform::default(), AutoTraveler::new(1, 3, 1.0)))
+            .id();
+        app.update();
+
+        let traveler = |app: &App, entity| app.world.get::<AutoTraveler>(entity).unwrap().clone();
+        assert_eq!(traveler(&app, swimmer).speed, 2.0);
+        assert_eq!(traveler(&app, swimmer).path, Some(vec![1, 2, 3]));
+        assert_eq!(traveler(&app, walker).path, Some(vec![1, 4, 5, 3]));
+
+        // Tweaking the profile updates the travelers using it.
+        app.world
+            .resource_mut::<NavigationProfiles>()
+            .get_mut("swimmer")
+            .unwrap()
+            .speed = 4.0;
+        app.update();
+        assert_eq!(traveler(&app, swimmer).speed, 4.0);
+        assert_eq!(traveler(&app, walker).speed, 1.0);
+    }
+}
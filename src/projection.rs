@@ -0,0 +1,141 @@
+use bevy_math::Vec3;
+
+use crate::{NavDomain, NavGraph};
+
+/// The closest place on a [`NavGraph`]'s connections to some position, from
+/// [`NavGraph::project_onto_graph`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraphProjection {
+    /// The projected position itself.
+    pub location: Vec3,
+    /// The lower ID of the two points the connection links.
+    pub from: u32,
+    /// The higher ID of the two points the connection links, or the same as `from` when the
+    /// closest point has no connections.
+    pub to: u32,
+    /// How far along the connection from `from` to `to` the projection lies, from `0.0` at
+    /// `from` to `1.0` at `to`.
+    pub t: f32,
+}
+
+impl GraphProjection {
+    /// Whichever end of the connection the projection is closer to.
+    pub fn closest_point(&self) -> u32 {
+        if self.t <= 0.5 {
+            self.from
+        } else {
+            self.to
+        }
+    }
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Projects `position` onto the closest connection of the graph, or `None` if it's empty.
+    ///
+    /// Meant for entities pushed off the graph, e.g. by physics, to find where to rejoin it
+    /// rather than snapping to the center of the nearest point. Points without connections count
+    /// as connections of zero length. Of several equally close connections, the one with the
+    /// lowest IDs is picked.
+    ///
+    /// Only the points within reach of `position` are looked at, going by the spatial grid
+    /// behind [`NavGraph::nearest_point`] and the longest connection it has seen.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(4.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.connect_points(1, 2);
+    ///
+    /// let projection = nav_graph.project_onto_graph(Vec3::new(1.0, 2.0, 0.0)).unwrap();
+    /// assert_eq!((projection.from, projection.to), (1, 2));
+    /// assert_eq!(projection.location, Vec3::new(1.0, 0.0, 0.0));
+    /// assert_eq!(projection.t, 0.25);
+    /// ```
+    pub fn project_onto_graph(&self, position: Vec3) -> Option<GraphProjection> {
+        let nearest = self.points.get(&self.nearest_point(position)?)?;
+        // The closest connection is no further than the nearest point, so one of its ends is
+        // within half the longest connection of that.
+        let reach = nearest.location().distance(position) + self.spatial.longest_connection() * 0.5;
+
+        let mut best: Option<(f32, GraphProjection)> = None;
+        let mut consider = |projection: GraphProjection| {
+            let distance_squared = projection.location.distance_squared(position);
+            let closer = best.is_none_or(|(best_distance_squared, best)| {
+                (distance_squared, projection.from, projection.to)
+                    < (best_distance_squared, best.from, best.to)
+            });
+            if closer {
+                best = Some((distance_squared, projection));
+            }
+        };
+
+        for id in self.points_within_radius(position, reach) {
+            let point = &self.points[&id];
+            if point.connections.is_empty() {
+                consider(GraphProjection {
+                    location: point.location(),
+                    from: id,
+                    to: id,
+                    t: 0.0,
+                });
+            }
+            for other in point
+                .connections
+                .iter()
+                .filter_map(|id| self.points.get(id))
+            {
+                let (a, b) = if point.id() < other.id() {
+                    (point, other)
+                } else {
+                    (other, point)
+                };
+                let segment = b.location() - a.location();
+                let length_squared = segment.length_squared();
+                let t = if length_squared > 0.0 {
+                    ((position - a.location()).dot(segment) / length_squared).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                consider(GraphProjection {
+                    location: a.location() + segment * t,
+                    from: a.id(),
+                    to: b.id(),
+                    t,
+                });
+            }
+        }
+        best.map(|(_, projection)| projection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NavPoint;
+
+    #[test]
+    pub fn test_projection_finds_long_connections() {
+        // The nearest point is 3, but the long connection from 1 to 2 passes closer.
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(-50.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(50.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(0.0, 3.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+
+        let position = Vec3::new(1.0, 1.0, 0.0);
+        assert_eq!(nav_graph.nearest_point(position), Some(3));
+        let projection = nav_graph.project_onto_graph(position).unwrap();
+        assert_eq!((projection.from, projection.to), (1, 2));
+        assert_eq!(projection.location, Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(projection.closest_point(), 2);
+
+        // Isolated points still count.
+        let projection = nav_graph
+            .project_onto_graph(Vec3::new(0.0, 2.5, 0.0))
+            .unwrap();
+        assert_eq!((projection.from, projection.to, projection.t), (3, 3, 0.0));
+    }
+}
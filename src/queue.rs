@@ -0,0 +1,379 @@
+use std::{cmp::Reverse, collections::BinaryHeap, marker::PhantomData};
+
+use bevy_ecs::{component::Component, entity::Entity, system::Resource};
+use bevy_tasks::Task;
+use bevy_utils::{Duration, Instant};
+use futures_lite::future;
+
+use crate::{AutoTraveler, NavDomain, NavGraph};
+
+/// The order travelers waiting in a [`PathRequestQueue`] are planned in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PathRequestOrder {
+    /// In the order they were added.
+    #[default]
+    Arrival,
+    /// Travelers with the shortest straight line distance to their destination first, so the
+    /// cheapest searches get through while long ones wait.
+    ShortestFirst,
+    /// Travelers with the highest [`AutoTraveler::path_priority`](crate::AutoTraveler::path_priority)
+    /// first.
+    Priority,
+}
+
+/// Holds travelers waiting for their path to be computed.
+///
+/// New [`AutoTraveler`](crate::AutoTraveler)s are added to the queue, and each frame as many of
+/// them as fit the frame's budget are taken in `order` and planned. The budget is any
+/// combination of `max_per_frame` searches, `max_time_per_frame` spent searching and
+/// `max_expanded_per_frame` points expanded, where the last two always let at least one search
+/// through.
+/// Travelers which don't fit in the current frame stay queued, so a large burst of spawns is
+/// spread across several frames instead of stalling a single one, and
+/// [`PathPreviewRequest`](crate::PathPreviewRequest)s wait until there's budget left over. Ties are planned in the order
+/// travelers were added, and travelers waiting on their origin or destination go behind those
+/// they tie with.
+///
+/// With `asynchronous` set, searches run on the `AsyncComputeTaskPool` instead, against a
+/// snapshot of the graph which is only retaken when its
+/// [`topology_version`](crate::NavGraph::topology_version) changes, so they may see stale
+/// occupancy, and each traveler gets its path in the
+/// first frame after its search finishes. `max_per_frame` then limits how many searches are
+/// started each frame, and the time and expansion budgets don't apply. Travelers given a fixed
+/// path are still planned straight away, and [`PathWarmStart`](crate::PathWarmStart)s aren't
+/// used.
+///
+/// Each [`NavDomain`] has its own queue.
+#[derive(Debug, Resource)]
+pub struct PathRequestQueue<M: NavDomain = ()> {
+    /// The maximum number of paths computed per frame, or `None` for no limit.
+    pub max_per_frame: Option<usize>,
+    /// The time to spend searching for paths each frame, or `None` for no limit. Searches
+    /// aren't interrupted, so a frame may run over by up to one search.
+    pub max_time_per_frame: Option<Duration>,
+    /// The number of points searches may expand each frame, or `None` for no limit. Searches
+    /// aren't interrupted, so a frame may run over by up to one search.
+    pub max_expanded_per_frame: Option<usize>,
+    /// Only applies to travelers queued after it's set.
+    pub order: PathRequestOrder,
+    /// Whether to search for paths on the `AsyncComputeTaskPool`.
+    pub asynchronous: bool,
+    pub(crate) budget: PathBudget,
+    pending: BinaryHeap<Reverse<(u64, u64, Entity)>>,
+    next_sequence: u64,
+    marker: PhantomData<M>,
+}
+
+/// How much of a frame's budget a [`PathRequestQueue`] has used so far.
+#[derive(Debug)]
+pub(crate) struct PathBudget {
+    started: Instant,
+    pub searches: usize,
+    pub expanded: usize,
+}
+
+impl PathBudget {
+    fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            searches: 0,
+            expanded: 0,
+        }
+    }
+}
+
+impl<M: NavDomain> Default for PathRequestQueue<M> {
+    fn default() -> Self {
+        Self {
+            max_per_frame: None,
+            max_time_per_frame: None,
+            max_expanded_per_frame: None,
+            order: PathRequestOrder::default(),
+            asynchronous: false,
+            budget: PathBudget::new(),
+            pending: BinaryHeap::new(),
+            next_sequence: 0,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl PathRequestQueue {
+    pub fn new(max_per_frame: Option<usize>) -> Self {
+        Self {
+            max_per_frame,
+            ..Default::default()
+        }
+    }
+}
+
+impl<M: NavDomain> PathRequestQueue<M> {
+    /// Limits the time spent searching for paths each frame.
+    pub fn with_max_time_per_frame(mut self, max_time_per_frame: Duration) -> Self {
+        self.max_time_per_frame = Some(max_time_per_frame);
+        self
+    }
+
+    /// Limits the number of points searches for paths may expand each frame.
+    pub fn with_max_expanded_per_frame(mut self, max_expanded_per_frame: usize) -> Self {
+        self.max_expanded_per_frame = Some(max_expanded_per_frame);
+        self
+    }
+
+    pub fn with_order(mut self, order: PathRequestOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// The number of travelers still waiting for a path.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns true if no travelers are waiting for a path.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns true if `entity` is waiting for a path.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.pending
+            .iter()
+            .any(|Reverse((_, _, queued))| *queued == entity)
+    }
+
+    pub(crate) fn push(
+        &mut self,
+        entity: Entity,
+        auto_traveler: &AutoTraveler,
+        nav_graph: Option<&NavGraph<M>>,
+    ) {
+        let rank = match self.order {
+            PathRequestOrder::Arrival => 0,
+            PathRequestOrder::ShortestFirst => {
+                let origin = nav_graph.and_then(|g| g.get_nav_point(auto_traveler.origin));
+                let destination = auto_traveler.destination_position.or_else(|| {
+                    nav_graph?
+                        .get_nav_point(auto_traveler.destination)
+                        .map(|point| point.location())
+                });
+                let distance = match (origin, destination) {
+                    (Some(origin), Some(destination)) => origin.location().distance(destination),
+                    _ => f32::INFINITY,
+                };
+                // Bits of non-negative floats sort the same as the floats themselves.
+                distance.to_bits() as u64
+            }
+            PathRequestOrder::Priority => {
+                (i32::MAX as i64 - auto_traveler.path_priority as i64) as u64
+            }
+        };
+        self.pending
+            .push(Reverse((rank, self.next_sequence, entity)));
+        self.next_sequence += 1;
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<Entity> {
+        self.pending.pop().map(|Reverse((_, _, entity))| entity)
+    }
+
+    /// Starts keeping track of this frame's budget.
+    pub(crate) fn start_budget(&mut self) {
+        self.budget = PathBudget::new();
+    }
+
+    /// Whether there's budget left for another search this frame.
+    pub(crate) fn has_budget(&self) -> bool {
+        let budget = &self.budget;
+        self.max_per_frame.is_none_or(|max| budget.searches < max)
+            && (budget.searches == 0
+                || self
+                    .max_expanded_per_frame
+                    .is_none_or(|max| budget.expanded < max)
+                    && self
+                        .max_time_per_frame
+                        .is_none_or(|max| budget.started.elapsed() < max))
+    }
+}
+
+/// A path search running on the `AsyncComputeTaskPool` for a traveler, see
+/// [`PathRequestQueue::asynchronous`].
+#[derive(Component)]
+pub(crate) struct PendingPath {
+    pub task: Task<Option<Vec<u32>>>,
+    /// Whether the traveler's origin was occupied when the search was started.
+    pub claimed_origin: bool,
+}
+
+impl PendingPath {
+    /// Takes the result of the search, if it's finished.
+    pub fn poll(&mut self) -> Option<Option<Vec<u32>>> {
+        if !self.task.is_finished() {
+            return None;
+        }
+        future::block_on(future::poll_once(&mut self.task))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_math::Vec3;
+    use bevy_time::Time;
+
+    use super::*;
+    use crate::{
+        traveler::{compute_initial_path, TravelerPosition},
+        AutoTraveler, DestinationQueued, NavClock, NavGraph, NavPoint, NavigatorPlugin,
+        TravelFailed,
+    };
+
+    #[test]
+    pub fn test_overflow_is_planned_in_later_frames() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(0.0, 1.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .insert_resource(PathRequestQueue::new(Some(2)))
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .add_event::<DestinationQueued>()
+            .add_event::<TravelFailed>()
+            .add_system(compute_initial_path::<()>);
+        let travelers: Vec<Entity> = (0..3)
+            .map(|_| app.world.spawn(AutoTraveler::new(1, 2, 1.0)).id())
+            .collect();
+
+        let planned = |app: &App| {
+            travelers
+                .iter()
+                .filter(|e| app.world.get::<AutoTraveler>(**e).unwrap().path.is_some())
+                .count()
+        };
+
+        app.update();
+        assert_eq!(planned(&app), 2);
+        assert_eq!(app.world.resource::<PathRequestQueue>().len(), 1);
+
+        app.update();
+        assert_eq!(planned(&app), 3);
+        assert!(app.world.resource::<PathRequestQueue>().is_empty());
+    }
+
+    #[test]
+    pub fn test_async_pathfinding() {
+        let mut app = App::new();
+        app.add_plugin(NavigatorPlugin::new().with_async_pathfinding())
+            .insert_resource(Time::default());
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        for id in 1..=4 {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
+        }
+        for id in 1..4 {
+            nav_graph.connect_points(id, id + 1);
+        }
+        let traveler = app.world.spawn(AutoTraveler::new(1, 4, 1.0)).id();
+
+        app.update();
+        assert!(app.world.get::<PendingPath>(traveler).is_some());
+        for _ in 0..1000 {
+            if app.world.get::<TravelerPosition>(traveler).is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            app.update();
+        }
+        assert!(app.world.get::<PendingPath>(traveler).is_none());
+        assert_eq!(
+            app.world.get::<AutoTraveler>(traveler).unwrap().path,
+            Some(vec![1, 2, 3, 4])
+        );
+    }
+
+    #[test]
+    pub fn test_expansion_budget_plans_shortest_first() {
+        let mut nav_graph = NavGraph::new();
+        for id in 1..=10 {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 4));
+        }
+        for id in 1..10 {
+            nav_graph.connect_points(id, id + 1);
+        }
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .insert_resource(
+                PathRequestQueue::new(None)
+                    .with_max_expanded_per_frame(3)
+                    .with_order(PathRequestOrder::ShortestFirst),
+            )
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .add_event::<DestinationQueued>()
+            .add_event::<TravelFailed>()
+            .add_system(compute_initial_path::<()>);
+        let far = app.world.spawn(AutoTraveler::new(1, 10, 1.0)).id();
+        let middle = app.world.spawn(AutoTraveler::new(1, 5, 1.0)).id();
+        let near = app.world.spawn(AutoTraveler::new(1, 2, 1.0)).id();
+        let planned = |app: &App, entity| {
+            app.world
+                .get::<AutoTraveler>(entity)
+                .unwrap()
+                .path
+                .is_some()
+        };
+
+        // Neighbors don't need a search, and the next traveler uses up the budget.
+        app.update();
+        assert!(planned(&app, near) && planned(&app, middle));
+        assert!(!planned(&app, far));
+
+        app.update();
+        assert!(planned(&app, far));
+    }
+
+    #[test]
+    pub fn test_travelers_waiting_on_quota_use_no_budget() {
+        let mut nav_graph = NavGraph::new();
+        for id in 1..=3 {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 4));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+        nav_graph.set_destination_quota(3, Some(0));
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .insert_resource(PathRequestQueue::new(Some(1)).with_order(PathRequestOrder::Priority))
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .add_event::<DestinationQueued>()
+            .add_event::<TravelFailed>()
+            .add_system(compute_initial_path::<()>);
+        let blocked = app
+            .world
+            .spawn(AutoTraveler::new(1, 3, 1.0).with_path_priority(10))
+            .id();
+        let others: Vec<Entity> = (0..2)
+            .map(|_| app.world.spawn(AutoTraveler::new(1, 2, 1.0)).id())
+            .collect();
+        let planned = |app: &App, entity| {
+            app.world
+                .get::<AutoTraveler>(entity)
+                .unwrap()
+                .path
+                .is_some()
+        };
+
+        // The blocked traveler stays at the head of the queue without holding up the rest.
+        for (frame, &other) in others.iter().enumerate() {
+            app.update();
+            assert!(planned(&app, other));
+            assert_eq!(app.world.resource::<PathRequestQueue>().len(), 2 - frame);
+        }
+        assert!(!planned(&app, blocked));
+    }
+}
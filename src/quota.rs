@@ -0,0 +1,91 @@
+#[cfg(feature = "travelers")]
+use bevy_ecs::component::Component;
+#[cfg(feature = "travelers")]
+use bevy_reflect::{FromReflect, Reflect};
+
+use crate::{NavDomain, NavGraph};
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Limits how many travelers may head for the specified point at once, or lifts the limit
+    /// with `None`, e.g. for workstations or service points which serve one visitor at a time.
+    ///
+    /// This is separate from the point's max_occupancy: it counts the travelers which have the
+    /// point as their destination, wherever they are. Travelers beyond the quota wait to be
+    /// planned, in the order they were added, with an [`AwaitingDestination`], and
+    /// [`DestinationQueued`](crate::DestinationQueued) is sent. A slot frees up when a traveler
+    /// holding one arrives, is removed, or heads somewhere else.
+    ///
+    /// The quota is checked when a traveler is planned, so travelers already on their way when
+    /// it's set or lowered keep going, as do the later legs of
+    /// [`AutoTraveler::then_visit`](crate::AutoTraveler::then_visit).
+    pub fn set_destination_quota(&mut self, id: u32, quota: Option<u32>) {
+        if !self.has_nav_point(id) {
+            return;
+        }
+        match quota {
+            Some(quota) => self.destination_quotas.insert(id, quota),
+            None => self.destination_quotas.remove(&id),
+        };
+    }
+
+    /// How many travelers may head for the specified point at once, if limited.
+    pub fn destination_quota(&self, id: u32) -> Option<u32> {
+        self.destination_quotas.get(&id).copied()
+    }
+}
+
+/// Marks a traveler waiting to be planned until a slot of its destination's quota frees up. See
+/// [`NavGraph::set_destination_quota`].
+#[cfg(feature = "travelers")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Component, Reflect, FromReflect)]
+pub struct AwaitingDestination;
+
+#[cfg(all(test, feature = "travelers"))]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::event::Events;
+    use bevy_math::Vec3;
+    use bevy_time::Time;
+    use bevy_transform::prelude::Transform;
+
+    use super::*;
+    use crate::{AutoTraveler, DestinationQueued, NavPoint, NavigatorPlugin};
+
+    #[test]
+    pub fn test_destination_quota_queues_travelers() {
+        let mut app = App::new();
+        app.add_plugin(NavigatorPlugin::new())
+            .insert_resource(Time::default());
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 4));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 4));
+        nav_graph.connect_points(1, 2);
+        nav_graph.set_destination_quota(2, Some(1));
+
+        let mut spawn = || {
+            app.world
+                .spawn((AutoTraveler::new(1, 2, 1.0), Transform::default()))
+                .id()
+        };
+        let (first, second) = (spawn(), spawn());
+        app.update();
+
+        let planned = |app: &App, entity| {
+            app.world
+                .get::<AutoTraveler>(entity)
+                .is_some_and(|auto_traveler| auto_traveler.path.is_some())
+        };
+        assert!(planned(&app, first));
+        assert!(!planned(&app, second));
+        assert!(app.world.get::<AwaitingDestination>(second).is_some());
+        let queued = app.world.resource::<Events<DestinationQueued>>();
+        let queued: Vec<_> = queued.get_reader().iter(queued).copied().collect();
+        assert_eq!(queued, [DestinationQueued::new(second, 2)]);
+
+        // The first traveler giving up its slot lets the second one go.
+        app.world.entity_mut(first).remove::<AutoTraveler>();
+        app.update();
+        assert!(planned(&app, second));
+        assert!(app.world.get::<AwaitingDestination>(second).is_none());
+    }
+}
@@ -0,0 +1,137 @@
+use bevy_math::Vec3;
+
+use crate::{NavDomain, NavGraph};
+
+/// How far from the ray, relative to the length of the connection leading to it, a point may be
+/// and still count as on the ray. Loose enough to follow diagonal rays across a grid.
+const RAY_TOLERANCE: f32 = 0.75;
+
+/// What stopped a [`NavGraph::raycast`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaycastHit {
+    /// The ray reaches this point, but it's full.
+    Full(u32),
+    /// The ray reaches this point, but the connection onto it is closed.
+    Closed(u32),
+    /// The graph ends just past this point, whether at its edge or at a hole.
+    Missing(u32),
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Walks the graph along a ray, returning the first point which blocks it within `max_dist`,
+    /// or `None` if the way is clear.
+    ///
+    /// This approximates the level's geometry with the nav data for quick line-of-sight checks.
+    /// The ray starts from the point nearest to `from`, which never blocks it, and follows
+    /// whichever connection stays closest to the ray. Where no connection carries on in its
+    /// direction, the graph is missing there.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint, RaycastHit};
+    /// let mut nav_graph = NavGraph::new();
+    /// for id in 1..=5 {
+    ///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// for id in 1..=3 {
+    ///     nav_graph.connect_points(id, id + 1);
+    /// }
+    /// nav_graph.occupy(3);
+    ///
+    /// assert_eq!(nav_graph.raycast(Vec3::new(1.0, 0.0, 0.0), Vec3::X, 1.5), None);
+    /// assert_eq!(
+    ///     nav_graph.raycast(Vec3::new(1.0, 0.0, 0.0), Vec3::X, 10.0),
+    ///     Some(RaycastHit::Full(3))
+    /// );
+    /// // Point 5 isn't connected, so the graph ends at 4.
+    /// assert_eq!(
+    ///     nav_graph.raycast(Vec3::new(3.0, 0.0, 0.0), Vec3::X, 10.0),
+    ///     Some(RaycastHit::Missing(4))
+    /// );
+    /// ```
+    pub fn raycast(&self, from: Vec3, dir: Vec3, max_dist: f32) -> Option<RaycastHit> {
+        let dir = dir.normalize_or_zero();
+        if dir == Vec3::ZERO {
+            return None;
+        }
+        let mut current = self.points.get(&self.nearest_point(from)?)?;
+        let mut along = (current.location() - from).dot(dir);
+
+        loop {
+            let next = current
+                .connections
+                .iter()
+                .filter_map(|id| {
+                    let point = &self.points[id];
+                    let offset = point.location() - from;
+                    let next_along = offset.dot(dir);
+                    let off_ray = (offset - dir * next_along).length();
+                    let step = point.location().distance(current.location());
+                    (next_along > along && off_ray <= step * RAY_TOLERANCE)
+                        .then_some((point, next_along, off_ray))
+                })
+                .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+
+            let Some((next, next_along, _)) = next else {
+                return Some(RaycastHit::Missing(current.id()));
+            };
+            if next_along > max_dist {
+                return None;
+            }
+            if self
+                .edge(current.id(), next.id())
+                .is_some_and(|edge| edge.closed)
+            {
+                return Some(RaycastHit::Closed(next.id()));
+            }
+            if !next.can_occupy() {
+                return Some(RaycastHit::Full(next.id()));
+            }
+            current = next;
+            along = next_along;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WalkabilityMap;
+
+    #[test]
+    pub fn test_raycast_across_grid() {
+        #[rustfmt::skip]
+        let walkable = [
+            true, true, true, true, true,
+            true, true, true, false, true,
+            true, true, true, true, true,
+            true, true, true, true, true,
+        ];
+        let mut nav_graph = NavGraph::new();
+        let map = WalkabilityMap::new(5, 4, &walkable).with_diagonals();
+        let first = nav_graph.add_walkability_map(&map).unwrap();
+        let id = |x: u32, y: u32| first + y * 5 + x;
+
+        // Diagonally across the grid, clear of the hole.
+        let diagonal = Vec3::new(1.0, 1.0, 0.0);
+        assert_eq!(nav_graph.raycast(Vec3::ZERO, diagonal, 3.0), None);
+        nav_graph.occupy(id(3, 3));
+        assert_eq!(
+            nav_graph.raycast(Vec3::ZERO, diagonal, 10.0),
+            Some(RaycastHit::Full(id(3, 3)))
+        );
+
+        // Straight into the hole.
+        assert_eq!(
+            nav_graph.raycast(Vec3::new(0.0, 1.0, 0.0), Vec3::X, 10.0),
+            Some(RaycastHit::Missing(id(2, 1)))
+        );
+
+        nav_graph.set_connection_closed(id(0, 3), id(1, 3), true);
+        assert_eq!(
+            nav_graph.raycast(Vec3::new(0.0, 3.0, 0.0), Vec3::X, 10.0),
+            Some(RaycastHit::Closed(id(1, 3)))
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use bevy_ecs::component::Component;
+#[cfg(feature = "travelers")]
+use bevy_ecs::system::{Query, Res};
+#[cfg(feature = "travelers")]
+use bevy_time::Time;
+
+use crate::{NavDomain, NavGraph, SearchOptions};
+
+/// A point expanded by a search, with its scores at the time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpandedNode {
+    pub id: u32,
+    /// The cost of the best path found to the point so far.
+    pub g: u32,
+    /// `g` plus the heuristic's estimate of the cost from the point to the destination.
+    pub f: u32,
+}
+
+/// Every point a search expanded, in order, along with its result. See
+/// [`NavGraph::find_path_recorded`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SearchRecording {
+    pub expansions: Vec<ExpandedNode>,
+    pub path: Option<Vec<u32>>,
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Computes a path like [`NavGraph::find_path_with`], recording every point the search
+    /// expands along the way, for visualizing and tuning searches on real maps.
+    ///
+    /// Trivial searches, such as from a point to itself, expand nothing.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint, SearchOptions};
+    /// let mut nav_graph = NavGraph::new();
+    /// for id in 1..=3 {
+    ///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    ///
+    /// let recording = nav_graph.find_path_recorded(1, 3, &SearchOptions::default());
+    /// assert_eq!(recording.path.unwrap()[..], [1, 2, 3]);
+    /// let expanded: Vec<u32> = recording.expansions.iter().map(|node| node.id).collect();
+    /// assert_eq!(expanded, [1, 2, 3]);
+    /// ```
+    pub fn find_path_recorded(&self, a: u32, b: u32, options: &SearchOptions) -> SearchRecording {
+        let mut expansions = Vec::new();
+        let path = self
+            .observe(
+                a,
+                b,
+                || self.search_recorded(a, b, options, Some(&mut expansions), None, &mut 0),
+                |(path, cost)| (path.path().len(), Some(*cost)),
+            )
+            .map(|(path, _)| path.into_path());
+        SearchRecording { expansions, path }
+    }
+}
+
+/// Plays back a [`SearchRecording`] over time, for debug drawing to animate.
+///
+/// Each frame, [`ExpansionPlayback::shown`] grows by `nodes_per_second` worth of expansions, in
+/// the order the search made them.
+#[derive(Debug, Clone, Component)]
+pub struct ExpansionPlayback {
+    pub recording: SearchRecording,
+    pub nodes_per_second: f32,
+    pub elapsed: f32,
+}
+
+impl ExpansionPlayback {
+    pub fn new(recording: SearchRecording, nodes_per_second: f32) -> Self {
+        Self {
+            recording,
+            nodes_per_second,
+            elapsed: 0.0,
+        }
+    }
+
+    /// The expansions played so far.
+    pub fn shown(&self) -> &[ExpandedNode] {
+        let count = (self.elapsed * self.nodes_per_second) as usize;
+        &self.recording.expansions[..count.min(self.recording.expansions.len())]
+    }
+
+    /// Returns true once every expansion has been played.
+    pub fn is_finished(&self) -> bool {
+        self.shown().len() == self.recording.expansions.len()
+    }
+
+    /// Starts playing from the beginning again.
+    pub fn restart(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+
+#[cfg(feature = "travelers")]
+pub(crate) fn advance_expansion_playback<M: NavDomain>(
+    mut playback_query: Query<&mut ExpansionPlayback, M::Filter>,
+    time: Res<Time>,
+) {
+    for mut playback in playback_query.iter_mut() {
+        if !playback.is_finished() {
+            playback.elapsed += time.delta_seconds();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "travelers"))]
+mod tests {
+    use bevy_app::App;
+    use bevy_math::Vec3;
+    use bevy_utils::{Duration, Instant};
+
+    use super::*;
+    use crate::NavPoint;
+
+    #[test]
+    pub fn test_playback_reveals_expansions_in_order() {
+        let mut nav_graph = NavGraph::new();
+        for (id, x, y) in [(1, 0.0, 0.0), (2, 1.0, 0.0), (3, 2.0, 0.0), (4, 1.0, 1.0)] {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 1));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+        nav_graph.connect_points(1, 4);
+        nav_graph.connect_points(4, 3);
+        let recording = nav_graph.find_path_recorded(1, 3, &SearchOptions::default());
+        assert_eq!(recording.expansions.first().unwrap().id, 1);
+        assert_eq!(recording.expansions.last().unwrap().id, 3);
+
+        let mut app = App::new();
+        app.insert_resource(Time::default())
+            .add_system(advance_expansion_playback::<()>);
+        let entity = app
+            .world
+            .spawn(ExpansionPlayback::new(recording.clone(), 2.0))
+            .id();
+        let start = Instant::now();
+        app.world.resource_mut::<Time>().update_with_instant(start);
+        app.world
+            .resource_mut::<Time>()
+            .update_with_instant(start + Duration::from_millis(500));
+        app.update();
+
+        let playback = app.world.get::<ExpansionPlayback>(entity).unwrap();
+        assert_eq!(playback.shown(), &recording.expansions[..1]);
+        assert!(!playback.is_finished());
+    }
+}
@@ -0,0 +1,171 @@
+use bevy_math::Vec3;
+use bevy_utils::HashMap;
+
+use crate::{memory::map_bytes, NavDomain, NavGraph};
+
+/// Precomputed path costs between one representative [`NavPoint`](crate::NavPoint) per region.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct RegionCostCache {
+    topology_version: Option<u64>,
+    representatives: HashMap<u32, u32>,
+    costs: HashMap<(u32, u32), u32>,
+}
+
+impl RegionCostCache {
+    pub fn memory_usage(&self) -> usize {
+        map_bytes(&self.representatives) + map_bytes(&self.costs)
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.representatives.shrink_to_fit();
+        self.costs.shrink_to_fit();
+    }
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Groups points into regions by which cell of a grid of `cell_size` they fall in.
+    ///
+    /// Regions are numbered from 0 in no particular order. Use [`NavPoint::set_region`](crate::NavPoint::set_region)
+    /// through [`NavGraph::modify`] to assign regions by hand instead, e.g. one per room.
+    pub fn assign_grid_regions(&mut self, cell_size: f32) {
+        let mut cells = HashMap::<(i32, i32, i32), u32>::new();
+        for point in self.points.values_mut() {
+            let cell = (point.location() / cell_size).floor();
+            let next_region = cells.len() as u32;
+            let region = *cells
+                .entry((cell.x as i32, cell.y as i32, cell.z as i32))
+                .or_insert(next_region);
+            point.set_region(region);
+        }
+        // Region changes don't move anything, but do invalidate the region cost cache.
+        self.topology_changed();
+    }
+
+    /// Rebuilds the cache used by [`NavGraph::estimate_cost`], if the graph has changed since it
+    /// was last built.
+    ///
+    /// Each region's representative is the point nearest its center, and costs are computed
+    /// between every pair of representatives, so this is expensive for graphs with many regions.
+    pub fn refresh_region_costs(&mut self) {
+        if self.region_costs.topology_version == Some(self.topology_version()) {
+            return;
+        }
+
+        let mut centers = HashMap::<u32, (Vec3, u32)>::new();
+        for point in self.points.values() {
+            let center = centers.entry(point.region()).or_insert((Vec3::ZERO, 0));
+            center.0 += point.location();
+            center.1 += 1;
+        }
+
+        let mut representatives = HashMap::<u32, (u32, f32)>::new();
+        for point in self.points.values() {
+            let (sum, count) = centers[&point.region()];
+            let dist = point.location().distance_squared(sum / count as f32);
+            let best = representatives
+                .entry(point.region())
+                .or_insert((point.id(), f32::MAX));
+            if dist < best.1 {
+                *best = (point.id(), dist);
+            }
+        }
+
+        let mut costs = HashMap::new();
+        for (region, (representative, _)) in &representatives {
+            let reached = self.cost_map(*representative, u32::MAX, None);
+            for (other_region, (other, _)) in &representatives {
+                if let Some(cost) = reached.get(other) {
+                    costs.insert((*region, *other_region), *cost);
+                }
+            }
+        }
+
+        self.region_costs = RegionCostCache {
+            topology_version: Some(self.topology_version()),
+            representatives: representatives
+                .into_iter()
+                .map(|(region, (id, _))| (region, id))
+                .collect(),
+            costs,
+        };
+    }
+
+    /// Roughly estimates the cost of travelling from `a` to `b`, for decisions which only need to
+    /// know "roughly how far".
+    ///
+    /// For points in different regions, this is the precomputed cost between the regions'
+    /// representatives, answered in constant time. For points in the same region, or if the
+    /// cache is out of date (see [`NavGraph::refresh_region_costs`]), it falls back to an exact
+    /// search. Occupancy is ignored by the cached costs but not by the fallback.
+    ///
+    /// Returns `None` if `b` can't be reached from `a`.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// for i in 0..20 {
+    ///     nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+    ///     if i > 0 {
+    ///         nav_graph.connect_points(i - 1, i);
+    ///     }
+    /// }
+    /// nav_graph.assign_grid_regions(5.0);
+    /// nav_graph.refresh_region_costs();
+    ///
+    /// let exact = nav_graph.path_cost(&nav_graph.find_path(1, 18).unwrap()).unwrap();
+    /// let estimate = nav_graph.estimate_cost(1, 18).unwrap();
+    /// assert!(estimate.abs_diff(exact) <= exact / 2);
+    /// ```
+    pub fn estimate_cost(&self, a: u32, b: u32) -> Option<u32> {
+        let (a_point, b_point) = (self.get_nav_point(a)?, self.get_nav_point(b)?);
+        let cache = &self.region_costs;
+        if cache.topology_version == Some(self.topology_version())
+            && a_point.region() != b_point.region()
+        {
+            let key = (a_point.region(), b_point.region());
+            return cache.costs.get(&key).copied();
+        }
+
+        self.path_cost(&self.find_path(a, b)?)
+    }
+
+    /// Returns the representative point of `region` in the region cost cache, if it's been
+    /// built.
+    pub fn region_representative(&self, region: u32) -> Option<u32> {
+        self.region_costs.representatives.get(&region).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NavPoint;
+
+    #[test]
+    pub fn test_region_costs_follow_topology() {
+        let mut nav_graph = NavGraph::new();
+        for i in 0..4 {
+            nav_graph.add_nav_point(NavPoint::new(
+                i,
+                Vec3::new(i as f32 * 2.0, 0.0, 0.0),
+                1.0,
+                1,
+            ));
+        }
+        nav_graph.connect_points(0, 1);
+        nav_graph.connect_points(2, 3);
+        nav_graph.assign_grid_regions(3.0);
+        nav_graph.refresh_region_costs();
+
+        // The regions aren't connected yet.
+        assert_eq!(nav_graph.estimate_cost(0, 3), None);
+
+        // Changing the graph makes estimates fall back to exact searches until refreshed.
+        nav_graph.connect_points(1, 2);
+        assert!(nav_graph.estimate_cost(0, 3).is_some());
+        nav_graph.refresh_region_costs();
+        assert!(nav_graph.estimate_cost(0, 3).is_some());
+    }
+}
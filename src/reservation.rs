@@ -0,0 +1,335 @@
+use bevy_ecs::entity::Entity;
+#[cfg(feature = "travelers")]
+use bevy_ecs::{
+    component::Component,
+    system::{Query, RemovedComponents, Res},
+};
+use bevy_reflect::{FromReflect, Reflect};
+#[cfg(feature = "travelers")]
+use bevy_time::Time;
+
+#[cfg(feature = "travelers")]
+use crate::{graphs::TravelerGraphsMut, AutoTraveler, NavGraphId};
+use crate::{NavDomain, NavGraph};
+
+/// A claim on a [`NavPoint`](crate::NavPoint) by a group of travelers, such as a convoy.
+///
+/// While reserved, the point can only be pathed through and occupied by members of the group.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, FromReflect)]
+pub struct CorridorReservation {
+    pub group: u32,
+    /// The time the reservation lapses, in seconds since app startup.
+    pub expires_at: f64,
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Reserves every point in `nodes` for `group` until `expires_at` (in seconds since startup).
+    ///
+    /// Members of other groups, and travelers without a group, can neither path through nor
+    /// occupy the reserved points until the reservation is released or expires. This lets a
+    /// convoy pass through a narrow shared section without interleaving with other traffic.
+    ///
+    /// Returns false, reserving nothing, if any of the points is reserved by another group or
+    /// doesn't exist. Points already reserved by `group` have their expiry updated.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint, SearchOptions};
+    /// let mut nav_graph = NavGraph::new();
+    /// for i in 1..=3 {
+    ///     nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    ///
+    /// assert!(nav_graph.reserve_corridor(7, &[2], 10.0));
+    ///
+    /// assert!(nav_graph.find_path(1, 3).is_none());
+    /// assert!(nav_graph.find_path_with(1, 3, &SearchOptions::new().for_group(7)).is_some());
+    ///
+    /// nav_graph.release_corridor(7);
+    /// assert!(nav_graph.find_path(1, 3).is_some());
+    /// ```
+    pub fn reserve_corridor(&mut self, group: u32, nodes: &[u32], expires_at: f64) -> bool {
+        let available = nodes
+            .iter()
+            .all(|id| self.has_nav_point(*id) && !self.is_reserved_against(*id, Some(group)));
+        if !available {
+            return false;
+        }
+
+        for id in nodes {
+            self.corridor_reservations
+                .insert(*id, CorridorReservation { group, expires_at });
+        }
+        true
+    }
+
+    /// Releases all points reserved by `group`.
+    pub fn release_corridor(&mut self, group: u32) {
+        self.corridor_reservations
+            .retain(|_, reservation| reservation.group != group);
+    }
+
+    /// Releases all reservations which expire at or before `now`.
+    pub fn expire_corridor_reservations(&mut self, now: f64) {
+        self.corridor_reservations
+            .retain(|_, reservation| reservation.expires_at > now);
+    }
+
+    /// Returns the reservation on the specified point, if any.
+    pub fn corridor_reservation(&self, id: u32) -> Option<&CorridorReservation> {
+        self.corridor_reservations.get(&id)
+    }
+
+    /// Returns true if the specified point is reserved by a group other than `group`.
+    #[inline(always)]
+    pub fn is_reserved_against(&self, id: u32, group: Option<u32>) -> bool {
+        if self.corridor_reservations.is_empty() {
+            return false;
+        }
+        self.corridor_reservations
+            .get(&id)
+            .map(|reservation| Some(reservation.group) != group)
+            .unwrap_or(false)
+    }
+
+    /// Like [`NavGraph::occupy`], but also fails if the point is reserved by a group other than
+    /// `group`.
+    pub fn occupy_as(&mut self, id: u32, group: Option<u32>) -> bool {
+        self.occupy_as_weighted(id, group, 1)
+    }
+
+    /// Like [`NavGraph::occupy_as`], but takes up `weight` slots at once. See
+    /// [`NavGraph::occupy_weighted`].
+    pub fn occupy_as_weighted(&mut self, id: u32, group: Option<u32>, weight: u32) -> bool {
+        !self.is_reserved_against(id, group) && self.occupy_weighted(id, weight)
+    }
+
+    /// Reserves the point `id` for `entity`, e.g. a traveler about to pass through it, so that
+    /// other travelers won't step onto it in the meantime.
+    ///
+    /// Unlike occupying it, reserving a point takes up none of its occupancy, and unlike a
+    /// corridor reservation, searches still path through it; it only holds other travelers back
+    /// when they're about to move onto it. See [`PathReservation`].
+    ///
+    /// Returns false if the point doesn't exist or is reserved for another entity.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_ecs::entity::Entity;
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::ZERO, 1.0, 1));
+    /// let (a, b) = (Entity::from_raw(1), Entity::from_raw(2));
+    ///
+    /// assert!(nav_graph.reserve_point(1, a));
+    /// assert!(!nav_graph.reserve_point(1, b));
+    /// assert!(nav_graph.is_point_reserved_against(1, b));
+    /// assert!(nav_graph.can_occupy(1));
+    ///
+    /// assert!(nav_graph.release_point(1, a));
+    /// assert!(nav_graph.reserve_point(1, b));
+    /// ```
+    pub fn reserve_point(&mut self, id: u32, entity: Entity) -> bool {
+        if !self.has_nav_point(id) {
+            return false;
+        }
+        *self.point_reservations.entry(id).or_insert(entity) == entity
+    }
+
+    /// Releases the point `id` if it's reserved for `entity`. Returns false if it wasn't.
+    pub fn release_point(&mut self, id: u32, entity: Entity) -> bool {
+        if self.point_reservations.get(&id) != Some(&entity) {
+            return false;
+        }
+        self.point_reservations.remove(&id);
+        true
+    }
+
+    /// Releases every point reserved for `entity`.
+    pub fn release_points_of(&mut self, entity: Entity) {
+        self.point_reservations
+            .retain(|_, reserved_for| *reserved_for != entity);
+    }
+
+    /// The entity the point `id` is reserved for, if any. See [`NavGraph::reserve_point`].
+    pub fn point_reserved_for(&self, id: u32) -> Option<Entity> {
+        self.point_reservations.get(&id).copied()
+    }
+
+    /// Returns true if the point `id` is reserved for an entity other than `entity`.
+    #[inline(always)]
+    pub fn is_point_reserved_against(&self, id: u32, entity: Entity) -> bool {
+        if self.point_reservations.is_empty() {
+            return false;
+        }
+        self.point_reservations
+            .get(&id)
+            .is_some_and(|reserved_for| *reserved_for != entity)
+    }
+}
+
+/// Has a traveler reserve the next `ahead` points of its path, releasing each as it passes, so
+/// travelers coming the other way wait at the entrance of a corridor rather than meeting inside
+/// it. See [`NavGraph::reserve_point`].
+///
+/// Points are reserved in path order, stopping at the first reserved for another traveler.
+/// Travelers don't move onto points reserved for others, and are blocked there as they are at
+/// full points, according to their [`BlockedBehavior`](crate::BlockedBehavior).
+#[cfg(feature = "travelers")]
+#[derive(Debug, Clone, Component, Reflect, FromReflect)]
+pub struct PathReservation {
+    pub ahead: usize,
+    reserved: Vec<u32>,
+    /// The graph `reserved` is on.
+    graph: Option<NavGraphId>,
+}
+
+#[cfg(feature = "travelers")]
+impl PathReservation {
+    pub fn new(ahead: usize) -> Self {
+        Self {
+            ahead,
+            reserved: Vec::new(),
+            graph: None,
+        }
+    }
+
+    /// The points currently reserved, in path order.
+    pub fn reserved(&self) -> &[u32] {
+        &self.reserved
+    }
+}
+
+#[cfg(feature = "travelers")]
+type ReservingTravelerQuery<'w, 's, M> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        Option<&'static AutoTraveler>,
+        &'static mut PathReservation,
+    ),
+    <M as NavDomain>::Filter,
+>;
+
+#[cfg(feature = "travelers")]
+pub(crate) fn reserve_paths_ahead<M: NavDomain>(
+    mut travelers_query: ReservingTravelerQuery<M>,
+    removed: RemovedComponents<PathReservation>,
+    mut graphs: TravelerGraphsMut<M>,
+) {
+    for entity in removed.iter() {
+        for (_, nav_graph) in graphs.iter_mut() {
+            nav_graph.release_points_of(entity);
+        }
+    }
+
+    for (entity, auto_traveler, mut reservation) in travelers_query.iter_mut() {
+        let graph = auto_traveler.map_or(reservation.graph, |auto_traveler| auto_traveler.graph);
+        if graph != reservation.graph {
+            if let Some(nav_graph) = graphs.get_mut(reservation.graph) {
+                nav_graph.release_points_of(entity);
+            }
+            reservation.reserved.clear();
+            reservation.graph = graph;
+        }
+        let Some(nav_graph) = graphs.get_mut(graph) else {
+            continue;
+        };
+        let ahead = auto_traveler
+            .and_then(|auto_traveler| {
+                let path = auto_traveler.path.as_ref()?;
+                path.get(auto_traveler.current_index + 1..)
+            })
+            .unwrap_or_default();
+        let ahead = &ahead[..ahead.len().min(reservation.ahead)];
+        if reservation.reserved == ahead {
+            continue;
+        }
+
+        let mut reserved = std::mem::take(&mut reservation.reserved);
+        for id in reserved.drain(..) {
+            if !ahead.contains(&id) {
+                nav_graph.release_point(id, entity);
+            }
+        }
+        for id in ahead {
+            if !nav_graph.reserve_point(*id, entity) {
+                break;
+            }
+            reserved.push(*id);
+        }
+        reservation.reserved = reserved;
+    }
+}
+
+#[cfg(feature = "travelers")]
+pub(crate) fn expire_corridor_reservations<M: NavDomain>(
+    mut graphs: TravelerGraphsMut<M>,
+    time: Res<Time>,
+) {
+    for (_, nav_graph) in graphs.iter_mut() {
+        if !nav_graph.corridor_reservations.is_empty() {
+            nav_graph.expire_corridor_reservations(time.elapsed_seconds_f64());
+        }
+    }
+}
+
+#[cfg(all(test, feature = "travelers"))]
+mod tests {
+    use bevy_app::App;
+    use bevy_transform::prelude::Transform;
+    use bevy_utils::Duration;
+
+    use super::*;
+    use crate::{traveler::TravelerPosition, GridConnectivity, NavigatorPlugin};
+
+    #[test]
+    pub fn test_path_reservation_holds_others_back() {
+        let mut app = App::new();
+        app.add_plugin(NavigatorPlugin::new())
+            .insert_resource(Time::default());
+        let (nav_graph, _) = NavGraph::<()>::from_grid(5, 1, 1.0, GridConnectivity::Four);
+        app.insert_resource(nav_graph);
+
+        let slow = app
+            .world
+            .spawn((
+                Transform::default(),
+                AutoTraveler::new(1, 5, 0.1),
+                PathReservation::new(3),
+            ))
+            .id();
+        app.update();
+        let nav_graph = app.world.resource::<NavGraph>();
+        assert_eq!(nav_graph.point_reserved_for(4), Some(slow));
+        assert_eq!(nav_graph.point_reserved_for(5), None);
+
+        // Point 4 is free but reserved, so the fast traveler waits at 5.
+        let fast = app
+            .world
+            .spawn((
+                Transform::from_xyz(4.0, 0.0, 0.0),
+                AutoTraveler::new(5, 4, 10.0),
+            ))
+            .id();
+        let start = app.world.resource::<Time>().startup();
+        for seconds in [0.0, 1.0, 2.0] {
+            app.world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_secs_f32(seconds));
+            app.update();
+        }
+        let position = app.world.get::<TravelerPosition>(fast).unwrap();
+        assert_eq!(position.current_nav_point, 5);
+        assert_eq!(position.next_nav_point, None);
+
+        app.world.entity_mut(slow).remove::<PathReservation>();
+        app.update();
+        assert_eq!(app.world.resource::<NavGraph>().point_reserved_for(4), None);
+    }
+}
@@ -0,0 +1,146 @@
+use bevy_ecs::{
+    entity::Entity,
+    event::EventWriter,
+    system::{Commands, Query, Res},
+};
+
+use crate::{
+    graphs::TravelerGraphsMut, lod::LowFidelity, traveler::TravelerPosition, AutoTraveler,
+    NavClock, NavDomain, Replan, ReservationRevoked,
+};
+
+type ReservingTravelerQuery<'w, 's, M> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static AutoTraveler,
+        &'static mut TravelerPosition,
+        Option<&'static LowFidelity>,
+    ),
+    <M as NavDomain>::Filter,
+>;
+
+/// Takes back the points travelers are moving onto once they can no longer hold them, sending
+/// [`ReservationRevoked`].
+///
+/// Only points the graph flagged since the last run are checked. A point over its max_occupancy
+/// is taken back from the travelers moving onto it, lowest entity first, until it fits again;
+/// travelers already standing on it keep their place.
+pub(crate) fn revoke_reservations<M: NavDomain>(
+    mut travelers_query: ReservingTravelerQuery<M>,
+    mut graphs: TravelerGraphsMut<M>,
+    clock: Res<NavClock>,
+    mut revoked_events: EventWriter<ReservationRevoked<M>>,
+    mut commands: Commands,
+) {
+    for (graph, nav_graph) in graphs.iter_mut() {
+        if nav_graph.revocation_candidates.is_empty() {
+            continue;
+        }
+        let candidates = std::mem::take(&mut nav_graph.revocation_candidates);
+
+        let mut reserving: Vec<(u32, Entity)> = travelers_query
+            .iter()
+            .filter(|(_, auto_traveler, _, _)| auto_traveler.graph == graph)
+            .filter_map(|(entity, _, traveler_position, _)| {
+                let next = traveler_position.next_nav_point?;
+                candidates.contains(&next).then_some((next, entity))
+            })
+            .collect();
+        reserving.sort();
+
+        let mut revoked: Vec<ReservationRevoked<M>> = Vec::new();
+        for (node, entity) in reserving {
+            let Ok((_, auto_traveler, mut traveler_position, low_fidelity)) =
+                travelers_query.get_mut(entity)
+            else {
+                continue;
+            };
+            let current = traveler_position.current_nav_point;
+            let (usable, over_occupied) = match nav_graph.get_nav_point(node) {
+                Some(point) => (
+                    point.connections().contains(&current)
+                        && nav_graph.is_open(current, node, clock.now),
+                    point.current_occupancy() > point.max_occupancy(),
+                ),
+                None => (false, false),
+            };
+            if usable && (!over_occupied || low_fidelity.is_some()) {
+                continue;
+            }
+
+            if low_fidelity.is_none() {
+                nav_graph.unoccupy_weighted(node, auto_traveler.occupancy_weight);
+            }
+            traveler_position.next_nav_point = None;
+            if !auto_traveler.fixed_path {
+                commands.entity(entity).insert(Replan);
+            }
+            match revoked.last_mut() {
+                Some(event) if event.node == node => event.entities.push(entity),
+                _ => revoked.push(ReservationRevoked::new(node, vec![entity])),
+            }
+        }
+        revoked_events.send_batch(revoked);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::event::Events;
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::{NavGraph, NavPoint};
+
+    #[test]
+    pub fn test_lowered_capacity_revokes_reservations() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 3));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 3));
+        nav_graph.connect_points(1, 2);
+
+        let mut app = App::new();
+        app.init_resource::<NavClock>()
+            .add_event::<ReservationRevoked>()
+            .add_system(revoke_reservations::<()>);
+        let mut spawn = |nav_graph: &mut NavGraph| {
+            nav_graph.occupy(1);
+            nav_graph.occupy(2);
+            let mut auto_traveler = AutoTraveler::new(1, 2, 1.0);
+            auto_traveler.path = Some(vec![1, 2]);
+            app.world
+                .spawn((
+                    auto_traveler,
+                    TravelerPosition {
+                        current_nav_point: 1,
+                        next_nav_point: Some(2),
+                    },
+                ))
+                .id()
+        };
+        let first = spawn(&mut nav_graph);
+        let second = spawn(&mut nav_graph);
+        let third = spawn(&mut nav_graph);
+        nav_graph.set_max_occupancy(2, 1);
+        app.insert_resource(nav_graph);
+        app.update();
+
+        let events = app.world.resource::<Events<ReservationRevoked>>();
+        let revoked: Vec<_> = events.get_reader().iter(events).cloned().collect();
+        assert_eq!(revoked, [ReservationRevoked::new(2, vec![first, second])]);
+        assert!(app.world.get::<Replan>(first).is_some());
+        assert!(app.world.get::<Replan>(third).is_none());
+        assert_eq!(
+            app.world
+                .get::<TravelerPosition>(third)
+                .unwrap()
+                .next_nav_point,
+            Some(2)
+        );
+        let nav_graph = app.world.resource::<NavGraph>();
+        assert_eq!(nav_graph.get_nav_point(2).unwrap().current_occupancy(), 1);
+    }
+}
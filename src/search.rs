@@ -0,0 +1,782 @@
+use std::sync::Arc;
+
+use bevy_ecs::component::Component;
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{AreaCosts, CostProvider, NavDomain, NavGraph, NavPoint, Schedule, TerritoryAccess};
+
+/// Extra constraints for a single path search.
+///
+/// Used with [`NavGraph::find_path_with`](crate::NavGraph::find_path_with). The default options
+/// produce the same results as [`NavGraph::find_path`](crate::NavGraph::find_path).
+#[derive(Debug, Default, Clone)]
+pub struct SearchOptions {
+    /// [`NavPoint`](crate::NavPoint)s which must not be part of the path.
+    ///
+    /// Excluding the origin results in no path being found, as does excluding the destination
+    /// without a [`SearchOptions::goal_tolerance`].
+    pub excluded: HashSet<u32>,
+    /// Accepts any [`NavPoint`](crate::NavPoint) within this many connections of the destination
+    /// as the end of the path.
+    ///
+    /// With a tolerance of 0 only the destination itself is accepted. Larger values let searches
+    /// toward crowded or occupied destinations end early at a nearby point instead.
+    pub goal_tolerance: u32,
+    /// The corridor reservation group the search is for.
+    ///
+    /// Points reserved by other groups are avoided. See
+    /// [`NavGraph::reserve_corridor`](crate::NavGraph::reserve_corridor).
+    pub group: Option<u32>,
+    /// Random noise added to connection costs, so identical travelers don't all take exactly the
+    /// same route.
+    pub noise: Option<PathNoise>,
+    /// Checks [`Availability`](crate::Availability) windows against the time each point would be
+    /// reached. Without a schedule, availability windows are ignored.
+    pub schedule: Option<Schedule>,
+    /// Paths through full [`NavPoint`](crate::NavPoint)s as if they were empty.
+    pub ignore_occupancy: bool,
+    /// The maximum number of [`NavPoint`](crate::NavPoint)s in the path, including both ends.
+    ///
+    /// Searches which would need a longer path fail instead, so callers can fall back to other
+    /// behavior rather than committing to an absurdly long trek. The limit also cuts searches
+    /// short in pathological graphs such as large mazes. The cheapest path within the limit is
+    /// found, even where cheaper but longer routes exist, at the cost of searching each point
+    /// once for every path length it's reached by more cheaply.
+    pub max_nodes: Option<usize>,
+    /// The faction the search is for. Points owned by hostile factions are treated according to
+    /// `territory`. See [`NavGraph::set_owner`](crate::NavGraph::set_owner).
+    pub faction: Option<u32>,
+    pub territory: TerritoryAccess,
+    /// How many slots of each point's max_occupancy the searching traveler takes up. Points
+    /// without that much room left are avoided, unless `ignore_occupancy` or `congestion_cost`
+    /// is set. 0 is treated as 1.
+    pub occupancy_weight: u32,
+    /// Makes crowded points costly rather than impassable.
+    ///
+    /// Above 0.0, full points no longer block the search. Instead, the cost of moving onto each
+    /// point is multiplied by `1.0 + congestion_cost * occupancy_ratio`, so a full point costs
+    /// `1.0 + congestion_cost` times as much as an empty one and paths bend around crowds only
+    /// when the way round is cheap enough. Points too small for the traveler even when empty
+    /// stay impassable. See [`NavPoint::occupancy_ratio`](crate::NavPoint::occupancy_ratio).
+    pub congestion_cost: f32,
+    /// The maximum number of [`NavPoint`](crate::NavPoint)s the search expands before giving up.
+    ///
+    /// Searches for unreachable destinations otherwise explore everything reachable from the
+    /// origin before failing, so this caps what a single doomed search can cost on large graphs.
+    /// A search which gives up finds no path, even if one exists.
+    pub max_expanded: Option<usize>,
+    /// What the search returns when the destination can't be reached.
+    pub path_policy: PathPolicy,
+    /// The heuristic to estimate the cost left with, or `None` for the graph's. See
+    /// [`NavGraph::set_heuristic`](crate::NavGraph::set_heuristic).
+    pub heuristic: Option<Heuristic>,
+    /// Multiplies the heuristic's estimates, trading path quality for fewer points expanded.
+    ///
+    /// Above 1.0, the search heads more greedily toward the destination, and the path found may
+    /// cost up to this many times the cheapest. `None` leaves the estimates as they are.
+    pub epsilon: Option<f32>,
+    /// The traversal flags of the searching agent, e.g. `AIR` for a flying one. Points with
+    /// flags are only passable if they share at least one with the mask. See
+    /// [`NavPoint::flags`](crate::NavPoint::flags).
+    pub traversal_mask: u32,
+    /// Makes changes of direction cost extra, so paths favor long straight runs.
+    ///
+    /// Reversing direction costs as much as a connection this long, and gentler turns
+    /// proportionally less by angle. The search keeps a single best way to each point, so with
+    /// a penalty, the path found isn't always the cheapest.
+    pub turn_penalty: f32,
+    /// The regions the search may pass through, or `None` for all of them. See
+    /// [`NavPoint::region`](crate::NavPoint::region).
+    ///
+    /// Only the origin may lie outside them, so a traveler that has strayed can still find its
+    /// way back in.
+    pub allowed_regions: Option<HashSet<u32>>,
+    /// The radius of the searching traveler. Points with less clearance are impassable. See
+    /// [`NavPoint::clearance`](crate::NavPoint::clearance).
+    pub agent_size: f32,
+    /// Scales the cost of moving onto each point by its area type. See [`AreaCosts`].
+    pub area_costs: Option<AreaCosts>,
+    /// Custom costs for each connection, replacing the graph's own. See [`CostProvider`].
+    pub cost_provider: Option<Arc<dyn CostProvider>>,
+}
+
+/// How a search estimates the cost left from each point to its destination.
+///
+/// Connections cost their squared length times 100, divided by their speed modifier, which by
+/// default is that of the point they lead to (see [`EdgeSpeedModel`](crate::EdgeSpeedModel)).
+/// The admissible heuristics measure the distance left their own way and multiply it by the
+/// least any connection of the graph costs per unit of length, weights and durations included,
+/// so they never overestimate and searches find the cheapest path. Only
+/// [`AreaCosts`] multipliers below 1.0 and [`CostProvider`]s returning less than a connection's
+/// own cost can make them overestimate.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+#[cfg_attr(feature = "asset", derive(serde::Serialize, serde::Deserialize))]
+pub enum Heuristic {
+    /// The straight line distance. Admissible on any graph.
+    #[default]
+    Euclidean,
+    /// The sum of the distances along each axis. Admissible on grids without diagonal
+    /// connections, where it's tighter than `Euclidean`.
+    Manhattan,
+    /// Diagonal moves across the XY plane and straight moves for the rest, plus the distance
+    /// along Z. Admissible on grids with diagonal connections, where it's tighter than
+    /// `Euclidean`.
+    Octile,
+    /// The cost of a single connection straight to the destination, as searches used before
+    /// heuristics were configurable. It overestimates on all but the shortest paths, so
+    /// searches expand fewer points but may miss the cheapest path.
+    SquaredLegacy,
+}
+
+/// What a search returns when its destination can't be reached. See
+/// [`SearchOptions::path_policy`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PathPolicy {
+    /// No path at all.
+    #[default]
+    CompleteOnly,
+    /// The path to the point closest to the destination, in a straight line, of those the search
+    /// reached, so travelers can get as close as they can.
+    PartialAllowed,
+}
+
+/// A path from [`NavGraph::find_path_result`](crate::NavGraph::find_path_result).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathResult {
+    /// A path all the way to the destination.
+    Complete(Vec<u32>),
+    /// A path part of the way, under [`PathPolicy::PartialAllowed`].
+    Partial(Vec<u32>),
+}
+
+impl PathResult {
+    pub fn path(&self) -> &[u32] {
+        match self {
+            PathResult::Complete(path) | PathResult::Partial(path) => path,
+        }
+    }
+
+    pub fn into_path(self) -> Vec<u32> {
+        match self {
+            PathResult::Complete(path) | PathResult::Partial(path) => path,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        matches!(self, PathResult::Complete(_))
+    }
+}
+
+/// Deterministic per-connection cost noise.
+///
+/// Each connection's cost is scaled by a factor between `1.0` and `1.0 + magnitude`, chosen by
+/// hashing the connection with `seed`. Searches with the same seed always see the same costs,
+/// while different seeds (e.g. one per traveler) spread out over near-optimal alternatives.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect, FromReflect)]
+pub struct PathNoise {
+    pub seed: u64,
+    pub magnitude: f32,
+}
+
+impl PathNoise {
+    pub fn new(seed: u64, magnitude: f32) -> Self {
+        Self { seed, magnitude }
+    }
+
+    /// The cost multiplier for the connection from `a` to `b`.
+    #[inline(always)]
+    pub fn factor(&self, a: u32, b: u32) -> f32 {
+        // splitmix64 finalizer over the seed and connection.
+        let mut x = self.seed ^ ((a as u64) << 32 | b as u64);
+        x = x.wrapping_add(0x9e3779b97f4a7c15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+        x ^= x >> 31;
+
+        let unit = (x >> 40) as f32 / (1_u64 << 24) as f32;
+        1.0 + self.magnitude * unit
+    }
+}
+
+impl SearchOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Excludes the given [`NavPoint`]s from the search.
+    pub fn excluding(mut self, ids: impl IntoIterator<Item = u32>) -> Self {
+        self.excluded.extend(ids);
+        self
+    }
+
+    /// Accepts any point within `hops` connections of the destination as the end of the path.
+    pub fn with_goal_tolerance(mut self, hops: u32) -> Self {
+        self.goal_tolerance = hops;
+        self
+    }
+
+    /// Searches on behalf of a corridor reservation group.
+    pub fn for_group(mut self, group: u32) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Fails searches which would need a path of more than `max_nodes` points.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Gives up the search after expanding `max_expanded` points.
+    pub fn with_max_expanded(mut self, max_expanded: usize) -> Self {
+        self.max_expanded = Some(max_expanded);
+        self
+    }
+
+    pub fn with_heuristic(mut self, heuristic: Heuristic) -> Self {
+        self.heuristic = Some(heuristic);
+        self
+    }
+
+    /// See [`SearchOptions::epsilon`].
+    pub fn with_epsilon(mut self, epsilon: f32) -> Self {
+        self.epsilon = Some(epsilon);
+        self
+    }
+
+    /// See [`SearchOptions::traversal_mask`].
+    pub fn with_traversal_mask(mut self, traversal_mask: u32) -> Self {
+        self.traversal_mask = traversal_mask;
+        self
+    }
+
+    /// See [`SearchOptions::agent_size`].
+    pub fn with_agent_size(mut self, agent_size: f32) -> Self {
+        self.agent_size = agent_size;
+        self
+    }
+
+    /// Makes crowded points costly rather than impassable. See
+    /// [`SearchOptions::congestion_cost`].
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint, SearchOptions};
+    /// let mut nav_graph = NavGraph::new();
+    /// let points = [(1, 0.0, 0.0), (2, 1.0, 0.0), (3, 2.0, 0.0), (4, 0.0, 1.0), (5, 2.0, 1.0)];
+    /// for (id, x, y) in points {
+    ///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 1));
+    /// }
+    /// for (a, b) in [(1, 2), (2, 3), (1, 4), (4, 5), (5, 3)] {
+    ///     nav_graph.connect_points(a, b);
+    /// }
+    /// nav_graph.occupy(2);
+    ///
+    /// // Normally the full point 2 is out of the question.
+    /// assert_eq!(nav_graph.find_path(1, 3), Some(vec![1, 4, 5, 3]));
+    /// // Mildly discouraged, the crowd is still quicker than the long way round...
+    /// let mild = SearchOptions::new().with_congestion_cost(1.0);
+    /// assert_eq!(nav_graph.find_path_with(1, 3, &mild), Some(vec![1, 2, 3]));
+    /// // ...until crowds are costly enough.
+    /// let strong = SearchOptions::new().with_congestion_cost(10.0);
+    /// assert_eq!(nav_graph.find_path_with(1, 3, &strong), Some(vec![1, 4, 5, 3]));
+    /// ```
+    pub fn with_congestion_cost(mut self, congestion_cost: f32) -> Self {
+        self.congestion_cost = congestion_cost;
+        self
+    }
+
+    /// See [`SearchOptions::area_costs`].
+    pub fn with_area_costs(mut self, area_costs: AreaCosts) -> Self {
+        self.area_costs = Some(area_costs);
+        self
+    }
+
+    /// See [`SearchOptions::turn_penalty`].
+    pub fn with_turn_penalty(mut self, turn_penalty: f32) -> Self {
+        self.turn_penalty = turn_penalty;
+        self
+    }
+
+    /// Sets what the search returns when the destination can't be reached.
+    pub fn with_path_policy(mut self, path_policy: PathPolicy) -> Self {
+        self.path_policy = path_policy;
+        self
+    }
+
+    /// Keeps the search inside `regions`, e.g. the regions of a castle a guard shouldn't leave.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_navigator::{GridConnectivity, NavGraph, SearchOptions};
+    /// let (mut nav_graph, ids) = NavGraph::<()>::from_grid(3, 3, 1.0, GridConnectivity::Four);
+    /// // The bottom row is region 0 and the rest region 1.
+    /// for id in 1..=9 {
+    ///     nav_graph.modify(id, |point| point.set_region((id > 3) as u32));
+    /// }
+    ///
+    /// let (a, b) = (ids.id(0, 0).unwrap(), ids.id(2, 0).unwrap());
+    /// let options = SearchOptions::new().within_regions([1]);
+    /// assert_eq!(nav_graph.find_path_with(a, b, &options), None);
+    ///
+    /// // The origin may lie outside, but the rest of the path stays in region 1.
+    /// let path = nav_graph.find_path_with(a, ids.id(2, 1).unwrap(), &options).unwrap();
+    /// assert_eq!(path, [1, 4, 5, 6]);
+    /// ```
+    pub fn within_regions(mut self, regions: impl IntoIterator<Item = u32>) -> Self {
+        self.allowed_regions = Some(regions.into_iter().collect());
+        self
+    }
+
+    /// Searches on behalf of `faction`, treating hostile territory according to `access`.
+    pub fn as_faction(mut self, faction: u32, access: TerritoryAccess) -> Self {
+        self.faction = Some(faction);
+        self.territory = access;
+        self
+    }
+
+    /// Ignores the occupancy of [`NavPoint`]s.
+    pub fn ignoring_occupancy(mut self) -> Self {
+        self.ignore_occupancy = true;
+        self
+    }
+
+    /// Adds deterministic noise to connection costs. See [`PathNoise`].
+    pub fn with_noise(mut self, seed: u64, magnitude: f32) -> Self {
+        self.noise = Some(PathNoise::new(seed, magnitude));
+        self
+    }
+
+    /// The multiplier for moving onto `point` from [`SearchOptions::congestion_cost`]. Never
+    /// below 1.0, so the heuristic stays admissible.
+    #[inline(always)]
+    pub(crate) fn congestion_factor(&self, point: &NavPoint) -> f32 {
+        1.0 + self.congestion_cost.max(0.0) * point.occupancy_ratio()
+    }
+}
+
+/// The state of a previous search, kept so later searches toward the same destination can
+/// continue from it.
+///
+/// See [`NavGraph::find_path_warm`]. Adding this to an entity with an
+/// [`AutoTraveler`](crate::AutoTraveler) makes the traveler's planning use it, in place of any
+/// [`PathCache`](crate::PathCache).
+#[derive(Debug, Default, Clone, Component, Reflect, FromReflect)]
+pub struct PathWarmStart {
+    topology_version: u64,
+    destination: u32,
+    path: Vec<u32>,
+    /// The points the search expanded, with the cost of reaching each.
+    #[reflect(ignore)]
+    tree: SearchTree,
+    /// The points next to those the search expanded which it couldn't pass through. While they
+    /// stay impassable, nothing the search ruled out has opened up.
+    blocked: Vec<u32>,
+    reused: u32,
+    resumed: u32,
+    searched: u32,
+}
+
+/// The points a search expanded, with the cost of reaching each from `origin` and the point it
+/// was reached from.
+///
+/// Passed to [`NavGraph::search_recorded`], a search starting from `origin` carries on from these
+/// instead of starting over, and stores what it expanded in turn.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct SearchTree {
+    pub origin: u32,
+    pub g_score: HashMap<u32, u32>,
+    pub came_from: HashMap<u32, u32>,
+}
+
+impl SearchTree {
+    pub fn clear(&mut self) {
+        self.g_score.clear();
+        self.came_from.clear();
+    }
+
+    /// Replaces the tree with the `closed` points of a search from `origin`. Searches which count
+    /// the points in their paths may expand a point more than once, in which case the cheapest
+    /// way to it is kept.
+    pub fn store(
+        &mut self,
+        origin: u32,
+        closed: &[(u32, usize)],
+        g_score: &HashMap<(u32, usize), u32>,
+        came_from: &HashMap<(u32, usize), (u32, usize)>,
+    ) {
+        self.clear();
+        self.origin = origin;
+        for key in closed {
+            let g = g_score[key];
+            if self.g_score.get(&key.0).is_some_and(|stored| *stored <= g) {
+                continue;
+            }
+            self.g_score.insert(key.0, g);
+            match came_from.get(key) {
+                Some((previous, _)) => self.came_from.insert(key.0, *previous),
+                None => self.came_from.remove(&key.0),
+            };
+        }
+    }
+
+    /// Keeps only the points reached through `root` without passing a point `keep` rejects,
+    /// making `root` the new origin.
+    fn retain_through(&mut self, root: u32, mut keep: impl FnMut(u32) -> bool) {
+        let mut kept = HashMap::<u32, bool>::with_capacity(self.g_score.len());
+        kept.insert(root, true);
+        let mut chain = Vec::new();
+        for &id in self.g_score.keys() {
+            let mut current = id;
+            let result = loop {
+                if let Some(&result) = kept.get(&current) {
+                    break result;
+                }
+                chain.push(current);
+                if !keep(current) {
+                    break false;
+                }
+                match self.came_from.get(&current) {
+                    Some(&previous) => current = previous,
+                    // Back at the old origin without passing `root`.
+                    None => break false,
+                }
+            };
+            for id in chain.drain(..) {
+                kept.insert(id, result);
+            }
+        }
+
+        let root_g = self.g_score[&root];
+        self.g_score
+            .retain(|id, _| kept.get(id).copied().unwrap_or(false));
+        for g in self.g_score.values_mut() {
+            *g -= root_g;
+        }
+        self.came_from
+            .retain(|id, _| *id != root && kept.get(id).copied().unwrap_or(false));
+        self.origin = root;
+    }
+}
+
+impl PathWarmStart {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of searches answered from the stored path.
+    pub fn reused(&self) -> u32 {
+        self.reused
+    }
+
+    /// The number of searches which carried on from the previous search.
+    pub fn resumed(&self) -> u32 {
+        self.resumed
+    }
+
+    /// The number of searches which needed a full search.
+    pub fn searched(&self) -> u32 {
+        self.searched
+    }
+
+    /// Forgets the previous search, forcing the next search to run in full.
+    pub fn clear(&mut self) {
+        self.path.clear();
+        self.tree.clear();
+        self.blocked.clear();
+    }
+
+    /// Like [`NavGraph::find_path_warm`], adding the number of points expanded to `expanded`.
+    pub(crate) fn find_path_counted<M: NavDomain>(
+        &mut self,
+        nav_graph: &NavGraph<M>,
+        a: u32,
+        b: u32,
+        options: &SearchOptions,
+        expanded: &mut usize,
+    ) -> Option<Vec<u32>> {
+        nav_graph.observe(
+            a,
+            b,
+            || match self.reuse(nav_graph, a, b, options) {
+                Some(path) => Some(path),
+                None => self
+                    .search(nav_graph, a, b, options, expanded)
+                    .map(PathResult::into_path),
+            },
+            |path| (path.len(), nav_graph.path_cost(path)),
+        )
+    }
+
+    /// Whether the previous search was toward `b` on the graph as it is now, with options its
+    /// results carry over to.
+    fn is_current<M: NavDomain>(
+        &self,
+        nav_graph: &NavGraph<M>,
+        b: u32,
+        options: &SearchOptions,
+    ) -> bool {
+        // Whether a point is open depends on when it's reached, and what it costs on how crowded
+        // it is, so don't guess.
+        self.destination == b
+            && self.topology_version == nav_graph.topology_version()
+            && options.schedule.is_none()
+            && options.congestion_cost <= 0.0
+    }
+
+    /// Whether searches with `options` can carry on from the previous search. The cost of
+    /// reaching a point mustn't depend on the way there, and must be the same from one search to
+    /// the next.
+    fn can_resume(options: &SearchOptions) -> bool {
+        options.turn_penalty <= 0.0
+            && options.max_nodes.is_none()
+            && options.goal_tolerance == 0
+            && options.cost_provider.is_none()
+    }
+
+    fn reuse<M: NavDomain>(
+        &mut self,
+        nav_graph: &NavGraph<M>,
+        a: u32,
+        b: u32,
+        options: &SearchOptions,
+    ) -> Option<Vec<u32>> {
+        if self.path.is_empty() || !self.is_current(nav_graph, b, options) {
+            return None;
+        }
+
+        let start = self.path.iter().position(|id| *id == a)?;
+        let remaining = &self.path[start..];
+        let clear = remaining[1..].iter().all(|id| {
+            nav_graph
+                .get_nav_point(*id)
+                .map(|point| nav_graph.is_passable(point, options))
+                .unwrap_or(false)
+        });
+        // A point the search had to avoid having since freed up may open a cheaper way.
+        if !clear || !self.freed(nav_graph, options).is_empty() {
+            return None;
+        }
+
+        self.reused += 1;
+        Some(remaining.to_vec())
+    }
+
+    /// The points the previous search couldn't pass through which have since freed up.
+    fn freed<M: NavDomain>(&self, nav_graph: &NavGraph<M>, options: &SearchOptions) -> Vec<u32> {
+        self.blocked
+            .iter()
+            .copied()
+            .filter(|id| {
+                nav_graph
+                    .get_nav_point(*id)
+                    .is_some_and(|point| nav_graph.is_passable(point, options))
+            })
+            .collect()
+    }
+
+    /// Trims the previous search down to what still holds for a search from `a`: the points
+    /// reached through `a`, less those reached through a point which has since become
+    /// impassable. A point which has freed up may offer a cheaper way to some of the rest, which
+    /// the search finds and expands them again for like any point it finds a cheaper way to.
+    ///
+    /// Returns whether there's anything to continue from.
+    fn repair<M: NavDomain>(
+        &mut self,
+        nav_graph: &NavGraph<M>,
+        a: u32,
+        b: u32,
+        options: &SearchOptions,
+    ) -> bool {
+        if !self.is_current(nav_graph, b, options)
+            || !Self::can_resume(options)
+            || !self.tree.g_score.contains_key(&a)
+        {
+            return false;
+        }
+        self.tree.retain_through(a, |id| {
+            id == a
+                || nav_graph
+                    .get_nav_point(id)
+                    .is_some_and(|point| nav_graph.is_passable(point, options))
+        });
+        true
+    }
+
+    /// Searches from `a` to `b`, continuing from the previous search where it still holds, and
+    /// stores the result, adding the number of points expanded to `expanded`.
+    fn search<M: NavDomain>(
+        &mut self,
+        nav_graph: &NavGraph<M>,
+        a: u32,
+        b: u32,
+        options: &SearchOptions,
+        expanded: &mut usize,
+    ) -> Option<PathResult> {
+        if self.repair(nav_graph, a, b, options) {
+            self.resumed += 1;
+        } else {
+            self.tree.clear();
+            self.searched += 1;
+        }
+        let result = nav_graph
+            .search_recorded(a, b, options, None, Some(&mut self.tree), expanded)
+            .map(|(path, _)| path);
+        self.path.clear();
+        self.blocked.clear();
+        // Partial paths don't lead to the destination, so there's nothing to continue along.
+        let Some(PathResult::Complete(path)) = &result else {
+            self.tree.clear();
+            return result;
+        };
+
+        self.topology_version = nav_graph.topology_version();
+        self.destination = b;
+        self.path.extend_from_slice(path);
+        let expanded = &self.tree.g_score;
+        self.blocked.extend(
+            expanded
+                .keys()
+                .filter_map(|id| nav_graph.get_nav_point(*id))
+                .flat_map(|point| point.connections().iter().copied())
+                .filter(|id| !expanded.contains_key(id))
+                .filter(|id| {
+                    nav_graph
+                        .get_nav_point(*id)
+                        .is_some_and(|point| !nav_graph.is_passable(point, options))
+                }),
+        );
+        self.blocked.sort_unstable();
+        self.blocked.dedup();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::NavPoint;
+
+    #[test]
+    pub fn test_warm_start_reuse() {
+        let mut nav_graph = NavGraph::new();
+        for i in 1..=5 {
+            nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+        }
+        nav_graph.add_nav_point(NavPoint::new(6, Vec3::new(4.0, 1.0, 0.0), 1.0, 1));
+        for (a, b) in [(1, 2), (2, 3), (3, 4), (4, 5), (3, 6), (6, 5)] {
+            nav_graph.connect_points(a, b);
+        }
+
+        let options = SearchOptions::new();
+        let mut warm_start = PathWarmStart::new();
+        let path = nav_graph.find_path_warm(1, 5, &options, &mut warm_start);
+        assert_eq!(path.unwrap()[..], [1, 2, 3, 4, 5]);
+        assert_eq!(warm_start.searched(), 1);
+
+        // Continuing along the same route reuses the stored path.
+        let path = nav_graph.find_path_warm(2, 5, &options, &mut warm_start);
+        assert_eq!(path.unwrap()[..], [2, 3, 4, 5]);
+        assert_eq!(warm_start.reused(), 1);
+
+        // Blocking the route ahead carries on from the previous search.
+        nav_graph.occupy(4);
+        let path = nav_graph.find_path_warm(3, 5, &options, &mut warm_start);
+        assert_eq!(path.unwrap()[..], [3, 6, 5]);
+        assert_eq!(warm_start.resumed(), 1);
+
+        // The detour is kept while 4 stays blocked, but not once it clears.
+        let path = nav_graph.find_path_warm(3, 5, &options, &mut warm_start);
+        assert_eq!(path.unwrap()[..], [3, 6, 5]);
+        assert_eq!(warm_start.reused(), 2);
+        nav_graph.unoccupy(4);
+        let path = nav_graph.find_path_warm(3, 5, &options, &mut warm_start);
+        assert_eq!(path.unwrap()[..], [3, 4, 5]);
+        assert_eq!(warm_start.resumed(), 2);
+        assert_eq!(warm_start.searched(), 1);
+
+        // A topology change forces a new search.
+        nav_graph.connect_points(3, 5);
+        nav_graph.find_path_warm(3, 5, &options, &mut warm_start);
+        assert_eq!(warm_start.searched(), 2);
+    }
+
+    #[test]
+    pub fn test_warm_start_matches_full_search() {
+        // Occupancy changes while travelers make their way across, and carrying on from the
+        // previous search must find paths as cheap as searching from scratch, expanding fewer
+        // points.
+        let options = SearchOptions::new();
+        let (mut resumed, mut expanded_resumed, mut expanded_full) = (0, 0, 0);
+        for seed in 0..10 {
+            let mut nav_graph = crate::testing::random_graph(seed, 200, 4);
+            let mut warm_start = PathWarmStart::new();
+            let (mut a, b) = (1, 200);
+            let mut last_path = Vec::new();
+            let mut crowd = None;
+            for step in 0..40u32 {
+                // A crowd gathers on the route ahead and then moves on, while points anywhere fill
+                // up and empty.
+                match crowd.take() {
+                    Some(id) => nav_graph.unoccupy(id),
+                    None if last_path.len() > 2 => {
+                        let id = last_path[last_path.len() / 2];
+                        nav_graph.occupy(id);
+                        crowd = Some(id);
+                    }
+                    None => {}
+                }
+                let toggled = (step * 53 + seed as u32 * 17) % 200 + 1;
+                if toggled != a && toggled != b && Some(toggled) != crowd {
+                    let point = nav_graph.get_nav_point(toggled).unwrap();
+                    if point.current_occupancy() > 0 {
+                        nav_graph.unoccupy(toggled);
+                    } else {
+                        nav_graph.occupy(toggled);
+                    }
+                }
+
+                let resumed_before = warm_start.resumed();
+                let mut expanded = 0;
+                let path = warm_start.find_path_counted(&nav_graph, a, b, &options, &mut expanded);
+                let mut expanded_anew = 0;
+                let expected = nav_graph
+                    .search_recorded(a, b, &options, None, None, &mut expanded_anew)
+                    .map(|(path, _)| path.into_path());
+                if warm_start.resumed() > resumed_before {
+                    expanded_resumed += expanded;
+                    expanded_full += expanded_anew;
+                }
+                assert_eq!(
+                    path.as_deref().and_then(|path| nav_graph.path_cost(path)),
+                    expected
+                        .as_deref()
+                        .and_then(|path| nav_graph.path_cost(path)),
+                    "seed {seed}, step {step}, {a} to {b}"
+                );
+                last_path = path.unwrap_or_default();
+                if step % 3 == 0 && last_path.len() > 2 {
+                    a = last_path[1];
+                }
+            }
+            resumed += warm_start.resumed();
+        }
+        assert!(resumed > 100, "only {resumed} searches carried on");
+        assert!(expanded_resumed * 2 < expanded_full);
+    }
+
+    #[test]
+    pub fn test_noise_factor() {
+        let noise = PathNoise::new(42, 0.25);
+        let factors: Vec<f32> = (0..100).map(|i| noise.factor(i, i + 1)).collect();
+
+        assert!(factors.iter().all(|f| (1.0..=1.25).contains(f)));
+        assert!(factors.iter().any(|f| *f != factors[0]));
+        assert_eq!(noise.factor(3, 4), factors[3]);
+        assert_eq!(PathNoise::new(42, 0.0).factor(3, 4), 1.0);
+    }
+}
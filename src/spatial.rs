@@ -0,0 +1,363 @@
+use bevy_math::{IVec3, Vec3};
+use bevy_utils::HashMap;
+
+use crate::{memory::map_bytes, navigation::DEFAULT_CAPACITY_HINT};
+
+/// How many points, along each axis, the cells of a [`SpatialGrid`] are sized to hold given the
+/// graph's capacity hint.
+const POINTS_PER_CELL: f32 = 4.0;
+
+/// Buckets [`NavPoint`](crate::NavPoint)s into a uniform grid of cubic cells by location, so
+/// nearest-point lookups only look at the cells around the query.
+#[derive(Debug, Clone)]
+pub(crate) struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<IVec3, Vec<(u32, Vec3)>>,
+    /// The lowest and highest cells that have held points. Only grows until the grid is rebuilt.
+    bounds: Option<(IVec3, IVec3)>,
+    len: usize,
+    /// The length of the longest connection between points. Also only grows until rebuilt.
+    longest_connection: f32,
+    /// The least any connection costs per unit of its length. Only shrinks until rebuilt.
+    min_cost_per_unit: f32,
+    /// The highest speed modifier of any point with a connection. Only grows until rebuilt.
+    fastest_speed_modifier: f32,
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        Self::for_capacity_hint(DEFAULT_CAPACITY_HINT)
+    }
+}
+
+impl SpatialGrid {
+    /// An empty grid sized for a graph with the specified capacity hint. See
+    /// [`NavGraph::set_capacity_hint`](crate::NavGraph::set_capacity_hint).
+    pub fn for_capacity_hint(capacity_hint: f32) -> Self {
+        Self {
+            cell_size: cell_size_for(capacity_hint),
+            cells: HashMap::default(),
+            bounds: None,
+            len: 0,
+            longest_connection: 0.0,
+            min_cost_per_unit: f32::INFINITY,
+            fastest_speed_modifier: 0.0,
+        }
+    }
+
+    /// Empties the grid and buckets `points` into it from scratch, tightening its bounds to
+    /// the points it now holds. Connections have to be noted again afterwards.
+    pub fn rebuild(&mut self, points: impl IntoIterator<Item = (u32, Vec3)>) {
+        self.cells.clear();
+        self.bounds = None;
+        self.len = 0;
+        self.longest_connection = 0.0;
+        self.min_cost_per_unit = f32::INFINITY;
+        self.fastest_speed_modifier = 0.0;
+        for (id, location) in points {
+            self.insert(id, location);
+        }
+    }
+
+    /// Re-buckets every point for the specified capacity hint.
+    pub fn resize_for(&mut self, capacity_hint: f32) {
+        let cell_size = cell_size_for(capacity_hint);
+        if cell_size == self.cell_size {
+            return;
+        }
+        let mut resized = Self::for_capacity_hint(capacity_hint);
+        resized.longest_connection = self.longest_connection;
+        resized.min_cost_per_unit = self.min_cost_per_unit;
+        resized.fastest_speed_modifier = self.fastest_speed_modifier;
+        for (id, location) in self.cells.drain().flat_map(|(_, points)| points) {
+            resized.insert(id, location);
+        }
+        *self = resized;
+    }
+
+    #[inline(always)]
+    pub fn note_connection(&mut self, length: f32, speed_modifier: f32, cost: u32) {
+        self.longest_connection = self.longest_connection.max(length);
+        self.fastest_speed_modifier = self.fastest_speed_modifier.max(speed_modifier);
+        if length > 0.0 {
+            self.min_cost_per_unit = self.min_cost_per_unit.min(cost as f32 / length);
+        }
+    }
+
+    #[inline(always)]
+    pub fn longest_connection(&self) -> f32 {
+        self.longest_connection
+    }
+
+    #[inline(always)]
+    pub fn fastest_speed_modifier(&self) -> f32 {
+        self.fastest_speed_modifier
+    }
+
+    /// The least any connection costs per unit of its length, going by the cost searches
+    /// actually pay for it, so weights, durations and rounding are all taken into account.
+    #[inline(always)]
+    pub fn min_cost_per_unit(&self) -> f32 {
+        if self.min_cost_per_unit.is_finite() {
+            self.min_cost_per_unit
+        } else {
+            0.0
+        }
+    }
+
+    #[inline(always)]
+    fn cell_of(&self, location: Vec3) -> IVec3 {
+        (location / self.cell_size).floor().as_ivec3()
+    }
+
+    pub fn insert(&mut self, id: u32, location: Vec3) {
+        let cell = self.cell_of(location);
+        self.cells.entry(cell).or_default().push((id, location));
+        self.len += 1;
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min(cell), max.max(cell)),
+            None => (cell, cell),
+        });
+    }
+
+    pub fn remove(&mut self, id: u32, location: Vec3) {
+        let cell = self.cell_of(location);
+        if let Some(points) = self.cells.get_mut(&cell) {
+            let before = points.len();
+            points.retain(|(other, _)| *other != id);
+            self.len -= before - points.len();
+            if points.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+
+    /// The ID of the point closest to `position`, or `None` if the grid is empty.
+    ///
+    /// Searches outward from the cell containing `position` one shell of cells at a time,
+    /// stopping once no closer point can be found further out. If that would visit more cells
+    /// than the grid holds, e.g. for a position far outside a sparse graph, every point is
+    /// checked instead.
+    pub fn nearest(&self, position: Vec3) -> Option<u32> {
+        let (min, max) = self.bounds?;
+        if self.cells.is_empty() {
+            return None;
+        }
+
+        let center = self.cell_of(position);
+        // No cells outside the bounds hold points, so skip the shells that lie entirely outside.
+        let first = (min - center)
+            .max(center - max)
+            .max(IVec3::ZERO)
+            .max_element();
+        let last = (center - min).max(max - center).max_element();
+
+        let mut best: Option<(f32, u32)> = None;
+        let mut visited = 0;
+        for ring in first..=last {
+            if let Some((distance_squared, _)) = best {
+                // Every point in this shell is at least `ring - 1` cells away.
+                let reach = (ring - 1).max(0) as f32 * self.cell_size;
+                if reach * reach >= distance_squared {
+                    break;
+                }
+            }
+
+            let low = (center - ring).max(min);
+            let high = (center + ring).min(max);
+            for x in low.x..=high.x {
+                for y in low.y..=high.y {
+                    visited += 1;
+                    if visited > self.cells.len() {
+                        return self.nearest_linear(position);
+                    }
+
+                    let on_edge = (x - center.x).abs() == ring || (y - center.y).abs() == ring;
+                    if on_edge {
+                        for z in low.z..=high.z {
+                            self.scan_cell(IVec3::new(x, y, z), position, &mut best);
+                        }
+                    } else {
+                        // Only the cells on the near and far faces of the shell.
+                        for z in [center.z - ring, center.z + ring] {
+                            if z >= low.z && z <= high.z {
+                                self.scan_cell(IVec3::new(x, y, z), position, &mut best);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|(_, id)| id)
+    }
+
+    /// The IDs of the points within `radius` of `position`, closest first, with ties broken by
+    /// lowest ID.
+    pub fn within(&self, position: Vec3, radius: f32) -> Vec<u32> {
+        let Some((min, max)) = self.bounds else {
+            return Vec::new();
+        };
+        let radius_squared = radius * radius;
+        let mut found: Vec<(f32, u32)> = Vec::new();
+        let mut collect = |points: &Vec<(u32, Vec3)>| {
+            for &(id, location) in points {
+                let distance_squared = location.distance_squared(position);
+                if distance_squared <= radius_squared {
+                    found.push((distance_squared, id));
+                }
+            }
+        };
+
+        let low = self.cell_of(position - radius).max(min);
+        let high = self.cell_of(position + radius).min(max);
+        let span = (high - low + IVec3::ONE).max(IVec3::ZERO).as_vec3();
+        if span.x * span.y * span.z > self.cells.len() as f32 {
+            // Checking every point is cheaper than visiting the mostly empty cells in range.
+            self.cells.values().for_each(&mut collect);
+        } else {
+            for x in low.x..=high.x {
+                for y in low.y..=high.y {
+                    for z in low.z..=high.z {
+                        if let Some(points) = self.cells.get(&IVec3::new(x, y, z)) {
+                            collect(points);
+                        }
+                    }
+                }
+            }
+        }
+
+        found.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        found.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Roughly how many points lie along each unit of distance, going by how densely the
+    /// occupied cells are filled, or `None` if the grid is empty.
+    ///
+    /// Axes the points don't spread along, e.g. height in a flat graph, aren't counted, so
+    /// flat and layered graphs aren't mistaken for sparse ones.
+    pub fn points_per_unit(&self) -> Option<f32> {
+        let (min, max) = self.bounds?;
+        if self.cells.is_empty() {
+            return None;
+        }
+        let spread = max - min;
+        let axes = [spread.x, spread.y, spread.z]
+            .into_iter()
+            .filter(|extent| *extent > 0)
+            .count()
+            .max(1);
+        let per_cell = self.len as f32 / self.cells.len() as f32;
+        Some(per_cell.powf(1.0 / axes as f32) / self.cell_size)
+    }
+
+    fn scan_cell(&self, cell: IVec3, position: Vec3, best: &mut Option<(f32, u32)>) {
+        for &(id, location) in self.cells.get(&cell).into_iter().flatten() {
+            let candidate = (location.distance_squared(position), id);
+            if best.is_none_or(|best| candidate < best) {
+                *best = Some(candidate);
+            }
+        }
+    }
+
+    fn nearest_linear(&self, position: Vec3) -> Option<u32> {
+        self.cells
+            .values()
+            .flatten()
+            .map(|&(id, location)| (location.distance_squared(position), id))
+            .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)))
+            .map(|(_, id)| id)
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        map_bytes(&self.cells)
+            + self
+                .cells
+                .values()
+                .map(|points| points.capacity() * std::mem::size_of::<(u32, Vec3)>())
+                .sum::<usize>()
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.cells.shrink_to_fit();
+        for points in self.cells.values_mut() {
+            points.shrink_to_fit();
+        }
+    }
+}
+
+fn cell_size_for(capacity_hint: f32) -> f32 {
+    if capacity_hint > 0.0 {
+        POINTS_PER_CELL / capacity_hint
+    } else {
+        POINTS_PER_CELL / DEFAULT_CAPACITY_HINT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{NavGraph, NavPoint};
+
+    #[test]
+    pub fn test_nearest_matches_linear_scan() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut random_location = |spread: f32| {
+            Vec3::new(
+                rng.gen_range(-spread..spread),
+                rng.gen_range(-spread..spread),
+                rng.gen_range(-spread..spread) * 0.1,
+            )
+        };
+
+        let mut nav_graph = NavGraph::new();
+        for id in 0..500 {
+            nav_graph.add_nav_point(NavPoint::new(id, random_location(50.0), 1.0, 1));
+        }
+        for id in 0..100 {
+            nav_graph.remove_point(id * 3);
+            nav_graph.set_location(id * 3 + 1, random_location(50.0));
+        }
+
+        for _ in 0..200 {
+            let position = random_location(80.0);
+            let expected = nav_graph
+                .iter_points()
+                .map(|point| (point.location().distance_squared(position), point.id()))
+                .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)))
+                .map(|(_, id)| id);
+            assert_eq!(nav_graph.nearest_point(position), expected);
+        }
+        let far_away = Vec3::new(1.0e6, -1.0e6, 0.0);
+        assert!(nav_graph.nearest_point(far_away).is_some());
+    }
+
+    #[test]
+    pub fn test_radius_query_after_rebuild() {
+        let mut nav_graph = NavGraph::new();
+        for id in 0..100 {
+            let location = Vec3::new((id % 10) as f32, (id / 10) as f32, 0.0) * 0.5;
+            nav_graph.add_nav_point(NavPoint::new(id, location, 1.0, 1));
+        }
+        // Far off points widen the grid until it's rebuilt without them.
+        nav_graph.add_nav_point(NavPoint::new(100, Vec3::splat(1000.0), 1.0, 1));
+        nav_graph.remove_point(100);
+        nav_graph.rebuild_spatial_index();
+
+        let position = Vec3::new(1.1, 1.3, 0.0);
+        let mut expected: Vec<_> = nav_graph
+            .iter_points()
+            .filter(|point| point.location().distance(position) <= 1.0)
+            .map(|point| (point.location().distance_squared(position), point.id()))
+            .collect();
+        expected.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        let expected: Vec<_> = expected.into_iter().map(|(_, id)| id).collect();
+        assert_eq!(nav_graph.points_within_radius(position, 1.0), expected);
+        assert_eq!(nav_graph.points_within_radius(position, 1000.0).len(), 100);
+
+        // The flat grid of points half a unit apart is measured as such.
+        let points_per_unit = nav_graph.spatial.points_per_unit().unwrap();
+        assert!((points_per_unit - 2.0).abs() < 0.5, "{}", points_per_unit);
+    }
+}
@@ -0,0 +1,128 @@
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{NavDomain, NavGraph, SearchOptions};
+
+/// Requirements for a spawn point. See [`NavGraph::pick_spawn_point`].
+///
+/// The default criteria accept any point with room for another occupant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnCriteria {
+    /// Only accept points with room for another occupant.
+    pub unoccupied: bool,
+    /// Only accept points in the same region as this point.
+    pub same_region_as: Option<u32>,
+    /// Only accept points a traveler at this point could currently reach.
+    pub reachable_from: Option<u32>,
+    /// Only accept points at least this far, as measured by [`NavGraph::path_cost`], from a
+    /// point, e.g. the player's. Points which can't be reached from it at all are accepted.
+    pub min_cost_from: Option<(u32, u32)>,
+    /// Only accept points with all of these tags. See [`NavPoint::tags`](crate::NavPoint::tags).
+    pub tags: u32,
+}
+
+impl Default for SpawnCriteria {
+    fn default() -> Self {
+        Self {
+            unoccupied: true,
+            same_region_as: None,
+            reachable_from: None,
+            min_cost_from: None,
+            tags: 0,
+        }
+    }
+}
+
+impl SpawnCriteria {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also accepts points which are full.
+    pub fn allowing_occupied(mut self) -> Self {
+        self.unoccupied = false;
+        self
+    }
+
+    pub fn in_region_of(mut self, id: u32) -> Self {
+        self.same_region_as = Some(id);
+        self
+    }
+
+    pub fn reachable_from(mut self, id: u32) -> Self {
+        self.reachable_from = Some(id);
+        self
+    }
+
+    pub fn away_from(mut self, id: u32, min_cost: u32) -> Self {
+        self.min_cost_from = Some((id, min_cost));
+        self
+    }
+
+    pub fn with_tags(mut self, tags: u32) -> Self {
+        self.tags = tags;
+        self
+    }
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Returns every point which meets `criteria`, in ascending ID order.
+    pub fn spawn_candidates(&self, criteria: &SpawnCriteria) -> Vec<u32> {
+        let region = match criteria.same_region_as {
+            Some(id) => match self.get_nav_point(id) {
+                Some(point) => Some(point.region()),
+                None => return Vec::new(),
+            },
+            None => None,
+        };
+        let reachable = criteria
+            .reachable_from
+            .map(|id| self.cost_map(id, u32::MAX, Some(&SearchOptions::default())));
+        let too_close = criteria
+            .min_cost_from
+            .map(|(id, min_cost)| self.cost_map(id, min_cost.saturating_sub(1), None));
+
+        let mut candidates: Vec<u32> = self
+            .points
+            .values()
+            .filter(|point| {
+                (!criteria.unoccupied || point.can_occupy())
+                    && point.has_tags(criteria.tags)
+                    && region.is_none_or(|region| point.region() == region)
+                    && reachable
+                        .as_ref()
+                        .is_none_or(|reachable| reachable.contains_key(&point.id()))
+                    && too_close
+                        .as_ref()
+                        .is_none_or(|too_close| !too_close.contains_key(&point.id()))
+            })
+            .map(|point| point.id())
+            .collect();
+        candidates.sort_unstable();
+        candidates
+    }
+
+    /// Picks a random point which meets `criteria`, or `None` if there aren't any.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint, SpawnCriteria};
+    /// let mut nav_graph = NavGraph::new();
+    /// for i in 1..=4 {
+    ///     nav_graph.add_nav_point(NavPoint::new(i, Vec3::new(i as f32, 0.0, 0.0), 1.0, 1));
+    /// }
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    /// nav_graph.occupy(3);
+    ///
+    /// // The player is at 1. Point 2 is too close, 3 is full and 4 can't be reached.
+    /// let criteria = SpawnCriteria::new().reachable_from(1).away_from(1, 150);
+    /// assert_eq!(nav_graph.pick_spawn_point(&criteria, &mut rand::thread_rng()), None);
+    ///
+    /// nav_graph.unoccupy(3);
+    /// assert_eq!(nav_graph.pick_spawn_point(&criteria, &mut rand::thread_rng()), Some(3));
+    /// ```
+    pub fn pick_spawn_point(&self, criteria: &SpawnCriteria, rng: &mut impl Rng) -> Option<u32> {
+        self.spawn_candidates(criteria).choose(rng).copied()
+    }
+}
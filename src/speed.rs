@@ -0,0 +1,224 @@
+use bevy_ecs::{
+    component::Component,
+    system::{Query, Res},
+};
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_time::Time;
+
+use crate::{NavDomain, NavGraph};
+
+/// A single multiplier applied to a traveler's speed. See [`SpeedModifiers`].
+#[derive(Debug, Clone, PartialEq, Reflect, FromReflect)]
+pub struct SpeedModifier {
+    /// What applied the modifier, e.g. `"frozen"` or `"encumbered"`.
+    pub source: String,
+    pub multiplier: f32,
+    /// How many more seconds the modifier lasts for, or `None` if it lasts until removed.
+    pub remaining: Option<f32>,
+}
+
+/// Multipliers stacked on top of [`AutoTraveler::speed`](crate::AutoTraveler::speed) while
+/// moving, for slows, hastes, encumbrance and the like.
+///
+/// Each source has at most one modifier; adding another from the same source replaces it. Timed
+/// modifiers are removed once their duration runs out.
+///
+/// ## Example
+/// ```
+/// # use bevy_navigator::SpeedModifiers;
+/// let mut modifiers = SpeedModifiers::default();
+/// modifiers.add("encumbered", 0.5);
+/// modifiers.add_timed("haste", 3.0, 10.0);
+/// assert_eq!(modifiers.multiplier(), 1.5);
+///
+/// modifiers.remove("haste");
+/// assert_eq!(modifiers.multiplier(), 0.5);
+/// ```
+#[derive(Debug, Default, Clone, Component, Reflect, FromReflect)]
+pub struct SpeedModifiers {
+    pub modifiers: Vec<SpeedModifier>,
+}
+
+impl SpeedModifiers {
+    /// Applies `multiplier` until it's removed, replacing any modifier from the same source.
+    pub fn add(&mut self, source: impl Into<String>, multiplier: f32) {
+        self.insert(source.into(), multiplier, None);
+    }
+
+    /// Applies `multiplier` for `duration` seconds, replacing any modifier from the same source.
+    pub fn add_timed(&mut self, source: impl Into<String>, multiplier: f32, duration: f32) {
+        self.insert(source.into(), multiplier, Some(duration));
+    }
+
+    /// Removes the modifier from `source`, if there is one.
+    pub fn remove(&mut self, source: &str) {
+        self.modifiers.retain(|modifier| modifier.source != source);
+    }
+
+    /// The product of every modifier, which the traveler's speed is multiplied by.
+    pub fn multiplier(&self) -> f32 {
+        self.modifiers
+            .iter()
+            .map(|modifier| modifier.multiplier)
+            .product()
+    }
+
+    fn insert(&mut self, source: String, multiplier: f32, remaining: Option<f32>) {
+        self.remove(&source);
+        self.modifiers.push(SpeedModifier {
+            source,
+            multiplier,
+            remaining,
+        });
+    }
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Estimates how long, in seconds, a traveler moving at `speed` with `modifiers` takes to
+    /// follow `path`, e.g. to show ETAs or to weigh a short but slow route against a long but
+    /// fast one.
+    ///
+    /// Each connection takes as long as it does in [`AutoTraveler`](crate::AutoTraveler)
+    /// movement, scaled by its speed modifier under the graph's
+    /// [`EdgeSpeedModel`](crate::EdgeSpeedModel). Points on the way
+    /// which are currently full add the time they typically take to free up, going by
+    /// [`NavGraph::expected_wait`].
+    ///
+    /// Returns `None` if the path isn't connected, or if the traveler can't move at all.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint, SpeedModifiers};
+    /// let mut nav_graph = NavGraph::new();
+    /// nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(4.0, 0.0, 0.0), 0.5, 1));
+    /// nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(8.0, 0.0, 0.0), 1.0, 1));
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    ///
+    /// let mut modifiers = SpeedModifiers::default();
+    /// modifiers.add("haste", 2.0);
+    /// // 4 units at 4 units per second, then 4 more at half that through the mud at 2.
+    /// assert_eq!(nav_graph.estimate_travel_time(&[1, 2, 3], 2.0, Some(&modifiers)), Some(3.0));
+    /// ```
+    pub fn estimate_travel_time(
+        &self,
+        path: &[u32],
+        speed: f32,
+        modifiers: Option<&SpeedModifiers>,
+    ) -> Option<f32> {
+        let speed = speed * modifiers.map_or(1.0, SpeedModifiers::multiplier);
+        path.windows(2).try_fold(0.0, |total, pair| {
+            let (from, to) = (self.get_nav_point(pair[0])?, self.get_nav_point(pair[1])?);
+            if !from.connections().contains(&pair[1]) {
+                return None;
+            }
+            let rate = speed
+                * self
+                    .edge_speed_model()
+                    .effective(from.speed_modifier(), to.speed_modifier());
+            if rate <= 0.0 {
+                return None;
+            }
+            let dwell = if to.can_occupy() {
+                0.0
+            } else {
+                self.expected_wait(pair[1]).unwrap_or(0.0)
+            };
+            Some(total + from.location().distance(to.location()) / rate + dwell)
+        })
+    }
+}
+
+pub(crate) fn expire_speed_modifiers<M: NavDomain>(
+    mut modifiers_query: Query<&mut SpeedModifiers, M::Filter>,
+    time: Res<Time>,
+) {
+    let delta_seconds = time.delta_seconds();
+    for mut modifiers in modifiers_query.iter_mut() {
+        if modifiers.modifiers.iter().all(|m| m.remaining.is_none()) {
+            continue;
+        }
+        modifiers
+            .modifiers
+            .retain_mut(|modifier| match &mut modifier.remaining {
+                Some(remaining) => {
+                    *remaining -= delta_seconds;
+                    *remaining > 0.0
+                }
+                None => true,
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::schedule::IntoSystemDescriptor;
+    use bevy_math::Vec3;
+    use bevy_transform::prelude::Transform;
+    use bevy_utils::{Duration, Instant};
+
+    use super::*;
+    use crate::{
+        traveler::{move_travelers, TravelerPosition},
+        AutoTraveler, DestinationSubstituted, LodPolicy, NavClock, NavGraph, NavPoint,
+        TrafficLanes, TravelCompleted, TravelFailed,
+    };
+
+    #[test]
+    pub fn test_speed_modifiers_scale_movement() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(10.0, 0.0, 0.0), 1.0, 2));
+        nav_graph.connect_points(1, 2);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .init_resource::<LodPolicy>()
+            .init_resource::<TrafficLanes>()
+            .add_event::<TravelCompleted>()
+            .add_event::<DestinationSubstituted>()
+            .add_event::<TravelFailed>()
+            .add_system(expire_speed_modifiers::<()>.before(move_travelers::<()>))
+            .add_system(move_travelers::<()>);
+        let mut spawn = |modifiers| {
+            let mut auto_traveler = AutoTraveler::new(1, 2, 1.0);
+            auto_traveler.path = Some(vec![1, 2]);
+            app.world
+                .spawn((
+                    auto_traveler,
+                    TravelerPosition {
+                        current_nav_point: 1,
+                        next_nav_point: None,
+                    },
+                    Transform::default(),
+                    modifiers,
+                ))
+                .id()
+        };
+        let normal = spawn(SpeedModifiers::default());
+        let mut slowed = SpeedModifiers::default();
+        slowed.add_timed("frozen", 0.5, 1.5);
+        let slowed = spawn(slowed);
+
+        let start = Instant::now();
+        app.world.resource_mut::<Time>().update_with_instant(start);
+        let x = |app: &App, entity| app.world.get::<Transform>(entity).unwrap().translation.x;
+        for second in 1..=2 {
+            app.world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_secs(second));
+            app.update();
+        }
+
+        // Slowed for the first second, then back to full speed once the effect wore off.
+        assert!((x(&app, normal) - 2.0).abs() < 0.01);
+        assert!((x(&app, slowed) - 1.5).abs() < 0.01);
+        let modifiers = app.world.get::<SpeedModifiers>(slowed).unwrap();
+        assert!(modifiers.modifiers.is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    system::{Commands, Query, Res},
+};
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_time::Time;
+use bevy_utils::HashMap;
+
+use crate::NavDomain;
+
+/// Holds a traveler at its origin for a while once its path is found, so a batch of travelers
+/// spawned at the same point don't all contend for the first points of their paths at once.
+///
+/// Staggered travelers leaving the same origin depart at least `interval` seconds apart, in the
+/// order their paths are found, and each waits at least `offset` seconds. Travelers spawned
+/// over several frames are spaced out the same as ones spawned together. While waiting, a
+/// traveler has an [`AwaitingDeparture`].
+///
+/// ## Example
+/// ```
+/// # use bevy_ecs::system::Commands;
+/// # use bevy_navigator::{AutoTraveler, DepartureStagger};
+/// fn spawn_squad(mut commands: Commands) {
+///     for _ in 0..8 {
+///         commands.spawn((
+///             AutoTraveler::new(1, 42, 2.0),
+///             DepartureStagger::new(0.25),
+///         ));
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect, FromReflect)]
+pub struct DepartureStagger {
+    pub interval: f32,
+    pub offset: f32,
+}
+
+impl DepartureStagger {
+    pub fn new(interval: f32) -> Self {
+        Self {
+            interval,
+            offset: 0.0,
+        }
+    }
+
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// Marks a traveler held at its origin by its [`DepartureStagger`] until `departs_at`, in
+/// seconds of [`Time::elapsed_seconds_f64`].
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect, FromReflect)]
+pub struct AwaitingDeparture {
+    pub departs_at: f64,
+}
+
+/// The next free departure time from each origin with staggered travelers.
+#[derive(Debug, Default)]
+pub(crate) struct DepartureSlots {
+    next: HashMap<u32, f64>,
+}
+
+impl DepartureSlots {
+    /// Books the earliest departure from `origin` allowed by `stagger`, returning its time.
+    pub fn book(&mut self, origin: u32, stagger: &DepartureStagger, now: f64) -> f64 {
+        // Slots in the past don't hold anyone back any more.
+        if self.next.len() > 64 {
+            self.next.retain(|_, next| *next > now);
+        }
+        let earliest = now + stagger.offset.max(0.0) as f64;
+        let departs_at = self
+            .next
+            .get(&origin)
+            .map_or(earliest, |next| next.max(earliest));
+        self.next
+            .insert(origin, departs_at + stagger.interval.max(0.0) as f64);
+        departs_at
+    }
+}
+
+pub(crate) fn release_departures<M: NavDomain>(
+    waiting_query: Query<(Entity, &AwaitingDeparture), <M as NavDomain>::Filter>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    let now = time.elapsed_seconds_f64();
+    for (entity, awaiting) in waiting_query.iter() {
+        if awaiting.departs_at <= now {
+            commands.entity(entity).remove::<AwaitingDeparture>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_math::Vec3;
+    use bevy_transform::prelude::Transform;
+    use bevy_utils::Duration;
+
+    use super::*;
+    use crate::{traveler::TravelerPosition, AutoTraveler, NavGraph, NavPoint, NavigatorPlugin};
+
+    #[test]
+    pub fn test_staggered_departures() {
+        let mut app = App::new();
+        app.add_plugin(NavigatorPlugin::new())
+            .insert_resource(Time::default());
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        for id in 1..=3 {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 4));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+
+        let travelers: Vec<_> = (0..3)
+            .map(|_| {
+                app.world
+                    .spawn((
+                        Transform::default(),
+                        AutoTraveler::new(1, 3, 1.0),
+                        DepartureStagger::new(1.0).with_offset(0.5),
+                    ))
+                    .id()
+            })
+            .collect();
+
+        let start = app.world.resource::<Time>().startup();
+        let advance_to = |app: &mut App, seconds: f32| {
+            app.world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_secs_f32(seconds));
+            app.update();
+        };
+        advance_to(&mut app, 0.0);
+        let departures: Vec<_> = travelers
+            .iter()
+            .map(|entity| {
+                app.world
+                    .get::<AwaitingDeparture>(*entity)
+                    .unwrap()
+                    .departs_at
+            })
+            .collect();
+        assert_eq!(departures, [0.5, 1.5, 2.5]);
+
+        advance_to(&mut app, 1.0);
+        advance_to(&mut app, 1.1);
+        let next_point = |app: &App, entity: Entity| {
+            app.world
+                .get::<TravelerPosition>(entity)
+                .unwrap()
+                .next_nav_point
+        };
+        assert_eq!(next_point(&app, travelers[0]), Some(2));
+        assert_eq!(next_point(&app, travelers[1]), None);
+        assert!(app.world.get::<AwaitingDeparture>(travelers[2]).is_some());
+    }
+}
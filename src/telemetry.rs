@@ -0,0 +1,54 @@
+use std::{fmt, sync::Arc};
+
+use bevy_utils::Duration;
+
+/// Information about a single path computation, passed to telemetry hooks.
+///
+/// See [`NavGraph::add_telemetry_hook`](crate::NavGraph::add_telemetry_hook).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathTelemetry {
+    pub origin: u32,
+    pub destination: u32,
+    /// The number of [`NavPoint`](crate::NavPoint)s in the resulting path, or `None` if no path
+    /// was found.
+    pub path_len: Option<usize>,
+    /// The total cost of the resulting path, or `None` if no path was found.
+    pub cost: Option<u32>,
+    /// How long the computation took.
+    pub duration: Duration,
+}
+
+type TelemetryHook = Arc<dyn Fn(&PathTelemetry) + Send + Sync>;
+
+/// The set of hooks fired for every path computation on a [`NavGraph`](crate::NavGraph).
+#[derive(Default, Clone)]
+pub(crate) struct TelemetryHooks(Vec<TelemetryHook>);
+
+impl TelemetryHooks {
+    pub fn add(&mut self, hook: impl Fn(&PathTelemetry) + Send + Sync + 'static) {
+        self.0.push(Arc::new(hook));
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn fire(&self, telemetry: &PathTelemetry) {
+        for hook in &self.0 {
+            hook(telemetry);
+        }
+    }
+}
+
+impl fmt::Debug for TelemetryHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TelemetryHooks")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
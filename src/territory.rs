@@ -0,0 +1,189 @@
+#[cfg(feature = "travelers")]
+use bevy_ecs::{entity::Entity, event::EventWriter, query::Changed, system::Query};
+use bevy_reflect::{FromReflect, Reflect};
+
+#[cfg(feature = "travelers")]
+use crate::{
+    graphs::TravelerGraphs, traveler::TravelerPosition, AutoTraveler, EnteredHostileTerritory,
+};
+use crate::{NavDomain, NavGraph};
+
+/// How a traveler treats [`NavPoint`](crate::NavPoint)s owned by factions hostile to its own.
+/// See [`NavGraph::set_owner`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect, FromReflect)]
+pub enum TerritoryAccess {
+    /// Ownership is ignored.
+    #[default]
+    Allow,
+    /// Hostile points are never planned through or entered.
+    Forbid,
+    /// Connections into hostile points cost this many times as much when planning.
+    Penalize(f32),
+    /// Hostile points are treated like any other, but entering one sends
+    /// [`EnteredHostileTerritory`].
+    AllowWithEvent,
+}
+
+impl<M: NavDomain> NavGraph<M> {
+    /// Sets the faction which owns the specified point, or clears it with `None`.
+    ///
+    /// Travelers of hostile factions treat the point according to their [`TerritoryAccess`].
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_math::Vec3;
+    /// # use bevy_navigator::{NavGraph, NavPoint, SearchOptions, TerritoryAccess};
+    /// let mut nav_graph = NavGraph::new();
+    /// for (id, x, y) in [(1, 0.0, 0.0), (2, 1.0, 0.0), (3, 2.0, 0.0), (4, 1.0, 1.0)] {
+    ///     nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 1));
+    /// }
+    /// nav_graph.connect_points(1, 2);
+    /// nav_graph.connect_points(2, 3);
+    /// nav_graph.connect_points(1, 4);
+    /// nav_graph.connect_points(4, 3);
+    ///
+    /// nav_graph.set_owner(2, Some(7));
+    /// nav_graph.set_hostile(7, 1, true);
+    ///
+    /// let options = SearchOptions::new().as_faction(1, TerritoryAccess::Forbid);
+    /// assert_eq!(nav_graph.find_path_with(1, 3, &options).unwrap()[..], [1, 4, 3]);
+    /// // Factions which aren't hostile pass through as usual.
+    /// let options = SearchOptions::new().as_faction(2, TerritoryAccess::Forbid);
+    /// assert_eq!(nav_graph.find_path_with(1, 3, &options).unwrap()[..], [1, 2, 3]);
+    /// ```
+    pub fn set_owner(&mut self, id: u32, owner: Option<u32>) {
+        if !self.has_nav_point(id) {
+            return;
+        }
+        let changed = match owner {
+            Some(owner) => self.owners.insert(id, owner) != Some(owner),
+            None => self.owners.remove(&id).is_some(),
+        };
+        if changed {
+            self.topology_changed();
+        }
+    }
+
+    /// The faction which owns the specified point, if any.
+    pub fn owner(&self, id: u32) -> Option<u32> {
+        self.owners.get(&id).copied()
+    }
+
+    /// Sets whether two factions are hostile to each other. Hostility is mutual.
+    pub fn set_hostile(&mut self, a: u32, b: u32, hostile: bool) {
+        let pair = (a.min(b), a.max(b));
+        let changed = if hostile {
+            self.hostilities.insert(pair)
+        } else {
+            self.hostilities.remove(&pair)
+        };
+        if changed {
+            self.topology_changed();
+        }
+    }
+
+    /// Returns true if the two factions are hostile to each other.
+    pub fn are_hostile(&self, a: u32, b: u32) -> bool {
+        self.hostilities.contains(&(a.min(b), a.max(b)))
+    }
+
+    /// Returns true if the specified point is owned by a faction hostile to `faction`.
+    #[inline(always)]
+    pub fn is_hostile_to(&self, id: u32, faction: Option<u32>) -> bool {
+        if self.hostilities.is_empty() {
+            return false;
+        }
+        match (faction, self.owners.get(&id)) {
+            (Some(faction), Some(owner)) => self.are_hostile(faction, *owner),
+            _ => false,
+        }
+    }
+
+    /// Returns true if a traveler of `faction` with `access` may enter the specified point.
+    #[inline(always)]
+    pub(crate) fn may_enter(&self, id: u32, faction: Option<u32>, access: TerritoryAccess) -> bool {
+        access != TerritoryAccess::Forbid || !self.is_hostile_to(id, faction)
+    }
+}
+
+#[cfg(feature = "travelers")]
+type EnteringTravelerQuery<'w, 's, M> = Query<
+    'w,
+    's,
+    (Entity, &'static AutoTraveler, &'static TravelerPosition),
+    (<M as NavDomain>::Filter, Changed<TravelerPosition>),
+>;
+
+#[cfg(feature = "travelers")]
+pub(crate) fn detect_hostile_entries<M: NavDomain>(
+    travelers_query: EnteringTravelerQuery<M>,
+    graphs: TravelerGraphs<M>,
+    mut entered_events: EventWriter<EnteredHostileTerritory<M>>,
+) {
+    for (entity, auto_traveler, traveler_position) in travelers_query.iter() {
+        if auto_traveler.territory_access != TerritoryAccess::AllowWithEvent {
+            continue;
+        }
+        let (Some(next), Some(nav_graph)) = (
+            traveler_position.next_nav_point,
+            graphs.get(auto_traveler.graph),
+        ) else {
+            continue;
+        };
+        if nav_graph.is_hostile_to(next, auto_traveler.faction) {
+            entered_events.send(EnteredHostileTerritory::new(
+                entity,
+                next,
+                nav_graph.owner(next).unwrap(),
+            ));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "travelers"))]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::event::Events;
+    use bevy_math::Vec3;
+    use bevy_time::Time;
+    use bevy_transform::prelude::Transform;
+
+    use super::*;
+    use crate::{NavPoint, NavigatorPlugin};
+
+    #[test]
+    pub fn test_territory_access() {
+        let mut app = App::new();
+        app.add_plugin(NavigatorPlugin::new())
+            .insert_resource(Time::default());
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        for (id, x, y) in [(1, 0.0, 0.0), (2, 1.0, 0.0), (3, 2.0, 0.0), (4, 1.0, 1.0)] {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 2));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+        nav_graph.connect_points(1, 4);
+        nav_graph.connect_points(4, 3);
+        nav_graph.set_owner(2, Some(7));
+        nav_graph.set_hostile(1, 7, true);
+
+        let mut spawn = |access| {
+            let auto_traveler = AutoTraveler::new(1, 3, 1.0).with_faction(1, access);
+            app.world.spawn((auto_traveler, Transform::default())).id()
+        };
+        let forbidden = spawn(TerritoryAccess::Forbid);
+        let penalized = spawn(TerritoryAccess::Penalize(10.0));
+        let announced = spawn(TerritoryAccess::AllowWithEvent);
+        app.update();
+        app.update();
+
+        let path = |entity| app.world.get::<AutoTraveler>(entity).unwrap().path.clone();
+        assert_eq!(path(forbidden), Some(vec![1, 4, 3]));
+        assert_eq!(path(penalized), Some(vec![1, 4, 3]));
+        assert_eq!(path(announced), Some(vec![1, 2, 3]));
+
+        let entered = app.world.resource::<Events<EnteredHostileTerritory>>();
+        let entered: Vec<_> = entered.get_reader().iter(entered).copied().collect();
+        assert_eq!(entered, [EnteredHostileTerritory::new(announced, 2, 7)]);
+    }
+}
@@ -0,0 +1,306 @@
+//! Reference implementations and helpers for checking [`NavGraph::find_path`] against them,
+//! and a headless app for integration tests of navigation logic.
+//!
+//! Enabled by the `testing` feature. The reference implementations are useful when extending
+//! the cost function or search, to verify that paths found on randomly generated graphs are
+//! still valid and as cheap as the reference's. [`test_app`] and [`NavTestApp`] step travelers
+//! through time deterministically, frame by frame.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+#[cfg(feature = "travelers")]
+use bevy_app::App;
+#[cfg(feature = "travelers")]
+use bevy_ecs::entity::Entity;
+use bevy_math::Vec3;
+#[cfg(feature = "travelers")]
+use bevy_time::Time;
+#[cfg(feature = "travelers")]
+use bevy_utils::Duration;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+#[cfg(feature = "travelers")]
+use crate::{AutoTraveler, NavigatorPlugin};
+use crate::{NavDomain, NavGraph, NavPoint};
+
+/// Generates a graph of `points` points scattered randomly over a square, each connected to its
+/// `neighbors` nearest points, with random speed modifiers and around a tenth of the points full.
+///
+/// The same `seed` always generates the same graph, with IDs from 1 to `points`.
+pub fn random_graph(seed: u64, points: u32, neighbors: usize) -> NavGraph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let size = (points as f32).sqrt() * 2.0;
+
+    let mut nav_graph = NavGraph::new();
+    let mut locations = Vec::with_capacity(points as usize);
+    for id in 1..=points {
+        let location = Vec3::new(rng.gen_range(0.0..size), rng.gen_range(0.0..size), 0.0);
+        let speed_modifier = rng.gen_range(0.5..2.0);
+        nav_graph.add_nav_point(NavPoint::new(id, location, speed_modifier, 1));
+        locations.push((id, location));
+        if rng.gen_bool(0.1) {
+            nav_graph.occupy(id);
+        }
+    }
+
+    for &(id, location) in &locations {
+        let mut nearest: Vec<_> = locations.iter().filter(|(other, _)| *other != id).collect();
+        nearest.sort_by(|(_, a), (_, b)| {
+            location
+                .distance_squared(*a)
+                .total_cmp(&location.distance_squared(*b))
+        });
+        for (other, _) in nearest.into_iter().take(neighbors) {
+            nav_graph.connect_points(id, *other);
+        }
+    }
+    nav_graph
+}
+
+/// Finds the cheapest path between two points with plain Dijkstra, along with its cost.
+///
+/// Connections cost the same as in [`NavGraph::path_cost`], and full points other than `a` are
+/// impassable, matching [`NavGraph::find_path`] with the default options.
+pub fn reference_path<M: NavDomain>(
+    nav_graph: &NavGraph<M>,
+    a: u32,
+    b: u32,
+) -> Option<(Vec<u32>, u32)> {
+    nav_graph.get_nav_point(a)?;
+    nav_graph.get_nav_point(b)?;
+
+    let mut costs = HashMap::from([(a, 0_u32)]);
+    let mut came_from = HashMap::new();
+    let mut open_set = BinaryHeap::from([Reverse((0_u32, a))]);
+    while let Some(Reverse((cost, current))) = open_set.pop() {
+        if current == b {
+            let mut path = vec![b];
+            while let Some(prev) = came_from.get(path.last().unwrap()) {
+                path.push(*prev);
+            }
+            path.reverse();
+            return Some((path, cost));
+        }
+        if cost > costs[&current] {
+            continue;
+        }
+
+        for &next in nav_graph.get_nav_point(current)?.connections() {
+            if !nav_graph.can_occupy(next) {
+                continue;
+            }
+            let next_cost = cost.saturating_add(nav_graph.path_cost(&[current, next])?);
+            if next_cost < *costs.get(&next).unwrap_or(&u32::MAX) {
+                costs.insert(next, next_cost);
+                came_from.insert(next, current);
+                open_set.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    None
+}
+
+/// How a path found by [`NavGraph::find_path`] differs from [`reference_path`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathMismatch {
+    /// The path doesn't lead from the origin to the destination along connections between
+    /// passable points.
+    Invalid(Vec<u32>),
+    /// No path was found, but the reference found one.
+    Missed { reference: Vec<u32> },
+    /// A path was found, but the reference found none.
+    Unexpected(Vec<u32>),
+    /// The path costs more than allowed compared to the reference.
+    Suboptimal {
+        path: Vec<u32>,
+        cost: u32,
+        reference: Vec<u32>,
+        reference_cost: u32,
+    },
+}
+
+/// Checks the result of [`NavGraph::find_path`] between two points against [`reference_path`].
+///
+/// The path must be valid, found exactly when the reference finds one, and cost at most
+/// `max_cost_ratio` times as much as the reference's.
+///
+/// ## Example
+/// ```
+/// # use bevy_navigator::testing::{check_path, random_graph};
+/// for seed in 0..10 {
+///     let nav_graph = random_graph(seed, 50, 3);
+///     // From 1 to a far point, and to each of its neighbors.
+///     let neighbors = nav_graph.get_nav_point(1).unwrap().connections().clone();
+///     for b in neighbors.into_iter().chain([50]) {
+///         assert_eq!(check_path(&nav_graph, 1, b, 1.0), Ok(()));
+///     }
+/// }
+/// ```
+pub fn check_path<M: NavDomain>(
+    nav_graph: &NavGraph<M>,
+    a: u32,
+    b: u32,
+    max_cost_ratio: f32,
+) -> Result<(), PathMismatch> {
+    let reference = reference_path(nav_graph, a, b);
+    let path = match (nav_graph.find_path(a, b), reference.as_ref()) {
+        (None, None) => return Ok(()),
+        (None, Some((reference, _))) => {
+            return Err(PathMismatch::Missed {
+                reference: reference.clone(),
+            })
+        }
+        (Some(path), None) => return Err(PathMismatch::Unexpected(path)),
+        (Some(path), Some(_)) => path,
+    };
+
+    let passable = path.iter().skip(1).all(|id| nav_graph.can_occupy(*id));
+    let cost = nav_graph.path_cost(&path);
+    let (Some(cost), true) = (
+        cost,
+        passable && path.first() == Some(&a) && path.last() == Some(&b),
+    ) else {
+        return Err(PathMismatch::Invalid(path));
+    };
+
+    let (reference, reference_cost) = reference.unwrap();
+    if cost as f32 > reference_cost as f32 * max_cost_ratio {
+        return Err(PathMismatch::Suboptimal {
+            path,
+            cost,
+            reference,
+            reference_cost,
+        });
+    }
+    Ok(())
+}
+
+/// A headless app with the [`NavigatorPlugin`] for the default domain and a [`Time`] which only
+/// moves on when stepped with [`NavTestApp::step`], so tests run the same on every machine.
+///
+/// ## Example
+/// ```
+/// # use bevy_transform::prelude::Transform;
+/// # use bevy_navigator::{AutoTraveler, GridConnectivity, NavGraph};
+/// # use bevy_navigator::testing::{test_app, NavTestApp};
+/// let mut app = test_app();
+/// let (nav_graph, _) = NavGraph::<()>::from_grid(4, 1, 1.0, GridConnectivity::Four);
+/// app.insert_resource(nav_graph);
+/// let entity = app
+///     .world
+///     .spawn((Transform::default(), AutoTraveler::new(1, 4, 1.0)))
+///     .id();
+///
+/// let seconds = app.advance_until_arrived(entity, 0.1, 10.0);
+/// assert!(seconds >= 3.0);
+/// app.assert_occupancy(4, 1);
+/// ```
+#[cfg(feature = "travelers")]
+pub fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugin(NavigatorPlugin::new())
+        .insert_resource(Time::default());
+    app
+}
+
+/// Time stepping and assertions for apps made with [`test_app`].
+#[cfg(feature = "travelers")]
+pub trait NavTestApp {
+    /// Moves time on by `seconds` and runs one update. The first step only starts the clock, so
+    /// nothing moves during it.
+    fn step(&mut self, seconds: f32);
+
+    /// Steps `step` seconds at a time until `entity` has arrived, i.e. no longer has an
+    /// [`AutoTraveler`], returning how many seconds that took.
+    ///
+    /// Panics if it hasn't arrived within `max_seconds`.
+    fn advance_until_arrived(&mut self, entity: Entity, step: f32, max_seconds: f32) -> f32;
+
+    /// Panics unless the point `id` of the default domain's graph has exactly `expected`
+    /// occupants.
+    fn assert_occupancy(&self, id: u32, expected: u32);
+}
+
+#[cfg(feature = "travelers")]
+impl NavTestApp for App {
+    fn step(&mut self, seconds: f32) {
+        let mut time = self.world.resource_mut::<Time>();
+        let now = match time.last_update() {
+            Some(last_update) => last_update + Duration::from_secs_f32(seconds),
+            None => time.startup(),
+        };
+        time.update_with_instant(now);
+        self.update();
+    }
+
+    fn advance_until_arrived(&mut self, entity: Entity, step: f32, max_seconds: f32) -> f32 {
+        let mut elapsed = 0.0;
+        if self.world.resource::<Time>().last_update().is_none() {
+            self.step(0.0);
+        }
+        while self.world.get::<AutoTraveler>(entity).is_some() {
+            assert!(
+                elapsed < max_seconds,
+                "{entity:?} didn't arrive within {max_seconds} seconds"
+            );
+            self.step(step);
+            elapsed += step;
+        }
+        elapsed
+    }
+
+    fn assert_occupancy(&self, id: u32, expected: u32) {
+        let occupancy = self
+            .world
+            .resource::<NavGraph>()
+            .get_nav_point(id)
+            .unwrap_or_else(|| panic!("no point {id}"))
+            .current_occupancy();
+        assert_eq!(occupancy, expected, "occupancy of point {id}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_find_path_matches_reference_on_random_graphs() {
+        // The default heuristic never overestimates, so the paths found are the cheapest.
+        for seed in 0..20 {
+            let nav_graph = random_graph(seed, 100, 4);
+            // From every point to each of its neighbors, which needn't be reached directly,
+            // and to a point elsewhere on the graph.
+            for a in 1..=100 {
+                let point = nav_graph.get_nav_point(a).unwrap();
+                let elsewhere = a * 37 % 100 + 1;
+                for &b in point.connections().iter().chain([&elsewhere]) {
+                    let result = check_path(&nav_graph, a, b, 1.0);
+                    assert_eq!(result, Ok(()), "seed {seed}, {a} to {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "travelers")]
+    #[should_panic(expected = "didn't arrive")]
+    pub fn test_app_gives_up_on_blocked_travelers() {
+        use bevy_transform::prelude::Transform;
+
+        let mut app = test_app();
+        let (mut nav_graph, _) =
+            NavGraph::<()>::from_grid(3, 1, 1.0, crate::GridConnectivity::Four);
+        nav_graph.occupy(3);
+        app.insert_resource(nav_graph);
+        let entity = app
+            .world
+            .spawn((Transform::default(), AutoTraveler::new(1, 3, 1.0)))
+            .id();
+        app.assert_occupancy(3, 1);
+        app.advance_until_arrived(entity, 0.5, 5.0);
+    }
+}
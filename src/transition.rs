@@ -0,0 +1,479 @@
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    entity::Entity,
+    event::Events,
+    query::With,
+    system::Command,
+    world::{Mut, World},
+};
+use bevy_transform::prelude::Transform;
+use bevy_utils::HashMap;
+
+use crate::{
+    lod::LowFidelity,
+    queue::PendingPath,
+    traveler::{NoPath, TravelerPosition},
+    AutoTraveler, Carrier, NavDomain, NavGraph, NavGraphId, NavGraphs, PathPreviewRequest,
+    PathRequestQueue, PathWarmStart, Replan, ResumeTravel, SuspendTravel, TravelAborted,
+};
+
+/// What happens to active travelers when their [`NavGraph`] is replaced. See [`ReplaceNavGraph`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GraphTransition {
+    /// Every traveler stops, sending [`TravelAborted`].
+    #[default]
+    Cancel,
+    /// Travelers are moved to the points of the new graph nearest to where they are and where
+    /// they were heading, and planned again. Points listed in the [`ReplaceNavGraph::id_remap`]
+    /// are taken from there instead.
+    ///
+    /// Travelers following a fixed path keep it only if every point left on it is in the remap.
+    /// Travelers which can't be re-homed are cancelled.
+    Rehome,
+}
+
+/// Replaces the [`NavGraph`] of the domain `M` wholesale, e.g. on a level change, or one of its
+/// [`NavGraphs`] with [`ReplaceNavGraph::on_graph`].
+///
+/// Simply overwriting the resource leaves travelers holding the IDs of the old graph, which may
+/// well exist in the new one but mean something else entirely. Instead, this command first
+/// cancels or re-homes every active traveler according to its [`GraphTransition`], and removes
+/// the [`TravelerPosition`]s of travelers which have already arrived. Nothing the travelers
+/// occupied in the old graph is released into the new one.
+///
+/// ## Example
+/// ```
+/// # use bevy_ecs::system::Commands;
+/// # use bevy_navigator::{GraphTransition, NavGraph, ReplaceNavGraph};
+/// fn load_level(mut commands: Commands) {
+///     let nav_graph = NavGraph::new();
+///     // ...
+///     commands.add(ReplaceNavGraph::new(nav_graph).with_transition(GraphTransition::Rehome));
+/// }
+/// ```
+pub struct ReplaceNavGraph<M: NavDomain = ()> {
+    pub nav_graph: NavGraph<M>,
+    pub transition: GraphTransition,
+    /// The ID in the new graph of points of the old graph which survive the change, for
+    /// [`GraphTransition::Rehome`].
+    pub id_remap: HashMap<u32, u32>,
+    /// The graph in [`NavGraphs`] to replace, or `None` for the [`NavGraph`] resource.
+    pub graph: Option<NavGraphId>,
+}
+
+impl<M: NavDomain> ReplaceNavGraph<M> {
+    pub fn new(nav_graph: NavGraph<M>) -> Self {
+        Self {
+            nav_graph,
+            transition: GraphTransition::default(),
+            id_remap: HashMap::default(),
+            graph: None,
+        }
+    }
+
+    /// Swaps in a regenerated graph, e.g. after major world edits, re-homing travelers through
+    /// `id_remap` where it has their points and by position where it doesn't.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_ecs::system::Commands;
+    /// # use bevy_navigator::{NavGraph, ReplaceNavGraph};
+    /// # use bevy_utils::HashMap;
+    /// fn regenerate(mut commands: Commands) {
+    ///     let nav_graph = NavGraph::new();
+    ///     let mut id_remap = HashMap::default();
+    ///     // The point with ID 7 before the edit is the one with ID 12 after it.
+    ///     id_remap.insert(7, 12);
+    ///     // ...
+    ///     commands.add(ReplaceNavGraph::preserving_travelers(nav_graph, id_remap));
+    /// }
+    /// ```
+    pub fn preserving_travelers(nav_graph: NavGraph<M>, id_remap: HashMap<u32, u32>) -> Self {
+        Self::new(nav_graph)
+            .with_transition(GraphTransition::Rehome)
+            .with_id_remap(id_remap)
+    }
+
+    pub fn with_transition(mut self, transition: GraphTransition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    pub fn with_id_remap(mut self, id_remap: HashMap<u32, u32>) -> Self {
+        self.id_remap = id_remap;
+        self
+    }
+
+    /// Replaces the graph `graph` in [`NavGraphs`], adding it if it isn't there, rather than the
+    /// [`NavGraph`] resource. Only travelers on that graph are transitioned.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_ecs::system::Commands;
+    /// # use bevy_navigator::{GraphTransition, NavGraph, NavGraphId, ReplaceNavGraph};
+    /// const TAVERN: NavGraphId = NavGraphId::named("tavern");
+    ///
+    /// fn rebuild_tavern(mut commands: Commands) {
+    ///     let interior = NavGraph::new();
+    ///     // ...
+    ///     commands.add(
+    ///         ReplaceNavGraph::new(interior)
+    ///             .on_graph(TAVERN)
+    ///             .with_transition(GraphTransition::Rehome),
+    ///     );
+    /// }
+    /// ```
+    pub fn on_graph(mut self, graph: NavGraphId) -> Self {
+        self.graph = Some(graph);
+        self
+    }
+}
+
+impl<M: NavDomain> Command for ReplaceNavGraph<M> {
+    fn write(self, world: &mut World) {
+        let new_graph = self.nav_graph;
+        let graph = self.graph;
+        // Entities without an `AutoTraveler` can only be on the `NavGraph` resource.
+        let on_graph = |auto_traveler: Option<&AutoTraveler>| {
+            auto_traveler.map_or(graph.is_none(), |auto_traveler| {
+                auto_traveler.graph == graph
+            })
+        };
+        let travelers: Vec<Entity> = world
+            .query_filtered::<(Entity, &AutoTraveler), M::Filter>()
+            .iter(world)
+            .filter(|(_, auto_traveler)| on_graph(Some(auto_traveler)))
+            .map(|(entity, _)| entity)
+            .collect();
+        let positioned: Vec<Entity> = world
+            .query_filtered::<(Entity, Option<&AutoTraveler>), (With<TravelerPosition>, M::Filter)>(
+            )
+            .iter(world)
+            .filter(|(_, auto_traveler)| on_graph(*auto_traveler))
+            .map(|(entity, _)| entity)
+            .collect();
+
+        let mut aborted = Vec::new();
+        let mut rehomed = Vec::new();
+        {
+            let empty = NavGraph::<M>::default();
+            let old_graph = match graph {
+                None => world.resource::<NavGraph<M>>(),
+                Some(id) => world
+                    .get_resource::<NavGraphs<M>>()
+                    .and_then(|graphs| graphs.get(id))
+                    .unwrap_or(&empty),
+            };
+            for &entity in &travelers {
+                let rehome = match self.transition {
+                    GraphTransition::Cancel => None,
+                    GraphTransition::Rehome => {
+                        rehome_points(world, old_graph, &new_graph, &self.id_remap, entity)
+                    }
+                };
+                match rehome {
+                    Some(points) => rehomed.push((entity, points)),
+                    None => aborted.push(entity),
+                }
+            }
+        }
+
+        for entity in positioned {
+            let mut entity = world.entity_mut(entity);
+            entity.remove::<TravelerPosition>();
+            if let Some(mut carrier) = entity.get_mut::<Carrier>() {
+                carrier.last_position = None;
+            }
+        }
+        for &entity in &aborted {
+            let mut entity_mut = world.entity_mut(entity);
+            let destination = entity_mut.get::<AutoTraveler>().unwrap().destination;
+            entity_mut.remove_intersection::<(
+                AutoTraveler,
+                NoPath,
+                Replan,
+                SuspendTravel,
+                ResumeTravel,
+                LowFidelity,
+                PendingPath,
+            )>();
+            if let Some(mut events) = world.get_resource_mut::<Events<TravelAborted<M>>>() {
+                events.send(TravelAborted::new(entity, destination));
+            }
+        }
+        for (entity, rehomed) in rehomed {
+            let mut entity_mut = world.entity_mut(entity);
+            // Searches still running were started on the old graph.
+            entity_mut.remove_intersection::<(NoPath, Replan, ResumeTravel, PendingPath)>();
+            if let Some(mut warm_start) = entity_mut.get_mut::<PathWarmStart>() {
+                warm_start.clear();
+            }
+            let mut auto_traveler = entity_mut.get_mut::<AutoTraveler>().unwrap();
+            auto_traveler.origin = rehomed.origin;
+            auto_traveler.destination = rehomed.destination;
+            auto_traveler.further_destinations = rehomed.further_destinations;
+            auto_traveler.path = rehomed.fixed_path;
+            auto_traveler.current_index = 0;
+            auto_traveler.blocked_time = 0.0;
+            // Ranked by the new graph, which isn't in place yet.
+            world.resource_scope(|world, mut queue: Mut<PathRequestQueue<M>>| {
+                queue.push(
+                    entity,
+                    world.get::<AutoTraveler>(entity).unwrap(),
+                    Some(&new_graph),
+                );
+            });
+        }
+
+        // Previews are recomputed against the new graph.
+        world
+            .query_filtered::<(&mut PathPreviewRequest, Option<&AutoTraveler>), M::Filter>()
+            .for_each_mut(world, |(mut request, auto_traveler)| {
+                if on_graph(auto_traveler) {
+                    request.set_changed();
+                }
+            });
+
+        match graph {
+            None => world.insert_resource(new_graph),
+            Some(id) => {
+                world
+                    .get_resource_or_insert_with(NavGraphs::<M>::default)
+                    .insert(id, new_graph);
+            }
+        }
+    }
+}
+
+/// Where a re-homed traveler starts from and is heading in the new graph.
+struct Rehomed {
+    origin: u32,
+    destination: u32,
+    further_destinations: Vec<u32>,
+    fixed_path: Option<Vec<u32>>,
+}
+
+/// The points of `new_graph` a traveler's points map to through `id_remap`, or else those
+/// nearest to where it is and where it's heading, or `None` if it can't be re-homed.
+fn rehome_points<M: NavDomain>(
+    world: &World,
+    old_graph: &NavGraph<M>,
+    new_graph: &NavGraph<M>,
+    id_remap: &HashMap<u32, u32>,
+    entity: Entity,
+) -> Option<Rehomed> {
+    let auto_traveler = world.get::<AutoTraveler>(entity)?;
+    let traveler_position = world.get::<TravelerPosition>(entity);
+    let current = traveler_position.map_or(auto_traveler.origin, |p| p.current_nav_point);
+    let remapped = |id: u32| {
+        id_remap
+            .get(&id)
+            .copied()
+            .filter(|id| new_graph.get_nav_point(*id).is_some())
+    };
+    let rehome = |id: u32| {
+        remapped(id).or_else(|| new_graph.nearest_point(old_graph.get_nav_point(id)?.location()))
+    };
+
+    let further_destinations = auto_traveler
+        .further_destinations
+        .iter()
+        .map(|id| rehome(*id))
+        .collect::<Option<_>>()?;
+
+    if auto_traveler.fixed_path {
+        // Planning takes the path from the traveler's current point.
+        let start = traveler_position.map_or(0, |_| auto_traveler.current_index);
+        let path: Vec<u32> = auto_traveler
+            .path
+            .as_ref()?
+            .get(start..)?
+            .iter()
+            .map(|id| remapped(*id))
+            .collect::<Option<_>>()?;
+        return Some(Rehomed {
+            origin: *path.first()?,
+            destination: *path.last()?,
+            further_destinations,
+            fixed_path: Some(path),
+        });
+    }
+
+    let origin = match remapped(current) {
+        Some(origin) => origin,
+        None => {
+            let position = match world.get::<Transform>(entity) {
+                Some(transform) => transform.translation,
+                None => old_graph.get_nav_point(current)?.location(),
+            };
+            new_graph.nearest_point(position)?
+        }
+    };
+    let destination = match auto_traveler.destination_position {
+        // Resolved again when the traveler is planned.
+        Some(_) => auto_traveler.destination,
+        None => rehome(auto_traveler.destination)?,
+    };
+    Some(Rehomed {
+        origin,
+        destination,
+        further_destinations,
+        fixed_path: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_math::Vec3;
+    use bevy_time::Time;
+
+    use super::*;
+    use crate::{NavPoint, NavigatorPlugin, OriginClaim};
+
+    #[test]
+    pub fn test_replacing_graph_cancels_or_rehomes() {
+        let mut app = App::new();
+        app.add_plugin(NavigatorPlugin::new())
+            .insert_resource(Time::default());
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+
+        let rehomed = app
+            .world
+            .spawn((AutoTraveler::new(1, 2, 1.0), Transform::default()))
+            .id();
+        let fixed = app
+            .world
+            .spawn((
+                AutoTraveler::new(1, 2, 1.0).with_fixed_path(vec![1, 2]),
+                Transform::default(),
+            ))
+            .id();
+        app.update();
+
+        // The same IDs exist in the new graph, but somewhere else entirely.
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(50.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(51.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(5, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(6, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(5, 6);
+        ReplaceNavGraph::new(nav_graph)
+            .with_transition(GraphTransition::Rehome)
+            .write(&mut app.world);
+
+        assert!(app.world.get::<AutoTraveler>(fixed).is_none());
+        assert!(app.world.get::<TravelerPosition>(fixed).is_none());
+        let aborted = app.world.resource::<Events<TravelAborted>>();
+        let aborted: Vec<_> = aborted.get_reader().iter(aborted).copied().collect();
+        assert_eq!(aborted, [TravelAborted::new(fixed, 2)]);
+
+        app.update();
+        let auto_traveler = app.world.get::<AutoTraveler>(rehomed).unwrap();
+        assert_eq!((auto_traveler.origin, auto_traveler.destination), (5, 6));
+        assert_eq!(auto_traveler.path.as_deref(), Some(&[5, 6][..]));
+        assert!(app.world.resource::<NavGraph>().can_occupy(1));
+    }
+
+    #[test]
+    pub fn test_rehoming_through_id_remap() {
+        let mut app = App::new();
+        app.add_plugin(NavigatorPlugin::new())
+            .insert_resource(Time::default());
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        for id in 1..=3 {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 2));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+
+        let planned = app
+            .world
+            .spawn((AutoTraveler::new(1, 3, 1.0), Transform::default()))
+            .id();
+        let fixed = app
+            .world
+            .spawn((
+                AutoTraveler::new(1, 3, 1.0).with_fixed_path(vec![1, 2, 3]),
+                Transform::default(),
+            ))
+            .id();
+        app.update();
+
+        // The regenerated graph has a point closer to where 1 was than the one it became.
+        let mut nav_graph = NavGraph::new();
+        for id in 11..=13 {
+            let x = (id - 10) as f32;
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, 0.5, 0.0), 1.0, 2));
+        }
+        nav_graph.add_nav_point(NavPoint::new(14, Vec3::new(1.0, 0.0, 0.0), 1.0, 2));
+        nav_graph.connect_points(11, 12);
+        nav_graph.connect_points(12, 13);
+        nav_graph.connect_points(11, 14);
+        let id_remap = [(1, 11), (2, 12), (3, 13)].into_iter().collect();
+        ReplaceNavGraph::preserving_travelers(nav_graph, id_remap).write(&mut app.world);
+
+        app.update();
+        for entity in [planned, fixed] {
+            let auto_traveler = app.world.get::<AutoTraveler>(entity).unwrap();
+            assert_eq!(auto_traveler.path.as_deref(), Some(&[11, 12, 13][..]));
+        }
+        assert!(app.world.get::<AutoTraveler>(fixed).unwrap().fixed_path);
+    }
+
+    #[test]
+    pub fn test_rehoming_drops_async_searches() {
+        let mut app = App::new();
+        app.add_plugin(NavigatorPlugin::new().with_async_pathfinding())
+            .insert_resource(Time::default());
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        for id in 1..=4 {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
+        }
+        for id in 1..4 {
+            nav_graph.connect_points(id, id + 1);
+        }
+        let traveler = app
+            .world
+            .spawn((
+                AutoTraveler::new(1, 4, 1.0).with_origin_claim(OriginClaim::Queue),
+                Transform::default(),
+            ))
+            .id();
+        app.update();
+        assert!(app.world.get::<PendingPath>(traveler).is_some());
+
+        // The same layout under new IDs, swapped in while the search is running. Only the new
+        // search claims its origin in the new graph.
+        let mut nav_graph = NavGraph::new();
+        for id in 11..=14 {
+            let x = (id - 10) as f32;
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, 0.0, 0.0), 1.0, 1));
+        }
+        for id in 11..14 {
+            nav_graph.connect_points(id, id + 1);
+        }
+        ReplaceNavGraph::new(nav_graph)
+            .with_transition(GraphTransition::Rehome)
+            .write(&mut app.world);
+        assert!(app.world.get::<PendingPath>(traveler).is_none());
+
+        for _ in 0..1000 {
+            if app.world.get::<TravelerPosition>(traveler).is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            app.update();
+        }
+        let auto_traveler = app.world.get::<AutoTraveler>(traveler).unwrap();
+        assert_eq!(auto_traveler.path.as_deref(), Some(&[11, 12, 13, 14][..]));
+        let nav_graph = app.world.resource::<NavGraph>();
+        let occupancy: Vec<u32> = (11..=14)
+            .map(|id| nav_graph.get_nav_point(id).unwrap().current_occupancy())
+            .collect();
+        assert_eq!(occupancy, [1, 0, 0, 0]);
+    }
+}
@@ -1,49 +1,97 @@
+use std::sync::Arc;
+
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    query::{Added, Without},
-    system::{Commands, Query, Res, ResMut},
+    event::EventWriter,
+    query::{Added, Or, With, Without, WorldQuery},
+    system::{Commands, Local, ParamSet, Query, Res, ResMut},
 };
+use bevy_math::{Quat, Vec3};
 use bevy_reflect::{FromReflect, Reflect};
+use bevy_tasks::AsyncComputeTaskPool;
 use bevy_time::Time;
 use bevy_transform::prelude::Transform;
-use bevy_utils::tracing::info;
+use bevy_utils::{tracing::info, HashMap};
 
-use crate::NavGraph;
+use crate::graphs::{TravelerGraphs, TravelerGraphsMut};
+use crate::queue::PendingPath;
+use crate::stagger::{AwaitingDeparture, DepartureSlots, DepartureStagger};
+use crate::{
+    lod::LowFidelity, turn::TurnBased, AreaCosts, AvailabilityMode, AwaitingDestination,
+    CooperativePlanning, DestinationQueued, DestinationSubstituted, Heuristic, LodPolicy, NavClock,
+    NavDomain, NavGraph, NavGraphId, PathCache, PathNoise, PathRequestQueue, PathResult,
+    PathWarmStart, SearchOptions, SpeedModifiers, TerritoryAccess, TrafficLanes, TravelCompleted,
+    TravelFailed, TravelHistory,
+};
 
-#[derive(Debug, Reflect, FromReflect, Clone, Copy)]
+#[derive(Debug, Default, Reflect, FromReflect, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "asset", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlockedBehavior {
     Wait,
+    #[default]
     Recompute,
+    /// Weighs how long the blocking point is expected to stay full (see
+    /// [`NavGraph::expected_wait`]) against the extra cost of a route around it, and takes
+    /// whichever is cheaper. Waiting costs as much as a connection as long as the distance the
+    /// traveler could have covered in the meantime.
+    ///
+    /// The choice is made when the traveler is first blocked, and again each time it has waited
+    /// as long as expected. Without any record of the point's releases, the detour is taken if
+    /// there is one.
+    Adaptive,
 }
 
-impl Default for BlockedBehavior {
-    fn default() -> Self {
-        Self::Recompute
-    }
-}
-
-#[derive(Debug, Reflect, FromReflect, Clone, Copy)]
+#[derive(Debug, Default, Reflect, FromReflect, Clone, Copy)]
 pub enum DestinationBehavior {
+    /// The traveler waits for its destination to free up, however long it takes.
+    #[default]
     Exactly,
+    /// If the destination is full when the traveler is about to move onto it, travel ends at
+    /// the free point closest to the destination within this distance of it instead, sending
+    /// [`DestinationSubstituted`]. The traveler's own point counts as free. Without such a
+    /// point, the traveler is blocked as usual.
     WithinRadius(f32),
 }
 
-impl Default for DestinationBehavior {
-    fn default() -> Self {
-        Self::Exactly
-    }
-}
-
-#[derive(Debug, Reflect, FromReflect, Clone, Copy)]
+#[derive(Debug, Default, Reflect, FromReflect, Clone, Copy)]
 pub enum PathBehavior {
+    /// The path is computed once when the traveler is added.
+    #[default]
     Precompute,
+    /// The rest of the path is recomputed each time the traveler reaches a point.
+    ///
+    /// Pair with a [`PathWarmStart`] to skip the search while the route ahead stays clear.
     ProgressiveRecompute,
 }
 
-impl Default for PathBehavior {
+/// Whether a traveler occupies its origin [`NavPoint`](crate::NavPoint) when its path is planned.
+#[derive(Debug, Default, Reflect, FromReflect, Clone, Copy, PartialEq, Eq)]
+pub enum OriginClaim {
+    /// The origin's occupancy is left alone, and is expected to be managed by the caller.
+    #[default]
+    Disabled,
+    /// The origin is occupied. If it's full, planning is retried on a later frame.
+    Queue,
+    /// The origin is occupied. If it's full, travel fails with [`TravelFailed`].
+    Fail,
+}
+
+/// Controls when a blocked traveler gives up waiting and takes a detour instead.
+#[derive(Debug, Reflect, FromReflect, Clone, Copy)]
+pub struct DetourSettings {
+    /// How long, in seconds, to wait at a blocked node before looking for a detour.
+    pub wait_threshold: f32,
+    /// A detour is only taken if its cost is at most this multiple of the direct route's cost.
+    pub max_cost_factor: f32,
+}
+
+impl Default for DetourSettings {
     fn default() -> Self {
-        Self::Precompute
+        Self {
+            wait_threshold: 2.0,
+            max_cost_factor: 1.5,
+        }
     }
 }
 
@@ -57,6 +105,83 @@ pub struct AutoTraveler {
     pub blocked_behavior: BlockedBehavior,
     pub destination_behavior: DestinationBehavior,
     pub path_behavior: PathBehavior,
+    pub detour: Option<DetourSettings>,
+    /// Accepts ending travel at any point within this many connections of the destination.
+    ///
+    /// See [`SearchOptions::goal_tolerance`].
+    pub goal_tolerance: u32,
+    /// If set, `origin` is replaced by the [`NavPoint`](crate::NavPoint) nearest the entity's
+    /// [`Transform`] when the traveler is added.
+    pub resolve_origin: bool,
+    /// If set, `destination` is replaced by the [`NavPoint`](crate::NavPoint) nearest this
+    /// position which is reachable from the origin, when the path is planned.
+    pub destination_position: Option<Vec3>,
+    /// If set, the traveler turns toward this rotation over its final segment, ending travel
+    /// facing it exactly.
+    pub arrival_facing: Option<Quat>,
+    pub origin_claim: OriginClaim,
+    /// The corridor reservation group this traveler belongs to.
+    ///
+    /// See [`NavGraph::reserve_corridor`].
+    pub group: Option<u32>,
+    /// The magnitude of random noise added to connection costs when planning. See [`PathNoise`].
+    pub path_noise: f32,
+    /// The seed for `path_noise`. If 0 when the traveler is planned, it's seeded from the entity.
+    pub path_noise_seed: u64,
+    /// If set, planning checks [`Availability`](crate::Availability) windows against the time
+    /// each point would be reached, according to the [`NavClock`].
+    ///
+    /// Either way, the traveler waits before moving onto a closed point or connection.
+    pub availability: Option<AvailabilityMode>,
+    /// The maximum number of points in the traveler's path. See [`SearchOptions::max_nodes`].
+    pub max_path_nodes: Option<usize>,
+    /// The faction the traveler belongs to. See [`NavGraph::set_owner`].
+    pub faction: Option<u32>,
+    /// How the traveler treats points owned by factions hostile to its own.
+    pub territory_access: TerritoryAccess,
+    /// How many slots of each [`NavPoint`](crate::NavPoint)'s max_occupancy the traveler takes
+    /// up, so large creatures crowd out more small ones. It's blocked by points without that
+    /// much room left, and plans around them. Defaults to 1.
+    pub occupancy_weight: u32,
+    /// If set, `path` was authored by hand and is followed exactly: it's never planned, replanned
+    /// or detoured around. See [`AutoTraveler::with_fixed_path`].
+    pub fixed_path: bool,
+    /// How long, in seconds, the traveler has been waiting for its next node to free up.
+    pub blocked_time: f32,
+    /// Destinations still to visit, in order, once `destination` is reached. See
+    /// [`AutoTraveler::then_visit`].
+    pub further_destinations: Vec<u32>,
+    /// How soon the traveler is planned relative to others waiting in the
+    /// [`PathRequestQueue`], higher first, under
+    /// [`PathRequestOrder::Priority`](crate::PathRequestOrder::Priority).
+    pub path_priority: i32,
+    /// The name of the [`NavigationProfile`](crate::NavigationProfile) in
+    /// [`NavigationProfiles`](crate::NavigationProfiles) whose values replace the traveler's own.
+    pub profile: Option<String>,
+    /// See [`SearchOptions::heuristic`].
+    pub heuristic: Option<Heuristic>,
+    /// See [`SearchOptions::epsilon`].
+    pub epsilon: Option<f32>,
+    /// The traversal flags of the traveler. See [`SearchOptions::traversal_mask`].
+    pub traversal_mask: u32,
+    /// See [`SearchOptions::turn_penalty`].
+    pub turn_penalty: f32,
+    /// See [`SearchOptions::allowed_regions`].
+    pub allowed_regions: Option<Vec<u32>>,
+    /// How the traveler weighs each area type, both when planning and in how fast it moves.
+    /// See [`AreaCosts`].
+    pub area_costs: Option<AreaCosts>,
+    /// The radius of the traveler, which keeps it out of points with less clearance. See
+    /// [`NavPoint::clearance`](crate::NavPoint::clearance).
+    pub size: f32,
+    /// See [`SearchOptions::congestion_cost`].
+    pub congestion_cost: f32,
+    /// The graph in [`NavGraphs`](crate::NavGraphs) the traveler paths against, or `None` for
+    /// the domain's [`NavGraph`]. Its points are all in that graph.
+    ///
+    /// To move a traveler to another graph, release whatever it occupies in the one it leaves and
+    /// give it a new `AutoTraveler` with an origin in the other.
+    pub graph: Option<NavGraphId>,
 }
 
 impl Default for AutoTraveler {
@@ -70,6 +195,34 @@ impl Default for AutoTraveler {
             blocked_behavior: BlockedBehavior::default(),
             destination_behavior: DestinationBehavior::default(),
             path_behavior: PathBehavior::default(),
+            detour: None,
+            goal_tolerance: 0,
+            resolve_origin: false,
+            destination_position: None,
+            arrival_facing: None,
+            origin_claim: OriginClaim::default(),
+            group: None,
+            path_noise: 0.0,
+            path_noise_seed: 0,
+            availability: None,
+            max_path_nodes: None,
+            faction: None,
+            territory_access: TerritoryAccess::default(),
+            occupancy_weight: 1,
+            fixed_path: false,
+            blocked_time: 0.0,
+            further_destinations: Vec::new(),
+            path_priority: 0,
+            profile: None,
+            heuristic: None,
+            epsilon: None,
+            traversal_mask: 0,
+            turn_penalty: 0.0,
+            allowed_regions: None,
+            area_costs: None,
+            size: 0.0,
+            congestion_cost: 0.0,
+            graph: None,
         }
     }
 }
@@ -84,6 +237,167 @@ impl AutoTraveler {
         }
     }
 
+    /// Creates a traveler which starts from the [`NavPoint`](crate::NavPoint) nearest to the
+    /// entity's [`Transform`], so the caller doesn't need to know which point it stands on.
+    ///
+    /// The origin is resolved when the traveler is added. If the entity has no [`Transform`] or
+    /// the graph is empty, no path will be found.
+    pub fn from_position(destination: u32, speed: f32) -> Self {
+        Self {
+            destination,
+            speed,
+            resolve_origin: true,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a traveler heading to the reachable [`NavPoint`](crate::NavPoint) nearest to
+    /// `position`.
+    ///
+    /// See [`NavGraph::pick_reachable_point`] for how the destination is chosen.
+    pub fn to_position(origin: u32, position: Vec3, speed: f32) -> Self {
+        Self::new(origin, 0, speed).with_destination_position(position)
+    }
+
+    /// Resolves the destination from a world position when the path is planned, rather than
+    /// using `destination` directly.
+    pub fn with_destination_position(mut self, position: Vec3) -> Self {
+        self.destination_position = Some(position);
+        self
+    }
+
+    /// Blends the traveler's rotation into `facing` over the last segment of its path.
+    pub fn with_arrival_facing(mut self, facing: Quat) -> Self {
+        self.arrival_facing = Some(facing);
+        self
+    }
+
+    /// Has planning occupy the origin on the traveler's behalf. See [`OriginClaim`].
+    pub fn with_origin_claim(mut self, origin_claim: OriginClaim) -> Self {
+        self.origin_claim = origin_claim;
+        self
+    }
+
+    /// Makes the traveler a member of a corridor reservation group.
+    pub fn with_group(mut self, group: u32) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Adds random noise of up to `magnitude` (as a fraction of each connection's cost) when
+    /// planning, so crowds of identical travelers spread across near-optimal routes.
+    pub fn with_path_noise(mut self, magnitude: f32) -> Self {
+        self.path_noise = magnitude;
+        self
+    }
+
+    /// Plans around [`Availability`](crate::Availability) windows. See
+    /// [`AutoTraveler::availability`].
+    pub fn with_availability(mut self, mode: AvailabilityMode) -> Self {
+        self.availability = Some(mode);
+        self
+    }
+
+    /// Fails planning, sending [`TravelFailed`], rather than following a path of more than
+    /// `max_nodes` points.
+    pub fn with_max_path_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_path_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Makes the traveler a member of `faction`, treating hostile territory according to
+    /// `access`.
+    pub fn with_faction(mut self, faction: u32, access: TerritoryAccess) -> Self {
+        self.faction = Some(faction);
+        self.territory_access = access;
+        self
+    }
+
+    pub fn with_occupancy_weight(mut self, occupancy_weight: u32) -> Self {
+        self.occupancy_weight = occupancy_weight;
+        self
+    }
+
+    /// Follows exactly `path` instead of planning one, for cutscenes and scripted patrols.
+    ///
+    /// The origin and destination are taken from the ends of `path`. Movement, occupancy and
+    /// travel events work as usual, but the path is never replanned; when it's blocked, the
+    /// traveler waits. If consecutive points in `path` aren't connected, or `path` is empty,
+    /// [`TravelFailed`] is sent instead.
+    pub fn with_fixed_path(mut self, path: Vec<u32>) -> Self {
+        self.origin = path.first().copied().unwrap_or(self.origin);
+        self.destination = path.last().copied().unwrap_or(self.destination);
+        self.path = Some(path);
+        self.fixed_path = true;
+        self
+    }
+
+    /// Goes on to visit each of `destinations` in order once `destination` is reached, only
+    /// completing travel at the last of them.
+    ///
+    /// The path to each destination is planned on arrival at the one before, from where the
+    /// traveler stands. If it can't be found, [`TravelFailed`] is sent and the traveler stays
+    /// put.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_ecs::system::Commands;
+    /// # use bevy_navigator::AutoTraveler;
+    /// fn spawn_patrol(mut commands: Commands) {
+    ///     commands.spawn(AutoTraveler::new(1, 10, 2.0).then_visit([20, 30, 1]));
+    /// }
+    /// ```
+    pub fn then_visit(mut self, destinations: impl IntoIterator<Item = u32>) -> Self {
+        self.further_destinations.extend(destinations);
+        self
+    }
+
+    /// See [`AutoTraveler::path_priority`].
+    pub fn with_path_priority(mut self, path_priority: i32) -> Self {
+        self.path_priority = path_priority;
+        self
+    }
+
+    /// Takes the traveler's speed and search settings from the
+    /// [`NavigationProfile`](crate::NavigationProfile) called `name`, when it's added and
+    /// whenever [`NavigationProfiles`](crate::NavigationProfiles) changes.
+    pub fn with_profile(mut self, name: impl Into<String>) -> Self {
+        self.profile = Some(name.into());
+        self
+    }
+
+    /// Keeps the traveler's paths inside `regions`. See [`SearchOptions::within_regions`].
+    pub fn with_allowed_regions(mut self, regions: impl IntoIterator<Item = u32>) -> Self {
+        self.allowed_regions = Some(regions.into_iter().collect());
+        self
+    }
+
+    /// Lets the traveler pass through points with any of the flags in `traversal_mask`, e.g.
+    /// the water points a swimmer can use. See [`NavPoint::flags`](crate::NavPoint::flags).
+    pub fn with_traversal_mask(mut self, traversal_mask: u32) -> Self {
+        self.traversal_mask = traversal_mask;
+        self
+    }
+
+    /// See [`AutoTraveler::size`].
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Plans paths through crowded points at a cost instead of around them. See
+    /// [`SearchOptions::congestion_cost`].
+    pub fn with_congestion_cost(mut self, congestion_cost: f32) -> Self {
+        self.congestion_cost = congestion_cost;
+        self
+    }
+
+    /// See [`AutoTraveler::area_costs`].
+    pub fn with_area_costs(mut self, area_costs: AreaCosts) -> Self {
+        self.area_costs = Some(area_costs);
+        self
+    }
+
     pub fn with_blocked_behavior(mut self, blocked_behavior: BlockedBehavior) -> Self {
         self.blocked_behavior = blocked_behavior;
         self
@@ -98,6 +412,59 @@ impl AutoTraveler {
         self.path_behavior = path_behavior;
         self
     }
+
+    /// Allows the traveler to take a detour around a node which stays blocked for too long.
+    pub fn with_detour(mut self, detour: DetourSettings) -> Self {
+        self.detour = Some(detour);
+        self
+    }
+
+    /// Accepts ending travel at any point within `hops` connections of the destination.
+    pub fn with_goal_tolerance(mut self, hops: u32) -> Self {
+        self.goal_tolerance = hops;
+        self
+    }
+
+    /// See [`AutoTraveler::graph`].
+    pub fn with_graph(mut self, graph: NavGraphId) -> Self {
+        self.graph = Some(graph);
+        self
+    }
+
+    /// The [`SearchOptions`] used when planning this traveler's path.
+    pub fn search_options(&self) -> SearchOptions {
+        SearchOptions {
+            goal_tolerance: self.goal_tolerance,
+            group: self.group,
+            max_nodes: self.max_path_nodes,
+            faction: self.faction,
+            territory: self.territory_access,
+            occupancy_weight: self.occupancy_weight,
+            heuristic: self.heuristic,
+            epsilon: self.epsilon,
+            traversal_mask: self.traversal_mask,
+            turn_penalty: self.turn_penalty,
+            area_costs: self.area_costs.clone(),
+            agent_size: self.size,
+            congestion_cost: self.congestion_cost,
+            allowed_regions: self
+                .allowed_regions
+                .as_ref()
+                .map(|regions| regions.iter().copied().collect()),
+            noise: (self.path_noise > 0.0)
+                .then(|| PathNoise::new(self.path_noise_seed, self.path_noise)),
+            ..Default::default()
+        }
+    }
+
+    /// The [`SearchOptions`] used when planning this traveler's path at [`NavClock`] time `now`.
+    pub fn search_options_at(&self, now: f64) -> SearchOptions {
+        let options = self.search_options();
+        match self.availability {
+            Some(mode) => options.departing_at(now, self.speed, mode),
+            None => options,
+        }
+    }
 }
 
 #[derive(Debug, Component, Reflect, FromReflect)]
@@ -106,92 +473,1546 @@ pub struct NoPath;
 #[derive(Debug, Component, Reflect, FromReflect)]
 pub struct TravelingPaused;
 
+/// Suspends a traveler's movement until [`ResumeTravel`] is added.
+///
+/// Unlike [`TravelingPaused`], suspending releases the point the traveler was moving to, so others
+/// can pass while it's stopped, e.g. to talk to the player. The point it last reached stays
+/// occupied.
+#[derive(Debug, Default, Component, Reflect, FromReflect)]
+pub struct SuspendTravel;
+
+/// Resumes a traveler suspended with [`SuspendTravel`], removing both components.
+///
+/// If the way ahead has since been blocked, the traveler's path is replanned from where it stands.
+#[derive(Debug, Default, Component, Reflect, FromReflect)]
+pub struct ResumeTravel;
+
+/// Only moves a traveler every `interval` frames, trading smoothness for throughput.
+///
+/// The time from skipped frames is carried over, so throttled travelers cover the same distance
+/// overall, just in larger steps. Travelers in the same corridor reservation group tick on the
+/// same frames so they stay in step, while other travelers are spread across frames.
+#[derive(Debug, Default, Clone, Copy, Component, Reflect, FromReflect)]
+pub struct TickInterval {
+    /// The number of frames between updates. 0 and 1 both update every frame.
+    pub interval: u32,
+    skipped: f32,
+}
+
+impl TickInterval {
+    pub fn new(interval: u32) -> Self {
+        Self {
+            interval,
+            skipped: 0.0,
+        }
+    }
+}
+
+/// Requests that a traveler's path be recomputed from wherever it currently is.
+///
+/// If the traveler is partway along a segment, the new path continues from its current position
+/// through whichever end of the segment gives the cheaper route, so the traveler never jumps back
+/// to the node it departed from. The marker is removed once the path has been recomputed.
+#[derive(Debug, Default, Component, Reflect, FromReflect)]
+pub struct Replan;
+
 #[derive(Debug, Component, Reflect, FromReflect)]
 pub struct TravelerPosition {
     pub current_nav_point: u32,
     pub next_nav_point: Option<u32>,
 }
 
-pub(crate) fn compute_initial_path(
-    mut new_travelers_query: Query<(Entity, &mut AutoTraveler), Added<AutoTraveler>>,
-    nav_graph: Res<NavGraph>,
+type NewTravelerQuery<'w, 's, M> = Query<
+    'w,
+    's,
+    (&'static mut AutoTraveler, Option<&'static Transform>),
+    (<M as NavDomain>::Filter, Added<AutoTraveler>),
+>;
+
+pub(crate) fn resolve_traveler_origins<M: NavDomain>(
+    mut new_travelers_query: NewTravelerQuery<M>,
+    graphs: TravelerGraphs<M>,
+) {
+    for (mut auto_traveler, transform) in new_travelers_query.iter_mut() {
+        if !auto_traveler.resolve_origin {
+            continue;
+        }
+
+        let nav_graph = graphs.get(auto_traveler.graph);
+        if let Some(origin) = transform.and_then(|t| nav_graph?.nearest_point(t.translation)) {
+            auto_traveler.origin = origin;
+        }
+        auto_traveler.resolve_origin = false;
+    }
+}
+
+#[derive(WorldQuery)]
+#[world_query(mutable)]
+pub(crate) struct PlanningTravelerQuery {
+    auto_traveler: &'static mut AutoTraveler,
+    history: Option<&'static mut TravelHistory>,
+    warm_start: Option<&'static mut PathWarmStart>,
+    low_fidelity: Option<&'static LowFidelity>,
+    stagger: Option<&'static DepartureStagger>,
+    awaiting_destination: Option<&'static AwaitingDestination>,
+    pending_path: Option<&'static mut PendingPath>,
+}
+
+type PlanningTravelers<'w, 's, M> = ParamSet<
+    'w,
+    's,
+    (
+        Query<
+            'w,
+            's,
+            (Entity, &'static AutoTraveler),
+            (<M as NavDomain>::Filter, Added<AutoTraveler>),
+        >,
+        Query<'w, 's, PlanningTravelerQuery, <M as NavDomain>::Filter>,
+        Query<
+            'w,
+            's,
+            &'static AutoTraveler,
+            (
+                <M as NavDomain>::Filter,
+                Or<(With<TravelerPosition>, With<PendingPath>)>,
+            ),
+        >,
+        Query<
+            'w,
+            's,
+            (Entity, &'static PendingPath, Option<&'static AutoTraveler>),
+            <M as NavDomain>::Filter,
+        >,
+    ),
+>;
+
+/// The graphs asynchronous searches run against, with the topology version they were taken at.
+type GraphSnapshots<M> = HashMap<Option<NavGraphId>, (u64, Arc<NavGraph<M>>)>;
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn compute_initial_path<M: NavDomain>(
+    mut travelers: PlanningTravelers<M>,
+    mut queue: ResMut<PathRequestQueue<M>>,
+    mut graphs: TravelerGraphsMut<M>,
+    clock: Res<NavClock>,
+    time: Res<Time>,
+    mut failed_events: EventWriter<TravelFailed<M>>,
+    mut queued_events: EventWriter<DestinationQueued<M>>,
     mut commands: Commands,
+    mut departures: Local<DepartureSlots>,
+    mut path_cache: Option<ResMut<PathCache<M>>>,
+    mut snapshots: Local<GraphSnapshots<M>>,
 ) {
-    for (entity, mut auto_traveler) in new_travelers_query.iter_mut() {
-        if let Some(path) = nav_graph.find_path(auto_traveler.origin, auto_traveler.destination) {
+    for (entity, auto_traveler) in travelers.p0().iter() {
+        queue.push(entity, auto_traveler, graphs.get(auto_traveler.graph));
+    }
+
+    // Planned travelers hold a slot of their destination's quota.
+    let mut heading_to = HashMap::<(Option<NavGraphId>, u32), u32>::new();
+    if graphs
+        .iter_mut()
+        .any(|(_, nav_graph)| !nav_graph.destination_quotas.is_empty())
+    {
+        for auto_traveler in travelers.p2().iter() {
+            let key = (auto_traveler.graph, auto_traveler.destination);
+            *heading_to.entry(key).or_default() += 1;
+        }
+    }
+
+    // Finished searches are applied on top of the paths computed this frame.
+    let mut finished = Vec::new();
+    for (entity, pending_path, auto_traveler) in travelers.p3().iter() {
+        if auto_traveler.is_none() {
+            commands.entity(entity).remove::<PendingPath>();
+        } else if pending_path.task.is_finished() {
+            finished.push(entity);
+        }
+    }
+
+    let mut travelers_query = travelers.p1();
+    queue.start_budget();
+    let mut deferred = Vec::new();
+    loop {
+        let entity = match finished.pop() {
+            Some(entity) => entity,
+            None if queue.has_budget() => {
+                let Some(entity) = queue.pop() else {
+                    break;
+                };
+                entity
+            }
+            None => break,
+        };
+        // The traveler may have been despawned or had its AutoTraveler removed while queued.
+        let Ok(PlanningTravelerQueryItem {
+            mut auto_traveler,
+            history,
+            mut warm_start,
+            low_fidelity,
+            stagger,
+            awaiting_destination,
+            pending_path,
+        }) = travelers_query.get_mut(entity)
+        else {
+            continue;
+        };
+
+        let graph = auto_traveler.graph;
+        let Some(nav_graph) = graphs.get_mut(graph) else {
+            info!("Graph {:?} not found", graph);
+            commands
+                .entity(entity)
+                .remove::<PendingPath>()
+                .insert(NoPath);
+            failed_events.send(TravelFailed::new(
+                entity,
+                auto_traveler.origin,
+                auto_traveler.destination,
+            ));
+            continue;
+        };
+
+        let from_task = pending_path.is_some();
+        let (claimed_origin, planned) = if let Some(mut pending_path) = pending_path {
+            let Some(planned) = pending_path.poll() else {
+                continue;
+            };
+            commands.entity(entity).remove::<PendingPath>();
+            (pending_path.claimed_origin, planned)
+        } else {
+            let claimed_origin = match auto_traveler.origin_claim {
+                OriginClaim::Disabled => false,
+                _ if low_fidelity.is_some() => false,
+                _ if nav_graph.occupy_as_weighted(
+                    auto_traveler.origin,
+                    auto_traveler.group,
+                    auto_traveler.occupancy_weight,
+                ) =>
+                {
+                    true
+                }
+                OriginClaim::Queue => {
+                    deferred.push(entity);
+                    continue;
+                }
+                OriginClaim::Fail => {
+                    info!("Origin is fully occupied");
+                    commands.entity(entity).insert(NoPath);
+                    failed_events.send(TravelFailed::new(
+                        entity,
+                        auto_traveler.origin,
+                        auto_traveler.destination,
+                    ));
+                    continue;
+                }
+            };
+
+            if auto_traveler.path_noise > 0.0 && auto_traveler.path_noise_seed == 0 {
+                auto_traveler.path_noise_seed = entity.to_bits();
+            }
+
+            if let Some(position) = auto_traveler
+                .destination_position
+                .filter(|_| !auto_traveler.fixed_path)
+            {
+                if let Some(destination) =
+                    nav_graph.pick_reachable_point(auto_traveler.origin, position)
+                {
+                    auto_traveler.destination = destination;
+                }
+            }
+
+            let destination = auto_traveler.destination;
+            let quota_full = nav_graph
+                .destination_quota(destination)
+                .is_some_and(|quota| {
+                    heading_to.get(&(graph, destination)).copied().unwrap_or(0) >= quota
+                });
+            if quota_full {
+                if claimed_origin {
+                    nav_graph
+                        .unoccupy_weighted(auto_traveler.origin, auto_traveler.occupancy_weight);
+                }
+                if awaiting_destination.is_none() {
+                    info!("Destination quota is full");
+                    commands.entity(entity).insert(AwaitingDestination);
+                    queued_events.send(DestinationQueued::new(entity, destination));
+                }
+                deferred.push(entity);
+                continue;
+            }
+            // Travelers waiting on their destination don't use up the budget.
+            queue.budget.searches += 1;
+
+            let mut options = auto_traveler.search_options_at(clock.now);
+            options.ignore_occupancy = low_fidelity.is_some();
+            if queue.asynchronous && !auto_traveler.fixed_path {
+                // Copying the graph is expensive, so snapshots are only retaken when its topology
+                // changes. Their occupancy goes stale, but movement rechecks it anyway.
+                let version = nav_graph.topology_version();
+                let (snapshot_version, snapshot) = snapshots
+                    .entry(graph)
+                    .or_insert_with(|| (version, Arc::new(nav_graph.clone())));
+                if *snapshot_version != version {
+                    *snapshot_version = version;
+                    *snapshot = Arc::new(nav_graph.clone());
+                }
+                let snapshot = snapshot.clone();
+                let (origin, destination) = (auto_traveler.origin, auto_traveler.destination);
+                let task = AsyncComputeTaskPool::get()
+                    .spawn(async move { snapshot.find_path_with(origin, destination, &options) });
+                commands.entity(entity).insert(PendingPath {
+                    task,
+                    claimed_origin,
+                });
+                *heading_to.entry((graph, destination)).or_default() += 1;
+                continue;
+            }
+            let planned = if auto_traveler.fixed_path {
+                auto_traveler
+                    .path
+                    .take()
+                    .filter(|path| !path.is_empty() && nav_graph.path_cost(path).is_some())
+            } else {
+                // The cache only follows the domain's own graph.
+                plan_path(
+                    nav_graph,
+                    auto_traveler.origin,
+                    auto_traveler.destination,
+                    &options,
+                    warm_start.as_deref_mut(),
+                    path_cache.as_deref_mut().filter(|_| graph.is_none()),
+                    &mut queue.budget.expanded,
+                )
+            };
+            (claimed_origin, planned)
+        };
+        if let Some(path) = planned {
             commands.entity(entity).insert(TravelerPosition {
                 current_nav_point: auto_traveler.origin,
                 next_nav_point: None,
             });
+            if let Some(mut history) = history {
+                history.record(auto_traveler.origin, time.elapsed_seconds_f64());
+            }
+            if let Some(stagger) = stagger {
+                let departs_at =
+                    departures.book(auto_traveler.origin, stagger, time.elapsed_seconds_f64());
+                commands
+                    .entity(entity)
+                    .insert(AwaitingDeparture { departs_at });
+            }
+            if awaiting_destination.is_some() {
+                commands.entity(entity).remove::<AwaitingDestination>();
+            }
+            if !auto_traveler.fixed_path {
+                if let Some(cost) = nav_graph.path_cost(&path) {
+                    nav_graph.calibrate_heuristic(path[0], path[path.len() - 1], cost);
+                }
+            }
+            // Searches started on the task pool were counted when they were started.
+            if !from_task {
+                let key = (graph, auto_traveler.destination);
+                *heading_to.entry(key).or_default() += 1;
+            }
             info!("Found path: {:?}", &path);
             auto_traveler.path = Some(path);
         } else {
             info!("No path found");
+            if claimed_origin {
+                nav_graph.unoccupy_weighted(auto_traveler.origin, auto_traveler.occupancy_weight);
+            }
             commands.entity(entity).insert(NoPath);
+            failed_events.send(TravelFailed::new(
+                entity,
+                auto_traveler.origin,
+                auto_traveler.destination,
+            ));
+        }
+    }
+
+    // Travelers waiting on their origin or destination are queued again, to be retried next
+    // frame.
+    for entity in deferred {
+        if let Ok(item) = travelers_query.get(entity) {
+            queue.push(
+                entity,
+                item.auto_traveler,
+                graphs.get(item.auto_traveler.graph),
+            );
         }
     }
 }
 
-pub(crate) fn move_travelers(
-    mut moving_travelers_query: Query<
-        (
-            Entity,
-            &mut Transform,
-            &mut AutoTraveler,
-            &mut TravelerPosition,
-        ),
-        Without<TravelingPaused>,
-    >,
-    mut nav_graph: ResMut<NavGraph>,
+#[derive(WorldQuery)]
+#[world_query(mutable)]
+pub(crate) struct MovingTravelerQuery {
+    entity: Entity,
+    transform: &'static mut Transform,
+    auto_traveler: &'static mut AutoTraveler,
+    traveler_position: &'static mut TravelerPosition,
+    history: Option<&'static mut TravelHistory>,
+    warm_start: Option<&'static mut PathWarmStart>,
+    cooperative: Option<&'static CooperativePlanning>,
+    tick: Option<&'static mut TickInterval>,
+    low_fidelity: Option<&'static mut LowFidelity>,
+    speed_modifiers: Option<&'static SpeedModifiers>,
+}
+
+type MovingTravelerFilter<M> = (
+    <M as NavDomain>::Filter,
+    Without<TravelingPaused>,
+    Without<SuspendTravel>,
+    Without<TurnBased>,
+    Without<AwaitingDeparture>,
+);
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn move_travelers<M: NavDomain>(
+    mut moving_travelers_query: Query<MovingTravelerQuery, MovingTravelerFilter<M>>,
+    mut graphs: TravelerGraphsMut<M>,
+    lanes: Res<TrafficLanes>,
+    clock: Res<NavClock>,
+    lod_policy: Res<LodPolicy>,
     time: Res<Time>,
+    mut completed_events: EventWriter<TravelCompleted<M>>,
+    mut failed_events: EventWriter<TravelFailed<M>>,
+    mut substituted_events: EventWriter<DestinationSubstituted<M>>,
     mut commands: Commands,
+    mut frame: Local<u32>,
 ) {
-    for (entity, mut transform, mut auto_traveler, mut traveler_position) in
-        moving_travelers_query.iter_mut()
+    *frame = frame.wrapping_add(1);
+    for MovingTravelerQueryItem {
+        entity,
+        mut transform,
+        mut auto_traveler,
+        mut traveler_position,
+        mut history,
+        mut warm_start,
+        cooperative,
+        mut tick,
+        mut low_fidelity,
+        speed_modifiers,
+    } in moving_travelers_query.iter_mut()
     {
+        // Travelers wait for their graph to be back.
+        let Some(nav_graph) = graphs.get_mut(auto_traveler.graph) else {
+            continue;
+        };
+        let mut delta_seconds = time.delta_seconds();
+        if let Some(tick) = tick.as_mut() {
+            tick.skipped += delta_seconds;
+            let phase = auto_traveler.group.unwrap_or_else(|| entity.index());
+            if frame.wrapping_add(phase) % tick.interval.max(1) != 0 {
+                continue;
+            }
+            delta_seconds = std::mem::take(&mut tick.skipped);
+        }
+        if let Some(low_fidelity) = low_fidelity.as_mut() {
+            low_fidelity.elapsed += delta_seconds;
+            if low_fidelity.elapsed < lod_policy.update_interval {
+                continue;
+            }
+            delta_seconds = std::mem::take(&mut low_fidelity.elapsed);
+        }
+        let low_fidelity = low_fidelity.is_some();
+
         let mut should_advance = false;
+        let mut blocked_time = 0.0;
+        let mut detour = None;
+        let mut substitute = None;
         if let Some(path) = auto_traveler.path.as_ref() {
+            if auto_traveler.current_index + 1 >= path.len()
+                && !auto_traveler.further_destinations.is_empty()
+            {
+                let destination = auto_traveler.further_destinations.remove(0);
+                info!(
+                    "Reached {}, heading on to {}",
+                    auto_traveler.destination, destination
+                );
+                auto_traveler.destination = destination;
+                auto_traveler.fixed_path = false;
+                auto_traveler.blocked_time = 0.0;
+                if !replan(
+                    nav_graph,
+                    &mut auto_traveler,
+                    &mut traveler_position,
+                    transform.translation,
+                    clock.now,
+                ) {
+                    info!("No path found");
+                    auto_traveler.path = None;
+                    commands.entity(entity).insert(NoPath);
+                    failed_events.send(TravelFailed::new(
+                        entity,
+                        traveler_position.current_nav_point,
+                        destination,
+                    ));
+                }
+                continue;
+            }
             if auto_traveler.current_index + 1 >= path.len() {
+                if let Some(facing) = auto_traveler.arrival_facing {
+                    transform.rotation = facing;
+                }
                 commands.entity(entity).remove::<AutoTraveler>();
+                completed_events.send(TravelCompleted::new(
+                    entity,
+                    traveler_position.current_nav_point,
+                ));
                 continue;
             }
 
-            if traveler_position.next_nav_point.is_none() {
-                if nav_graph.occupy(path[auto_traveler.current_index + 1]) {
-                    traveler_position.next_nav_point = Some(path[auto_traveler.current_index + 1]);
+            let holding = cooperative.is_some_and(|cooperative| {
+                cooperative.holds(
+                    path,
+                    auto_traveler.current_index,
+                    time.elapsed_seconds_f64(),
+                )
+            });
+            if traveler_position.next_nav_point.is_none() && !holding {
+                let next = path[auto_traveler.current_index + 1];
+                if nav_graph.is_open(traveler_position.current_nav_point, next, clock.now)
+                    && !nav_graph.is_point_reserved_against(next, entity)
+                    && nav_graph.may_enter(
+                        next,
+                        auto_traveler.faction,
+                        auto_traveler.territory_access,
+                    )
+                    && (low_fidelity
+                        || nav_graph.occupy_as_weighted(
+                            next,
+                            auto_traveler.group,
+                            auto_traveler.occupancy_weight,
+                        ))
+                {
+                    traveler_position.next_nav_point = Some(next);
                 } else {
                     // determine based on BlockedBehavior
                     info!("Travel blocked");
-                    continue;
+                    blocked_time = auto_traveler.blocked_time + delta_seconds;
+                    if let (DestinationBehavior::WithinRadius(radius), true, false, false) = (
+                        auto_traveler.destination_behavior,
+                        auto_traveler.current_index + 2 == path.len(),
+                        auto_traveler.fixed_path,
+                        low_fidelity
+                            || nav_graph.can_occupy_weighted(next, auto_traveler.occupancy_weight),
+                    ) {
+                        substitute = substitute_destination(
+                            nav_graph,
+                            traveler_position.current_nav_point,
+                            next,
+                            radius,
+                            &auto_traveler.search_options_at(clock.now),
+                        );
+                    }
+                    if substitute.is_some() {
+                        blocked_time = 0.0;
+                    } else if let (BlockedBehavior::Adaptive, false) =
+                        (auto_traveler.blocked_behavior, auto_traveler.fixed_path)
+                    {
+                        let expected_wait = nav_graph.expected_wait(next);
+                        let reconsider = auto_traveler.blocked_time == 0.0
+                            || expected_wait.is_some_and(|wait| {
+                                wait > 0.0
+                                    && (auto_traveler.blocked_time / wait).floor()
+                                        < (blocked_time / wait).floor()
+                            });
+                        if reconsider {
+                            detour = weigh_detour(
+                                nav_graph,
+                                &path[auto_traveler.current_index..],
+                                auto_traveler.search_options_at(clock.now),
+                                expected_wait.map(|wait| wait * auto_traveler.speed),
+                            );
+                        }
+                    } else if let Some(settings) =
+                        auto_traveler.detour.filter(|_| !auto_traveler.fixed_path)
+                    {
+                        if blocked_time >= settings.wait_threshold {
+                            detour = find_detour(
+                                nav_graph,
+                                &path[auto_traveler.current_index..],
+                                auto_traveler.search_options_at(clock.now),
+                                settings,
+                            );
+                            // Either way, wait out another full threshold before checking again.
+                            blocked_time = 0.0;
+                        }
+                    }
                 }
             }
 
             if let (Some(from), Some(to)) = (
                 nav_graph.get_nav_point(traveler_position.current_nav_point),
-                nav_graph.get_nav_point(traveler_position.next_nav_point.unwrap()),
+                traveler_position
+                    .next_nav_point
+                    .and_then(|next| nav_graph.get_nav_point(next)),
             ) {
-                let direction = (to.location() - from.location()).normalize();
-                let movement =
-                    direction * auto_traveler.speed * from.speed_modifier() * time.delta_seconds();
+                let on_last_segment = auto_traveler.current_index + 2 == path.len();
+                let mut target = to.location();
+                if !on_last_segment {
+                    target += lanes.offset_for((to.location() - from.location()).normalize());
+                }
+
+                // Head for the target from wherever the traveler actually is, so lane changes
+                // and re-plans don't cause it to jump.
+                let direction = (target - transform.translation).normalize_or_zero();
+                let speed = match nav_graph.connection_duration(from.id(), to.id()) {
+                    // Cross at the pace which takes the whole duration from one end to the other.
+                    Some(duration) => from.location().distance(target) / duration.max(f32::EPSILON),
+                    None => {
+                        let length = from.location().distance(target);
+                        let progress = if length > 0.0 {
+                            1.0 - transform.translation.distance(target) / length
+                        } else {
+                            1.0
+                        };
+                        let model = nav_graph.edge_speed_model();
+                        let area_factor = auto_traveler.area_costs.as_ref().map_or(1.0, |costs| {
+                            costs.speed_factor(model, from.area(), to.area(), progress)
+                        });
+                        auto_traveler.speed
+                            * speed_modifiers.map_or(1.0, SpeedModifiers::multiplier)
+                            * model.at(from.speed_modifier(), to.speed_modifier(), progress)
+                            * area_factor
+                    }
+                };
+                let movement = direction * speed * delta_seconds;
 
                 let movement_len_squared = movement.length_squared();
-                let dist_squared = transform.translation.distance_squared(to.location());
+                let dist_squared = transform.translation.distance_squared(target);
 
                 // Check if we're going to overshoot or are within the move threshold and just snap to the destination instead.
-                if movement_len_squared >= dist_squared || dist_squared <= 0.001_f32.powi(2) {
-                    transform.translation = to.location();
+                let arriving =
+                    movement_len_squared >= dist_squared || dist_squared <= 0.001_f32.powi(2);
+                if let Some(facing) = auto_traveler.arrival_facing.filter(|_| on_last_segment) {
+                    // Turn by the same fraction of the remaining angle as of the remaining
+                    // distance covered this frame, so the rotation lands exactly on arrival.
+                    let fraction = if arriving {
+                        1.0
+                    } else {
+                        (movement_len_squared / dist_squared).sqrt()
+                    };
+                    transform.rotation = transform.rotation.slerp(facing, fraction);
+                }
+
+                if arriving {
+                    transform.translation = target;
                     should_advance = true;
-                    nav_graph.unoccupy(traveler_position.current_nav_point);
-                    traveler_position.current_nav_point = path[auto_traveler.current_index + 1];
+                    if !low_fidelity {
+                        nav_graph.unoccupy_weighted_at(
+                            traveler_position.current_nav_point,
+                            auto_traveler.occupancy_weight,
+                            time.elapsed_seconds_f64(),
+                        );
+                    }
+                    let next = path[auto_traveler.current_index + 1];
+                    nav_graph.record_traversal(traveler_position.current_nav_point, next);
+                    traveler_position.current_nav_point = next;
                     traveler_position.next_nav_point = None;
+                    if let Some(history) = history.as_mut() {
+                        history.record(
+                            traveler_position.current_nav_point,
+                            time.elapsed_seconds_f64(),
+                        );
+                    }
                 } else {
                     transform.translation += movement;
                 }
             }
         }
 
+        if auto_traveler.blocked_time != blocked_time {
+            auto_traveler.blocked_time = blocked_time;
+        }
+        if let Some((substitute, path)) = substitute {
+            info!("Destination full, ending travel at {} instead", substitute);
+            substituted_events.send(DestinationSubstituted::new(
+                entity,
+                auto_traveler.destination,
+                substitute,
+            ));
+            auto_traveler.destination = substitute;
+            auto_traveler.path = Some(path);
+            auto_traveler.current_index = 0;
+        }
+        if let Some(detour) = detour {
+            info!("Taking detour: {:?}", &detour);
+            auto_traveler.blocked_time = 0.0;
+            auto_traveler.path = Some(detour);
+            auto_traveler.current_index = 0;
+        }
         if should_advance {
             auto_traveler.current_index += 1;
+
+            if let (PathBehavior::ProgressiveRecompute, false) =
+                (auto_traveler.path_behavior, auto_traveler.fixed_path)
+            {
+                let mut options = auto_traveler.search_options_at(clock.now);
+                options.ignore_occupancy = low_fidelity;
+                if let Some(path) = plan_path(
+                    nav_graph,
+                    traveler_position.current_nav_point,
+                    auto_traveler.destination,
+                    &options,
+                    warm_start.as_deref_mut(),
+                    None,
+                    &mut 0,
+                ) {
+                    auto_traveler.path = Some(path);
+                    auto_traveler.current_index = 0;
+                }
+            }
+        }
+    }
+}
+
+/// Plans a path, reusing the traveler's [`PathWarmStart`] if it has one.
+fn plan_path<M: NavDomain>(
+    nav_graph: &NavGraph<M>,
+    a: u32,
+    b: u32,
+    options: &SearchOptions,
+    warm_start: Option<&mut PathWarmStart>,
+    path_cache: Option<&mut PathCache<M>>,
+    expanded: &mut usize,
+) -> Option<Vec<u32>> {
+    if let Some(warm_start) = warm_start {
+        return warm_start.find_path_counted(nav_graph, a, b, options, expanded);
+    }
+    match path_cache {
+        Some(path_cache) => path_cache.find_path_counted(nav_graph, a, b, options, expanded),
+        None => nav_graph
+            .find_path_counted(a, b, options, expanded)
+            .map(PathResult::into_path),
+    }
+}
+
+type SuspendedTravelerQuery<'w, 's, M> = Query<
+    'w,
+    's,
+    (
+        &'static AutoTraveler,
+        &'static mut TravelerPosition,
+        Option<&'static LowFidelity>,
+    ),
+    (<M as NavDomain>::Filter, Added<SuspendTravel>),
+>;
+
+pub(crate) fn suspend_travelers<M: NavDomain>(
+    mut suspended_query: SuspendedTravelerQuery<M>,
+    mut graphs: TravelerGraphsMut<M>,
+) {
+    for (auto_traveler, mut traveler_position, low_fidelity) in suspended_query.iter_mut() {
+        let Some(next) = traveler_position.next_nav_point.take() else {
+            continue;
+        };
+        // Low fidelity travelers don't hold the points they pass through.
+        if let (None, Some(nav_graph)) = (low_fidelity, graphs.get_mut(auto_traveler.graph)) {
+            nav_graph.unoccupy_weighted(next, auto_traveler.occupancy_weight);
+        }
+    }
+}
+
+/// Travelers of the domain `M` marked with `T`.
+type MarkedTravelerQuery<'w, 's, M, T> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut AutoTraveler,
+        &'static mut TravelerPosition,
+        &'static Transform,
+    ),
+    (<M as NavDomain>::Filter, With<T>),
+>;
+
+pub(crate) fn resume_travelers<M: NavDomain>(
+    mut resumed_query: MarkedTravelerQuery<M, ResumeTravel>,
+    graphs: TravelerGraphs<M>,
+    clock: Res<NavClock>,
+    mut commands: Commands,
+) {
+    for (entity, mut auto_traveler, mut traveler_position, transform) in resumed_query.iter_mut() {
+        commands
+            .entity(entity)
+            .remove::<SuspendTravel>()
+            .remove::<ResumeTravel>();
+        let Some(nav_graph) = graphs.get(auto_traveler.graph) else {
+            continue;
+        };
+
+        let Some(path) = auto_traveler.path.as_ref() else {
+            continue;
+        };
+        let Some(next) = path.get(auto_traveler.current_index + 1).copied() else {
+            continue;
+        };
+        let current = traveler_position.current_nav_point;
+        let clear = nav_graph.can_occupy_weighted(next, auto_traveler.occupancy_weight)
+            && !nav_graph.is_reserved_against(next, auto_traveler.group)
+            && nav_graph.is_open(current, next, clock.now);
+        if clear || auto_traveler.fixed_path {
+            // Movement takes the next point again as usual, or waits for it.
+            continue;
+        }
+
+        if !replan(
+            nav_graph,
+            &mut auto_traveler,
+            &mut traveler_position,
+            transform.translation,
+            clock.now,
+        ) {
+            info!("No path found while resuming, keeping the current path");
+        }
+    }
+}
+
+pub(crate) fn replan_travelers<M: NavDomain>(
+    mut replan_query: MarkedTravelerQuery<M, Replan>,
+    graphs: TravelerGraphs<M>,
+    clock: Res<NavClock>,
+    mut commands: Commands,
+) {
+    for (entity, mut auto_traveler, mut traveler_position, transform) in replan_query.iter_mut() {
+        commands.entity(entity).remove::<Replan>();
+        if auto_traveler.fixed_path {
+            info!("Not replanning a fixed path");
+            continue;
+        }
+        let Some(nav_graph) = graphs.get(auto_traveler.graph) else {
+            continue;
+        };
+        if !replan(
+            nav_graph,
+            &mut auto_traveler,
+            &mut traveler_position,
+            transform.translation,
+            clock.now,
+        ) {
+            info!("No path found while replanning, keeping the current path");
+        }
+    }
+}
+
+/// Recomputes a traveler's path from its current position, returning false if no new path
+/// could be found. The existing path is left untouched in that case.
+///
+/// When the traveler is between two nodes, both are already occupied by it, so the new path is
+/// planned from whichever end of the segment is cheaper to continue from. Turning back is done by
+/// swapping the segment's ends rather than moving the traveler, so its position stays continuous.
+pub(crate) fn replan<M: NavDomain>(
+    nav_graph: &NavGraph<M>,
+    auto_traveler: &mut AutoTraveler,
+    traveler_position: &mut TravelerPosition,
+    translation: Vec3,
+    now: f64,
+) -> bool {
+    let options = auto_traveler.search_options_at(now);
+    let destination = auto_traveler.destination;
+    let current = traveler_position.current_nav_point;
+
+    let Some(next) = traveler_position.next_nav_point else {
+        let Some(path) = nav_graph.find_path_with(current, destination, &options) else {
+            return false;
+        };
+        auto_traveler.path = Some(path);
+        auto_traveler.current_index = 0;
+        return true;
+    };
+
+    let (Some(current_point), Some(next_point)) = (
+        nav_graph.get_nav_point(current),
+        nav_graph.get_nav_point(next),
+    ) else {
+        return false;
+    };
+    let segment_cost = nav_graph.path_cost(&[current, next]).unwrap_or(0) as f32;
+    let segment_len = current_point.location().distance(next_point.location());
+    let progress = if segment_len > 0.0 {
+        (translation.distance(current_point.location()) / segment_len).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+
+    // The cost of each candidate includes the part of the current segment still to be covered to
+    // reach its starting node.
+    let candidate = |start: u32, partial_cost: f32| {
+        let path = nav_graph.find_path_with(start, destination, &options)?;
+        let cost = nav_graph.path_cost(&path)? as f32 + partial_cost;
+        Some((path, cost))
+    };
+    let forward = candidate(next, (1.0 - progress) * segment_cost);
+    let backward = candidate(current, progress * segment_cost);
+
+    let turn_back = match (&forward, &backward) {
+        (Some((_, forward_cost)), Some((_, backward_cost))) => backward_cost < forward_cost,
+        (None, Some(_)) => true,
+        (_, None) => false,
+    };
+
+    if turn_back {
+        let (path, _) = backward.unwrap();
+        traveler_position.current_nav_point = next;
+        traveler_position.next_nav_point = Some(current);
+        let mut new_path = Vec::with_capacity(path.len() + 1);
+        new_path.push(next);
+        new_path.extend(path);
+        auto_traveler.path = Some(new_path);
+    } else if let Some((path, _)) = forward {
+        let mut new_path = Vec::with_capacity(path.len() + 1);
+        new_path.push(current);
+        new_path.extend(path);
+        auto_traveler.path = Some(new_path);
+    } else {
+        return false;
+    }
+    auto_traveler.current_index = 0;
+    true
+}
+
+/// Looks for a route around the blocked next node of `remaining_path`, which starts at the
+/// traveler's current node.
+///
+/// The detour is only returned if its cost is within [`DetourSettings::max_cost_factor`] of the
+/// cost of the remaining direct route.
+fn find_detour<M: NavDomain>(
+    nav_graph: &NavGraph<M>,
+    remaining_path: &[u32],
+    options: SearchOptions,
+    settings: DetourSettings,
+) -> Option<Vec<u32>> {
+    let direct_cost = nav_graph.path_cost(remaining_path)?;
+    let options = options.excluding([remaining_path[1]]);
+    let detour = nav_graph.find_path_with(
+        remaining_path[0],
+        remaining_path[remaining_path.len() - 1],
+        &options,
+    )?;
+    let detour_cost = nav_graph.path_cost(&detour)?;
+
+    (detour_cost as f32 <= direct_cost as f32 * settings.max_cost_factor).then_some(detour)
+}
+
+/// Picks the free point closest to the full `destination`, within `radius` of it, which the
+/// traveler at `current` can reach, returning it with the path there.
+fn substitute_destination<M: NavDomain>(
+    nav_graph: &NavGraph<M>,
+    current: u32,
+    destination: u32,
+    radius: f32,
+    options: &SearchOptions,
+) -> Option<(u32, Vec<u32>)> {
+    let location = nav_graph.get_nav_point(destination)?.location();
+    nav_graph
+        .points_within_radius(location, radius)
+        .into_iter()
+        .filter(|id| *id != destination)
+        .find_map(|id| {
+            if id == current {
+                return Some((id, vec![current]));
+            }
+            if !nav_graph.can_occupy_weighted(id, options.occupancy_weight.max(1)) {
+                return None;
+            }
+            Some((id, nav_graph.find_path_with(current, id, options)?))
+        })
+}
+
+/// Looks for a route around the blocked next node of `remaining_path`, returning it if its extra
+/// cost is less than the cost of waiting `wait_distance`, or if no wait is known.
+fn weigh_detour<M: NavDomain>(
+    nav_graph: &NavGraph<M>,
+    remaining_path: &[u32],
+    options: SearchOptions,
+    wait_distance: Option<f32>,
+) -> Option<Vec<u32>> {
+    let direct_cost = nav_graph.path_cost(remaining_path)?;
+    let options = options.excluding([remaining_path[1]]);
+    let detour = nav_graph.find_path_with(
+        remaining_path[0],
+        remaining_path[remaining_path.len() - 1],
+        &options,
+    )?;
+    let extra_cost = nav_graph.path_cost(&detour)?.saturating_sub(direct_cost);
+
+    let wait_cost = wait_distance.map_or(u32::MAX, |d| (d * d * 100.0) as u32);
+    (extra_cost < wait_cost).then_some(detour)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::{event::Events, schedule::IntoSystemDescriptor};
+    use bevy_utils::Duration;
+
+    use super::*;
+    use crate::{EdgeSpeedModel, NavPoint};
+
+    #[test]
+    pub fn test_detour_cost_factor() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(0.0, -1.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(-1.0, -1.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(4, Vec3::new(0.0, -2.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(1, 3);
+        nav_graph.connect_points(2, 4);
+        nav_graph.connect_points(3, 4);
+        nav_graph.occupy(2);
+
+        let settings = DetourSettings {
+            wait_threshold: 0.0,
+            max_cost_factor: 1.5,
+        };
+        assert!(find_detour(&nav_graph, &[1, 2, 4], SearchOptions::new(), settings).is_none());
+
+        let settings = DetourSettings {
+            max_cost_factor: 2.0,
+            ..settings
+        };
+        assert_eq!(
+            find_detour(&nav_graph, &[1, 2, 4], SearchOptions::new(), settings).unwrap()[..],
+            [1, 3, 4]
+        );
+    }
+
+    #[test]
+    pub fn test_origin_from_transform() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(4.0, 0.0, 0.0), 1.0, 1));
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .add_system(resolve_traveler_origins::<()>);
+        let entity = app
+            .world
+            .spawn((
+                AutoTraveler::from_position(1, 1.0),
+                Transform::from_xyz(3.0, 0.5, 0.0),
+            ))
+            .id();
+        app.update();
+
+        let auto_traveler = app.world.get::<AutoTraveler>(entity).unwrap();
+        assert_eq!(auto_traveler.origin, 2);
+        assert!(!auto_traveler.resolve_origin);
+    }
+
+    #[test]
+    pub fn test_destination_from_position() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(2.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .init_resource::<PathRequestQueue>()
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .init_resource::<LodPolicy>()
+            .add_event::<DestinationQueued>()
+            .add_event::<TravelFailed>()
+            .add_system(compute_initial_path::<()>);
+        let entity = app
+            .world
+            .spawn(AutoTraveler::to_position(1, Vec3::new(2.0, 0.0, 0.0), 1.0))
+            .id();
+        app.update();
+
+        let auto_traveler = app.world.get::<AutoTraveler>(entity).unwrap();
+        assert_eq!(auto_traveler.destination, 2);
+        assert_eq!(auto_traveler.path.as_ref().unwrap()[..], [1, 2]);
+    }
+
+    #[test]
+    pub fn test_replan_mid_segment_turns_back() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(-1.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(1, 3);
+        nav_graph.occupy(1);
+        nav_graph.occupy(2);
+
+        let mut auto_traveler = AutoTraveler::new(1, 3, 1.0);
+        auto_traveler.path = Some(vec![1, 2]);
+        let mut traveler_position = TravelerPosition {
+            current_nav_point: 1,
+            next_nav_point: Some(2),
+        };
+
+        assert!(replan(
+            &nav_graph,
+            &mut auto_traveler,
+            &mut traveler_position,
+            Vec3::new(0.25, 0.0, 0.0),
+            0.0,
+        ));
+        assert_eq!(auto_traveler.path.unwrap()[..], [2, 1, 3]);
+        assert_eq!(auto_traveler.current_index, 0);
+        assert_eq!(traveler_position.current_nav_point, 2);
+        assert_eq!(traveler_position.next_nav_point, Some(1));
+    }
+
+    #[test]
+    pub fn test_zero_length_path_completes() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .init_resource::<PathRequestQueue>()
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .init_resource::<LodPolicy>()
+            .add_event::<DestinationQueued>()
+            .add_event::<TravelFailed>()
+            .add_event::<TravelCompleted>()
+            .add_event::<DestinationSubstituted>()
+            .init_resource::<TrafficLanes>()
+            .add_system(compute_initial_path::<()>.label("compute_path"))
+            .add_system(move_travelers::<()>.after("compute_path"));
+        let entity = app
+            .world
+            .spawn((AutoTraveler::new(1, 1, 1.0), Transform::default()))
+            .id();
+        app.update();
+        app.update();
+
+        assert!(app.world.get::<AutoTraveler>(entity).is_none());
+        let events = app.world.resource::<Events<TravelCompleted>>();
+        let completed: Vec<_> = events.get_reader().iter(events).copied().collect();
+        assert_eq!(completed, [TravelCompleted::new(entity, 1)]);
+    }
+
+    #[test]
+    pub fn test_substitute_destination_when_full() {
+        // A corridor from 1 to 3, with 4 and 5 branching off 2 near the destination.
+        let mut nav_graph = NavGraph::new();
+        for (id, x, y) in [
+            (1, 0.0, 0.0),
+            (2, 1.0, 0.0),
+            (3, 2.0, 0.0),
+            (4, 2.0, 0.5),
+            (5, 2.0, 3.0),
+        ] {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 1));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+        nav_graph.connect_points(2, 4);
+        nav_graph.connect_points(2, 5);
+        nav_graph.occupy(2);
+        nav_graph.occupy(3);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .init_resource::<LodPolicy>()
+            .init_resource::<TrafficLanes>()
+            .add_event::<TravelCompleted>()
+            .add_event::<DestinationSubstituted>()
+            .add_event::<TravelFailed>()
+            .add_system(move_travelers::<()>);
+        let mut auto_traveler = AutoTraveler::new(1, 3, 1.0)
+            .with_destination_behavior(DestinationBehavior::WithinRadius(2.0));
+        auto_traveler.path = Some(vec![1, 2, 3]);
+        auto_traveler.current_index = 1;
+        let entity = app
+            .world
+            .spawn((
+                auto_traveler,
+                TravelerPosition {
+                    current_nav_point: 2,
+                    next_nav_point: None,
+                },
+                Transform::from_xyz(1.0, 0.0, 0.0),
+            ))
+            .id();
+        app.update();
+
+        let auto_traveler = app.world.get::<AutoTraveler>(entity).unwrap();
+        assert_eq!(auto_traveler.destination, 4);
+        assert_eq!(auto_traveler.path.as_deref(), Some(&[2, 4][..]));
+        assert_eq!(auto_traveler.current_index, 0);
+        let events = app.world.resource::<Events<DestinationSubstituted>>();
+        let substituted: Vec<_> = events.get_reader().iter(events).copied().collect();
+        assert_eq!(substituted, [DestinationSubstituted::new(entity, 3, 4)]);
+    }
+
+    #[test]
+    pub fn test_further_destinations() {
+        let mut app = App::new();
+        app.add_plugin(crate::NavigatorPlugin::new())
+            .insert_resource(Time::default());
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        for id in 1..=3 {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
         }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+
+        let entity = app
+            .world
+            .spawn((
+                Transform::from_xyz(2.0, 0.0, 0.0),
+                AutoTraveler::new(2, 3, 1.0).then_visit([1]),
+            ))
+            .id();
+        // Step until travel completes, while the completion event is still around.
+        let start = app.world.resource::<Time>().startup();
+        for frame in 0..40 {
+            app.world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_secs_f32(frame as f32 * 0.25));
+            app.update();
+            if app.world.get::<AutoTraveler>(entity).is_none() {
+                break;
+            }
+        }
+
+        assert!(app.world.get::<AutoTraveler>(entity).is_none());
+        let events = app.world.resource::<Events<TravelCompleted>>();
+        let completed: Vec<_> = events.get_reader().iter(events).copied().collect();
+        assert_eq!(completed, [TravelCompleted::new(entity, 1)]);
+        let nav_graph = app.world.resource::<NavGraph>();
+        let traversals: Vec<_> = [(2, 3), (3, 2), (2, 1)]
+            .iter()
+            .map(|(from, to)| nav_graph.traversal_count(*from, *to))
+            .collect();
+        assert_eq!(traversals, [1, 1, 1]);
+    }
+
+    #[test]
+    pub fn test_origin_claim() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .init_resource::<PathRequestQueue>()
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .init_resource::<LodPolicy>()
+            .add_event::<DestinationQueued>()
+            .add_event::<TravelFailed>()
+            .add_system(compute_initial_path::<()>);
+        let first = app
+            .world
+            .spawn(AutoTraveler::new(1, 2, 1.0).with_origin_claim(OriginClaim::Fail))
+            .id();
+        let second = app
+            .world
+            .spawn(AutoTraveler::new(1, 2, 1.0).with_origin_claim(OriginClaim::Fail))
+            .id();
+        let queued = app
+            .world
+            .spawn(AutoTraveler::new(1, 2, 1.0).with_origin_claim(OriginClaim::Queue))
+            .id();
+        app.update();
+
+        assert!(app.world.get::<AutoTraveler>(first).unwrap().path.is_some());
+        assert!(app.world.get::<NoPath>(second).is_some());
+        assert!(app.world.resource::<PathRequestQueue>().contains(queued));
+        assert!(!app.world.resource::<NavGraph>().can_occupy(1));
+    }
+
+    #[test]
+    pub fn test_tick_interval_compensates_delta() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 2));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(10.0, 0.0, 0.0), 1.0, 2));
+        nav_graph.connect_points(1, 2);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .init_resource::<LodPolicy>()
+            .init_resource::<TrafficLanes>()
+            .add_event::<TravelCompleted>()
+            .add_event::<DestinationSubstituted>()
+            .add_event::<TravelFailed>()
+            .add_system(move_travelers::<()>);
+        let mut spawn = |tick: Option<TickInterval>| {
+            let mut auto_traveler = AutoTraveler::new(1, 2, 1.0).with_group(0);
+            auto_traveler.path = Some(vec![1, 2]);
+            let mut entity = app.world.spawn((
+                auto_traveler,
+                TravelerPosition {
+                    current_nav_point: 1,
+                    next_nav_point: None,
+                },
+                Transform::default(),
+            ));
+            if let Some(tick) = tick {
+                entity.insert(tick);
+            }
+            entity.id()
+        };
+        let every_frame = spawn(None);
+        let throttled = spawn(Some(TickInterval::new(2)));
+
+        let start = bevy_utils::Instant::now();
+        app.world.resource_mut::<Time>().update_with_instant(start);
+        let x = |app: &App, entity| app.world.get::<Transform>(entity).unwrap().translation.x;
+        for frame in 1..=4 {
+            app.world.resource_mut::<Time>().update_with_instant(
+                start + bevy_utils::Duration::from_secs_f32(0.1 * frame as f32),
+            );
+            app.update();
+            if frame % 2 == 1 {
+                assert!(x(&app, throttled) < x(&app, every_frame));
+            } else {
+                assert!((x(&app, throttled) - x(&app, every_frame)).abs() < 0.001);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_fixed_path() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 1.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(3, Vec3::new(2.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+        nav_graph.connect_points(1, 3);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .init_resource::<PathRequestQueue>()
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .add_event::<DestinationQueued>()
+            .add_event::<TravelFailed>()
+            .add_system(compute_initial_path::<()>);
+        let scripted = app
+            .world
+            .spawn(AutoTraveler::default().with_fixed_path(vec![1, 2, 3]))
+            .id();
+        let broken = app
+            .world
+            .spawn(AutoTraveler::default().with_fixed_path(vec![1, 4]))
+            .id();
+        app.update();
+
+        let auto_traveler = app.world.get::<AutoTraveler>(scripted).unwrap();
+        assert_eq!(auto_traveler.path.as_ref().unwrap()[..], [1, 2, 3]);
+        assert_eq!((auto_traveler.origin, auto_traveler.destination), (1, 3));
+        assert!(app.world.get::<NoPath>(broken).is_some());
+    }
+
+    #[test]
+    pub fn test_suspend_releases_next_point() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+        nav_graph.occupy(1);
+        nav_graph.occupy(2);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .init_resource::<NavClock>()
+            .add_system(suspend_travelers::<()>)
+            .add_system(resume_travelers::<()>.after(suspend_travelers::<()>));
+        let mut auto_traveler = AutoTraveler::new(1, 2, 1.0);
+        auto_traveler.path = Some(vec![1, 2]);
+        let entity = app
+            .world
+            .spawn((
+                auto_traveler,
+                TravelerPosition {
+                    current_nav_point: 1,
+                    next_nav_point: Some(2),
+                },
+                Transform::from_xyz(0.5, 0.0, 0.0),
+                SuspendTravel,
+            ))
+            .id();
+
+        app.update();
+        assert!(app.world.resource::<NavGraph>().can_occupy(2));
+        assert_eq!(
+            app.world
+                .get::<TravelerPosition>(entity)
+                .unwrap()
+                .next_nav_point,
+            None
+        );
+
+        app.world.entity_mut(entity).insert(ResumeTravel);
+        app.update();
+        assert!(app.world.get::<SuspendTravel>(entity).is_none());
+        assert!(app.world.get::<ResumeTravel>(entity).is_none());
+    }
+
+    #[test]
+    pub fn test_suspend_low_fidelity_keeps_others_points() {
+        let mut nav_graph = NavGraph::new();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(1.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+        // Someone else holds 2, which the low fidelity traveler passes through regardless.
+        nav_graph.occupy(2);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .add_system(suspend_travelers::<()>);
+        let mut auto_traveler = AutoTraveler::new(1, 2, 1.0);
+        auto_traveler.path = Some(vec![1, 2]);
+        let entity = app
+            .world
+            .spawn((
+                auto_traveler,
+                TravelerPosition {
+                    current_nav_point: 1,
+                    next_nav_point: Some(2),
+                },
+                LowFidelity::default(),
+                SuspendTravel,
+            ))
+            .id();
+
+        app.update();
+        assert!(!app.world.resource::<NavGraph>().can_occupy(2));
+        let traveler_position = app.world.get::<TravelerPosition>(entity).unwrap();
+        assert_eq!(traveler_position.next_nav_point, None);
+    }
+
+    #[test]
+    pub fn test_occupancy_weight() {
+        let mut nav_graph = NavGraph::new();
+        for (id, x, y) in [(1, 0.0, 0.0), (2, 1.0, 0.0), (3, 2.0, 0.0), (4, 1.0, 3.0)] {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, y, 0.0), 1.0, 3));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+        nav_graph.connect_points(1, 4);
+        nav_graph.connect_points(4, 3);
+        nav_graph.occupy_weighted(2, 2);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .init_resource::<PathRequestQueue>()
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .init_resource::<LodPolicy>()
+            .add_event::<DestinationQueued>()
+            .add_event::<TravelFailed>()
+            .add_system(compute_initial_path::<()>);
+        let traveler = |weight| {
+            AutoTraveler::new(1, 3, 1.0)
+                .with_origin_claim(OriginClaim::Fail)
+                .with_occupancy_weight(weight)
+        };
+        let small = app.world.spawn(traveler(1)).id();
+        let large = app.world.spawn(traveler(2)).id();
+        app.update();
+
+        // Only the small traveler fits through the crowded point 2.
+        let path = |entity| app.world.get::<AutoTraveler>(entity).unwrap().path.clone();
+        assert_eq!(path(small), Some(vec![1, 2, 3]));
+        assert_eq!(path(large), Some(vec![1, 4, 3]));
+        let nav_graph = app.world.resource::<NavGraph>();
+        assert_eq!(nav_graph.get_nav_point(1).unwrap().current_occupancy(), 3);
+    }
+
+    #[test]
+    pub fn test_fixed_duration_connection() {
+        let mut app = App::new();
+        app.add_plugin(crate::NavigatorPlugin::new())
+            .insert_resource(Time::default());
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(0.0, 10.0, 0.0), 1.0, 1));
+        nav_graph.connect_points(1, 2);
+        nav_graph.set_connection_duration(1, 2, Some(4.0));
+
+        // However fast the traveler, the ride takes 4 seconds.
+        let entity = app
+            .world
+            .spawn((Transform::default(), AutoTraveler::new(1, 2, 100.0)))
+            .id();
+        let start = app.world.resource::<Time>().startup();
+        for seconds in [0.0, 1.0, 2.0] {
+            app.world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_secs_f32(seconds));
+            app.update();
+        }
+        let position = app.world.get::<Transform>(entity).unwrap().translation;
+        assert!((position.y - 5.0).abs() < 1e-4);
+    }
+
+    #[test]
+    pub fn test_arrival_facing() {
+        use crate::testing::{test_app, NavTestApp};
+
+        let mut app = test_app();
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        for id in 1..=3 {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(id as f32, 0.0, 0.0), 1.0, 1));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+
+        let facing = Quat::from_rotation_z(2.0);
+        let entity = app
+            .world
+            .spawn((
+                Transform::from_xyz(1.0, 0.0, 0.0),
+                AutoTraveler::new(1, 3, 1.0).with_arrival_facing(facing),
+            ))
+            .id();
+        let rotation = |app: &App| app.world.get::<Transform>(entity).unwrap().rotation;
+
+        // Nothing turns before the last segment, and the turn is underway halfway along it.
+        for seconds in [0.0, 0.5, 0.5] {
+            app.step(seconds);
+        }
+        assert_eq!(rotation(&app), Quat::IDENTITY);
+        app.step(0.5);
+        let turned = rotation(&app).angle_between(Quat::IDENTITY);
+        assert!(turned > 0.1 && turned < 1.9, "{turned}");
+
+        app.advance_until_arrived(entity, 0.1, 5.0);
+        assert_eq!(rotation(&app), facing);
+    }
+
+    #[test]
+    pub fn test_blended_edge_speed() {
+        use crate::testing::{test_app, NavTestApp};
+
+        let mut app = test_app();
+        let mut nav_graph = app.world.resource_mut::<NavGraph>();
+        nav_graph.add_nav_point(NavPoint::new(1, Vec3::new(0.0, 0.0, 0.0), 1.0, 1));
+        nav_graph.add_nav_point(NavPoint::new(2, Vec3::new(10.0, 0.0, 0.0), 4.0, 1));
+        nav_graph.connect_points(1, 2);
+        nav_graph.set_edge_speed_model(EdgeSpeedModel::Blend);
+        let estimate = nav_graph.estimate_travel_time(&[1, 2], 1.0, None).unwrap();
+
+        // Picking up speed on the way takes longer than moving at the destination's pace
+        // throughout, and as long as planned.
+        let entity = app
+            .world
+            .spawn((Transform::default(), AutoTraveler::new(1, 2, 1.0)))
+            .id();
+        let elapsed = app.advance_until_arrived(entity, 0.01, 10.0);
+        assert!(estimate > 2.5);
+        assert!((elapsed - estimate).abs() < 0.05, "{elapsed} vs {estimate}");
     }
 }
@@ -0,0 +1,172 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::{
+    entity::Entity,
+    query::{Changed, Or},
+    system::{Query, RemovedComponents, ResMut, Resource},
+};
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{traveler::TravelerPosition, AutoTraveler, NavDomain};
+
+/// Finds the travelers on or heading to each [`NavPoint`](crate::NavPoint), without scanning every
+/// [`AutoTraveler`]'s path.
+///
+/// Kept up to date by the plugin at the end of each frame, so it reflects where travelers were
+/// after moving. Each [`NavDomain`] has its own index, covering the travelers on its
+/// [`NavGraph`](crate::NavGraph) resource; those on the graphs in
+/// [`NavGraphs`](crate::NavGraphs) aren't indexed.
+#[derive(Debug, Resource)]
+pub struct TravelerIndex<M: NavDomain = ()> {
+    occupying: HashMap<u32, HashSet<Entity>>,
+    heading_to: HashMap<u32, HashSet<Entity>>,
+    /// The points each traveler is indexed under, as `(occupying, heading_to)`.
+    entries: HashMap<Entity, (Vec<u32>, Vec<u32>)>,
+    marker: PhantomData<M>,
+}
+
+impl<M: NavDomain> Default for TravelerIndex<M> {
+    fn default() -> Self {
+        Self {
+            occupying: HashMap::default(),
+            heading_to: HashMap::default(),
+            entries: HashMap::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: NavDomain> TravelerIndex<M> {
+    /// The travelers currently at the specified point, or moving onto it.
+    pub fn occupying(&self, id: u32) -> impl Iterator<Item = Entity> + '_ {
+        self.occupying.get(&id).into_iter().flatten().copied()
+    }
+
+    /// The travelers whose remaining path includes the specified point, including those moving
+    /// onto it.
+    pub fn heading_to(&self, id: u32) -> impl Iterator<Item = Entity> + '_ {
+        self.heading_to.get(&id).into_iter().flatten().copied()
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        let Some((occupying, heading_to)) = self.entries.remove(&entity) else {
+            return;
+        };
+        for (points, index) in [
+            (occupying, &mut self.occupying),
+            (heading_to, &mut self.heading_to),
+        ] {
+            for id in points {
+                if let Some(entities) = index.get_mut(&id) {
+                    entities.remove(&entity);
+                    if entities.is_empty() {
+                        index.remove(&id);
+                    }
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, entity: Entity, occupying: Vec<u32>, heading_to: Vec<u32>) {
+        for id in &occupying {
+            self.occupying.entry(*id).or_default().insert(entity);
+        }
+        for id in &heading_to {
+            self.heading_to.entry(*id).or_default().insert(entity);
+        }
+        self.entries.insert(entity, (occupying, heading_to));
+    }
+}
+
+type ChangedTravelerQuery<'w, 's, M> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static AutoTraveler,
+        Option<&'static TravelerPosition>,
+    ),
+    (
+        <M as NavDomain>::Filter,
+        Or<(Changed<AutoTraveler>, Changed<TravelerPosition>)>,
+    ),
+>;
+
+pub(crate) fn index_travelers<M: NavDomain>(
+    changed_query: ChangedTravelerQuery<M>,
+    removed: RemovedComponents<AutoTraveler>,
+    mut index: ResMut<TravelerIndex<M>>,
+) {
+    for entity in removed.iter() {
+        index.remove(entity);
+    }
+
+    for (entity, auto_traveler, traveler_position) in changed_query.iter() {
+        index.remove(entity);
+        if auto_traveler.graph.is_some() {
+            continue;
+        }
+        let (Some(position), Some(path)) = (traveler_position, auto_traveler.path.as_ref()) else {
+            continue;
+        };
+
+        let mut occupying = vec![position.current_nav_point];
+        occupying.extend(position.next_nav_point);
+        let heading_to = path
+            .get(auto_traveler.current_index + 1..)
+            .unwrap_or_default()
+            .to_vec();
+        index.insert(entity, occupying, heading_to);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, CoreStage};
+
+    use super::*;
+
+    #[test]
+    pub fn test_index_follows_travelers() {
+        let mut app = App::new();
+        app.init_resource::<TravelerIndex>()
+            .add_system_to_stage(CoreStage::PostUpdate, index_travelers::<()>);
+        let mut auto_traveler = AutoTraveler::new(1, 3, 1.0);
+        auto_traveler.path = Some(vec![1, 2, 3]);
+        let traveler = app
+            .world
+            .spawn((
+                auto_traveler,
+                TravelerPosition {
+                    current_nav_point: 1,
+                    next_nav_point: Some(2),
+                },
+            ))
+            .id();
+        app.update();
+
+        let index = app.world.resource::<TravelerIndex>();
+        assert_eq!(index.occupying(2).collect::<Vec<_>>(), [traveler]);
+        assert_eq!(index.heading_to(3).collect::<Vec<_>>(), [traveler]);
+        assert_eq!(index.heading_to(1).count(), 0);
+
+        *app.world.get_mut::<TravelerPosition>(traveler).unwrap() = TravelerPosition {
+            current_nav_point: 2,
+            next_nav_point: None,
+        };
+        app.world
+            .get_mut::<AutoTraveler>(traveler)
+            .unwrap()
+            .current_index = 1;
+        app.update();
+        let index = app.world.resource::<TravelerIndex>();
+        assert_eq!(index.occupying(1).count(), 0);
+        assert_eq!(index.heading_to(2).count(), 0);
+
+        app.world.entity_mut(traveler).remove::<AutoTraveler>();
+        app.update();
+        let index = app.world.resource::<TravelerIndex>();
+        assert_eq!(index.occupying(2).count(), 0);
+        assert_eq!(index.heading_to(3).count(), 0);
+    }
+}
@@ -0,0 +1,255 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::{EventReader, EventWriter},
+    query::{Without, WorldQuery},
+    system::{Commands, Query, Res},
+};
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_time::Time;
+use bevy_transform::prelude::Transform;
+
+use crate::{
+    graphs::TravelerGraphsMut, traveler::TravelerPosition, AutoTraveler, NavClock, NavDomain,
+    SuspendTravel, TravelCompleted, TravelHistory, TravelingPaused,
+};
+
+/// Advances every [`TurnBased`] traveler by one turn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AdvanceTurn;
+
+/// Moves a traveler in discrete turns instead of every frame.
+///
+/// Each [`AdvanceTurn`] event moves the traveler up to `nodes_per_turn` points along its path,
+/// stopping early if the next step would take the total cost for the turn (as measured by
+/// [`NavGraph::path_cost`](crate::NavGraph::path_cost)) over `max_cost_per_turn`, or if the next
+/// point is full. Travelers are placed directly on each point they reach rather than moving
+/// smoothly between them.
+///
+/// Travelers take their turns in order of descending `initiative`, so on contested points the
+/// traveler with the highest initiative moves in first.
+#[derive(Debug, Clone, Copy, Component, Reflect, FromReflect)]
+pub struct TurnBased {
+    pub nodes_per_turn: u32,
+    pub max_cost_per_turn: Option<u32>,
+    pub initiative: i32,
+}
+
+impl Default for TurnBased {
+    fn default() -> Self {
+        Self {
+            nodes_per_turn: 1,
+            max_cost_per_turn: None,
+            initiative: 0,
+        }
+    }
+}
+
+impl TurnBased {
+    pub fn new(nodes_per_turn: u32) -> Self {
+        Self {
+            nodes_per_turn,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_cost_per_turn(mut self, max_cost: u32) -> Self {
+        self.max_cost_per_turn = Some(max_cost);
+        self
+    }
+
+    pub fn with_initiative(mut self, initiative: i32) -> Self {
+        self.initiative = initiative;
+        self
+    }
+}
+
+#[derive(WorldQuery)]
+#[world_query(mutable)]
+pub(crate) struct TurnTravelerQuery {
+    entity: Entity,
+    turn_based: &'static TurnBased,
+    transform: &'static mut Transform,
+    auto_traveler: &'static mut AutoTraveler,
+    traveler_position: &'static mut TravelerPosition,
+    history: Option<&'static mut TravelHistory>,
+}
+
+type TurnTravelerFilter<M> = (
+    <M as NavDomain>::Filter,
+    Without<TravelingPaused>,
+    Without<SuspendTravel>,
+);
+
+pub(crate) fn advance_turn_travelers<M: NavDomain>(
+    mut turns: EventReader<AdvanceTurn>,
+    mut travelers_query: Query<TurnTravelerQuery, TurnTravelerFilter<M>>,
+    mut graphs: TravelerGraphsMut<M>,
+    clock: Res<NavClock>,
+    time: Res<Time>,
+    mut completed_events: EventWriter<TravelCompleted<M>>,
+    mut commands: Commands,
+) {
+    // AutoTraveler removal is deferred, so keep track of who finished during an earlier turn
+    // this frame.
+    let mut finished = Vec::new();
+    for _ in turns.iter() {
+        let mut order: Vec<(i32, Entity)> = travelers_query
+            .iter()
+            .map(|traveler| (traveler.turn_based.initiative, traveler.entity))
+            .collect();
+        order.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        for (_, entity) in order {
+            if finished.contains(&entity) {
+                continue;
+            }
+            let Ok(TurnTravelerQueryItem {
+                entity,
+                turn_based,
+                mut transform,
+                mut auto_traveler,
+                mut traveler_position,
+                mut history,
+            }) = travelers_query.get_mut(entity)
+            else {
+                continue;
+            };
+            let Some(path) = auto_traveler.path.as_ref() else {
+                continue;
+            };
+            let Some(nav_graph) = graphs.get_mut(auto_traveler.graph) else {
+                continue;
+            };
+
+            let mut index = auto_traveler.current_index;
+            let mut steps = 0;
+            let mut cost = 0_u32;
+            while steps < turn_based.nodes_per_turn && index + 1 < path.len() {
+                let (current, next) = (path[index], path[index + 1]);
+                let step_cost = nav_graph.path_cost(&[current, next]).unwrap_or(u32::MAX);
+                if turn_based
+                    .max_cost_per_turn
+                    .is_some_and(|max| cost.saturating_add(step_cost) > max)
+                {
+                    break;
+                }
+                if !nav_graph.is_open(current, next, clock.now)
+                    || nav_graph.is_point_reserved_against(next, entity)
+                    || !nav_graph.occupy_as_weighted(
+                        next,
+                        auto_traveler.group,
+                        auto_traveler.occupancy_weight,
+                    )
+                {
+                    break;
+                }
+
+                nav_graph.unoccupy_weighted_at(
+                    current,
+                    auto_traveler.occupancy_weight,
+                    time.elapsed_seconds_f64(),
+                );
+                cost = cost.saturating_add(step_cost);
+                steps += 1;
+                index += 1;
+                nav_graph.record_traversal(current, next);
+                traveler_position.current_nav_point = next;
+                if let Some(point) = nav_graph.get_nav_point(next) {
+                    transform.translation = point.location();
+                }
+                if let Some(history) = history.as_mut() {
+                    history.record(next, time.elapsed_seconds_f64());
+                }
+            }
+
+            let arrived = index + 1 >= path.len();
+            if index != auto_traveler.current_index {
+                auto_traveler.current_index = index;
+            }
+            if arrived {
+                finished.push(entity);
+                commands.entity(entity).remove::<AutoTraveler>();
+                completed_events.send(TravelCompleted::new(
+                    entity,
+                    traveler_position.current_nav_point,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_math::Vec3;
+
+    use super::*;
+    use crate::{NavGraph, NavPoint};
+
+    #[test]
+    pub fn test_initiative_resolves_contested_points() {
+        let mut nav_graph = NavGraph::new();
+        for (id, x) in [(1, 0.0), (2, 1.0), (3, 2.0)] {
+            nav_graph.add_nav_point(NavPoint::new(id, Vec3::new(x, 0.0, 0.0), 1.0, 1));
+        }
+        nav_graph.connect_points(1, 2);
+        nav_graph.connect_points(2, 3);
+
+        let mut app = App::new();
+        app.insert_resource(nav_graph)
+            .insert_resource(Time::default())
+            .init_resource::<NavClock>()
+            .add_event::<AdvanceTurn>()
+            .add_event::<TravelCompleted>()
+            .add_system(advance_turn_travelers::<()>);
+        let mut spawn = |origin, destination, initiative| {
+            let mut auto_traveler = AutoTraveler::new(origin, destination, 1.0);
+            auto_traveler.path = Some(vec![origin, 2, destination]);
+            app.world
+                .spawn((
+                    auto_traveler,
+                    TurnBased::new(1).with_initiative(initiative),
+                    TravelerPosition {
+                        current_nav_point: origin,
+                        next_nav_point: None,
+                    },
+                    Transform::default(),
+                ))
+                .id()
+        };
+        let slow = spawn(1, 3, 0);
+        let fast = spawn(3, 1, 5);
+
+        // Nothing moves until a turn is advanced.
+        app.update();
+        assert_eq!(
+            app.world
+                .get::<TravelerPosition>(fast)
+                .unwrap()
+                .current_nav_point,
+            3
+        );
+
+        app.world.send_event(AdvanceTurn);
+        app.update();
+        assert_eq!(
+            app.world
+                .get::<TravelerPosition>(fast)
+                .unwrap()
+                .current_nav_point,
+            2
+        );
+        assert_eq!(
+            app.world
+                .get::<TravelerPosition>(slow)
+                .unwrap()
+                .current_nav_point,
+            1
+        );
+        assert_eq!(
+            app.world.get::<Transform>(fast).unwrap().translation,
+            Vec3::new(1.0, 0.0, 0.0)
+        );
+    }
+}